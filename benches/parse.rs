@@ -0,0 +1,75 @@
+#![allow(clippy::unwrap_used)]
+#![allow(missing_docs)]
+#![allow(unused_crate_dependencies)]
+
+// Run with `cargo bench`. A cold parse is a single `str::parse` of a changelog that hasn't been
+// parsed before, which is all a CLI invocation does, and the large changelog is a couple of
+// megabytes so any work that isn't linear in the size of the input stands out. Building the
+// markdown AST is measured on its own as the baseline the rest of parsing adds to.
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use keep_a_changelog_file::Changelog;
+use std::fmt::Write;
+use std::hint::black_box;
+
+const GROUPS: [&str; 6] = [
+    "Added",
+    "Changed",
+    "Deprecated",
+    "Removed",
+    "Fixed",
+    "Security",
+];
+
+fn changelog(releases: usize) -> String {
+    let mut changelog =
+        "# Changelog\n\n## [Unreleased]\n\n### Added\n\n- Added an upcoming feature\n".to_string();
+    let mut links = String::new();
+    for release in (1..=releases).rev() {
+        let _ = write!(changelog, "\n## [{release}.0.0] - 2024-01-01\n");
+        for group in GROUPS {
+            let _ = write!(changelog, "\n### {group}\n\n");
+            for entry in 0..5 {
+                let _ = writeln!(
+                    changelog,
+                    "- {group} entry {entry} for `release {release}` with a [link](https://example.com/{release}/{entry}) and **emphasis**"
+                );
+            }
+        }
+        let _ = writeln!(
+            links,
+            "[{release}.0.0]: https://github.com/org/repo/compare/v{}.0.0...v{release}.0.0",
+            release - 1
+        );
+    }
+    format!("{changelog}\n{links}")
+}
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for (name, releases) in [("typical", 20), ("multi-megabyte", 700)] {
+        let input = changelog(releases);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_function(format!("{name}/markdown"), |b| {
+            b.iter(|| markdown::to_mdast(black_box(&input), &markdown::ParseOptions::default()));
+        });
+        group.bench_function(name, |b| {
+            b.iter(|| black_box(&input).parse::<Changelog>().unwrap());
+        });
+    }
+    group.finish();
+}
+
+fn write(c: &mut Criterion) {
+    let changelog = changelog(700).parse::<Changelog>().unwrap();
+    c.bench_function("write/multi-megabyte", |b| {
+        b.iter(|| black_box(&changelog).to_string());
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = parse, write
+}
+criterion_main!(benches);
@@ -0,0 +1,9 @@
+#![no_main]
+
+use keep_a_changelog_file::Changelog;
+use libfuzzer_sys::fuzz_target;
+
+// any changelog that can be constructed must be written and read back without panicking
+fuzz_target!(|changelog: Changelog| {
+    let _ = changelog.to_string().parse::<Changelog>();
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use keep_a_changelog_file::{Changelog, ParseChangelogOptions};
+use libfuzzer_sys::fuzz_target;
+
+// parsing any input must return an error instead of panicking, in both strict and lenient modes,
+// and a changelog that parsed must parse again after being written out
+fuzz_target!(|input: &str| {
+    if let Ok(changelog) = input.parse::<Changelog>() {
+        let _ = changelog.to_string().parse::<Changelog>();
+    }
+    let _ = Changelog::parse_with_options(input, &ParseChangelogOptions::new().with_lenient(true));
+    let _ = Changelog::diagnose(input);
+});
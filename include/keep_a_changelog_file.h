@@ -0,0 +1,38 @@
+#ifndef KEEP_A_CHANGELOG_FILE_H
+#define KEEP_A_CHANGELOG_FILE_H
+
+/*
+ * C API for keep_a_changelog_file, built with:
+ *
+ *     cargo rustc --release --lib --crate-type cdylib --features capi
+ *
+ * Strings passed in must be nul-terminated UTF-8. Every pointer returned is owned by the caller
+ * and must be released with kacl_free or kacl_free_string.
+ */
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct KaclChangelog KaclChangelog;
+
+/* Parses a changelog. Returns NULL if the input is not a valid changelog. */
+KaclChangelog *kacl_parse(const char *input);
+
+/* Returns the diagnostics for a changelog as a JSON array. */
+char *kacl_diagnostics(const char *input);
+
+/* Writes a parsed changelog back out as markdown. */
+char *kacl_to_string(const KaclChangelog *changelog);
+
+/* Frees a changelog returned by kacl_parse. */
+void kacl_free(KaclChangelog *changelog);
+
+/* Frees a string returned by kacl_diagnostics or kacl_to_string. */
+void kacl_free_string(char *value);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,160 @@
+//! Node.js bindings so JavaScript release tooling uses the same parser as the Rust crate.
+//!
+//! Changelogs cross the boundary as the JSON form of the model from the `json` feature.
+
+use keep_a_changelog_file::{ChangeGroup, Changelog, PromoteOptions};
+use napi::{Error, Result};
+use napi_derive::napi;
+
+/// Parses a changelog into its JSON form, throwing if it isn't a valid changelog.
+#[napi]
+pub fn parse(input: String) -> Result<serde_json::Value> {
+    let changelog = input.parse::<Changelog>().map_err(to_js_error)?;
+    serde_json::to_value(changelog).map_err(to_js_error)
+}
+
+/// Returns the diagnostics for a changelog. An empty array means the changelog is valid.
+#[napi]
+pub fn validate(input: String) -> Result<serde_json::Value> {
+    serde_json::to_value(Changelog::diagnose(&input)).map_err(to_js_error)
+}
+
+/// Options for `promote`, mirroring `PromoteOptions`.
+#[napi(object)]
+pub struct Promote {
+    pub version: String,
+    pub date: Option<String>,
+    pub tag: Option<String>,
+    pub link: Option<String>,
+}
+
+/// Moves the unreleased changes of a changelog into a new release and returns the updated
+/// markdown.
+#[napi]
+pub fn promote(input: String, options: Promote) -> Result<String> {
+    let mut changelog = input.parse::<Changelog>().map_err(to_js_error)?;
+    let mut promote_options = PromoteOptions::new(options.version.parse().map_err(to_js_error)?);
+    if let Some(date) = options.date {
+        promote_options = promote_options.with_date(date.parse().map_err(to_js_error)?);
+    }
+    if let Some(tag) = options.tag {
+        promote_options = promote_options.with_tag(tag.parse().map_err(to_js_error)?);
+    }
+    if let Some(link) = options.link {
+        promote_options = promote_options.with_link(link.parse().map_err(to_js_error)?);
+    }
+    changelog
+        .promote_unreleased(&promote_options)
+        .map_err(to_js_error)?;
+    Ok(changelog.to_string())
+}
+
+/// Writes the JSON form of a changelog returned by `parse` back out as markdown.
+#[napi]
+pub fn render(changelog: serde_json::Value) -> Result<String> {
+    let changelog = serde_json::from_value::<Changelog>(changelog).map_err(to_js_error)?;
+    Ok(changelog.to_string())
+}
+
+/// The result of `format`.
+#[napi(object)]
+pub struct Formatted {
+    pub formatted: String,
+    pub changed: bool,
+}
+
+/// Rewrites a changelog the same way as `keep_a_changelog fmt`, reporting whether anything
+/// changed. Like `fmt`, it throws rather than drop content that isn't part of the changelog model,
+/// such as prose between releases.
+#[napi]
+pub fn format(input: String) -> Result<Formatted> {
+    let formatted = parse_lossless(&input)?.to_string();
+    let changed = formatted != input;
+    Ok(Formatted { formatted, changed })
+}
+
+/// Adds an entry to a change group of the unreleased changes and returns the updated markdown.
+/// The group is one of added, changed, deprecated, removed, fixed, or security. Like
+/// `keep_a_changelog add`, it throws rather than drop content that isn't part of the changelog
+/// model.
+#[napi]
+pub fn add_change(input: String, group: String, entry: String) -> Result<String> {
+    let mut changelog = parse_lossless(&input)?;
+    let group = group.parse::<ChangeGroup>().map_err(to_js_error)?;
+    changelog.unreleased.add(group, entry);
+    Ok(changelog.to_string())
+}
+
+// content that parsing leaves out would be lost by writing the changelog back out, so the
+// changelog is refused instead, the same as the CLI does
+fn parse_lossless(input: &str) -> Result<Changelog> {
+    let dropped = Changelog::dropped_content(input).map_err(to_js_error)?;
+    if !dropped.is_empty() {
+        return Err(Error::from_reason(format!(
+            "The changelog has content that would be lost by rewriting it\n{}",
+            dropped
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n")
+        )));
+    }
+    input.parse::<Changelog>().map_err(to_js_error)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn to_js_error(error: impl std::fmt::Display) -> Error {
+    Error::from_reason(error.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        let formatted =
+            format("# Changelog\n\n## [Unreleased]\n\n### Added\n\n* X\n".into()).unwrap();
+        assert!(formatted.changed);
+        assert!(formatted.formatted.contains("- X\n"));
+    }
+
+    #[test]
+    fn test_format_lossy() {
+        let Err(error) = format(
+            "# Changelog\n\n## [Unreleased]\n\n## [1.0.0] - 2024-01-01\n\nSome prose\n\n### Added\n\n- X\n"
+                .into(),
+        ) else {
+            panic!("Expected the lossy changelog to be refused");
+        };
+        assert!(error
+            .reason
+            .starts_with("The changelog has content that would be lost by rewriting it\n"));
+    }
+
+    #[test]
+    fn test_add_change() {
+        let updated = add_change(
+            "# Changelog\n\n## [Unreleased]\n".into(),
+            "fixed".into(),
+            "Fixed Y".into(),
+        )
+        .unwrap();
+        assert!(updated.contains("## [Unreleased]\n\n### Fixed\n\n- Fixed Y\n"));
+    }
+
+    #[test]
+    fn test_add_change_lossy() {
+        let Err(error) = add_change(
+            "# Changelog\n\n## [Unreleased]\n\n## [Unreleased]\n\n### Added\n\n- X\n".into(),
+            "fixed".into(),
+            "Fixed Y".into(),
+        ) else {
+            panic!("Expected the lossy changelog to be refused");
+        };
+        assert!(error
+            .reason
+            .starts_with("The changelog has content that would be lost by rewriting it\n"));
+    }
+}
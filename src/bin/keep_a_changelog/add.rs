@@ -0,0 +1,148 @@
+use crate::changelog_file::{read_changelog, write_changelog};
+use crate::error::CliError;
+use crate::output::print_json;
+use keep_a_changelog_file::{ChangeGroup, Changelog};
+use std::fmt::Write as _;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::Path;
+
+const CHANGE_GROUPS: [ChangeGroup; 6] = [
+    ChangeGroup::Added,
+    ChangeGroup::Changed,
+    ChangeGroup::Deprecated,
+    ChangeGroup::Removed,
+    ChangeGroup::Fixed,
+    ChangeGroup::Security,
+];
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The type of change: added, changed, deprecated, removed, fixed, or security. Prompted for
+    /// when no entries are given in a terminal.
+    #[arg(long, short)]
+    group: Option<ChangeGroup>,
+    /// The text of each entry to add, in order. Without any entries, a terminal prompts for the
+    /// change group and entries and shows the result before writing it.
+    #[arg(value_parser = parse_entry, requires = "group")]
+    entries: Vec<String>,
+    /// What to print once the entries given as arguments are added.
+    #[arg(long, value_enum, default_value_t = Format::Quiet)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// Nothing.
+    Quiet,
+    /// The Unreleased section with the new entries as JSON.
+    Json,
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let mut changelog = read_changelog(file)?;
+    if args.entries.is_empty() {
+        if !std::io::stdin().is_terminal() {
+            Err(CliError::NoEntries)?;
+        }
+        return prompt(file, &mut changelog, args.group.clone());
+    }
+    let group = args
+        .group
+        .as_ref()
+        .expect("clap requires a group along with the entries");
+    for entry in &args.entries {
+        changelog.unreleased.add(group.clone(), entry);
+    }
+    if let Format::Json = args.format {
+        print_json(file, &changelog.unreleased);
+    }
+    write_changelog(file, &changelog)
+}
+
+// prompts go to stderr so they don't mix with output when stdout is redirected
+fn prompt(
+    file: &Path,
+    changelog: &mut Changelog,
+    group: Option<ChangeGroup>,
+) -> Result<(), CliError> {
+    let mut input = std::io::stdin().lock();
+    let mut output = std::io::stderr();
+    let mut read_line = |question: &str| -> Option<String> {
+        let _ = write!(output, "{question}");
+        let _ = output.flush();
+        let mut line = String::new();
+        match input.read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line.trim().to_string()),
+        }
+    };
+
+    let Some(group) = group.or_else(|| prompt_group(&mut read_line)) else {
+        return Ok(());
+    };
+
+    let mut entries = vec![];
+    eprintln!("Entries for {group}, one per line. Leave the line empty when done.");
+    while let Some(answer) = read_line("> ") {
+        if answer.is_empty() {
+            break;
+        }
+        match parse_entry(&answer) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+    if entries.is_empty() {
+        eprintln!("Nothing was added");
+        return Ok(());
+    }
+
+    for entry in entries {
+        changelog.unreleased.add(group.clone(), entry);
+    }
+    eprintln!(
+        "\n## [Unreleased]\n\n{}\n",
+        changelog.unreleased.changes.to_string().trim_end()
+    );
+    let answer = read_line(&format!("Write to '{}'? [Y/n] ", file.display()));
+    if answer.is_some_and(|answer| answer.is_empty() || answer.eq_ignore_ascii_case("y")) {
+        write_changelog(file, changelog)
+    } else {
+        eprintln!("Nothing was added");
+        Ok(())
+    }
+}
+fn prompt_group(read_line: &mut impl FnMut(&str) -> Option<String>) -> Option<ChangeGroup> {
+    let mut question = "Type of change:\n".to_string();
+    for (index, group) in CHANGE_GROUPS.iter().enumerate() {
+        let _ = writeln!(question, "  {}) {group}", index + 1);
+    }
+    question.push_str("> ");
+    loop {
+        let answer = read_line(&question)?;
+        let choice = answer
+            .parse::<usize>()
+            .ok()
+            .and_then(|choice| CHANGE_GROUPS.get(choice.wrapping_sub(1)).cloned())
+            .or_else(|| answer.parse().ok());
+        if choice.is_some() {
+            return choice;
+        }
+    }
+}
+
+// entries are written after a `- ` list marker so a leading list marker would nest a list and
+// a blank entry would leave an empty list item
+pub(crate) fn parse_entry(value: &str) -> Result<String, String> {
+    let entry = value.trim();
+    let entry = entry
+        .strip_prefix("- ")
+        .or_else(|| entry.strip_prefix("* "))
+        .unwrap_or(entry)
+        .trim_start();
+    if entry.is_empty() {
+        Err("entries can't be empty".to_string())
+    } else {
+        Ok(entry.to_string())
+    }
+}
@@ -0,0 +1,114 @@
+use crate::changelog_file::{is_stdio, read, read_changelog, write, write_changelog};
+use crate::error::CliError;
+use crate::latest::latest_release;
+use crate::manifest::{find_manifests, set_version, workspace_manifest};
+use crate::output::to_json;
+use keep_a_changelog_file::{PromoteOptions, VersionBump};
+use std::path::{Path, PathBuf};
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The part of the latest version to increment.
+    #[arg(long, value_enum, default_value_t = Level::Auto)]
+    level: Level,
+    /// A manifest to update with the new version. Defaults to any `Cargo.toml`, `package.json`,
+    /// or `pyproject.toml` next to the changelog.
+    #[arg(long = "manifest", value_name = "PATH", conflicts_with = "no_manifest")]
+    manifests: Vec<PathBuf>,
+    /// Only update the changelog.
+    #[arg(long)]
+    no_manifest: bool,
+    /// How to print the new version.
+    #[arg(long, value_enum, default_value_t = Format::Plain)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// Only the new version.
+    Plain,
+    /// The previous and new versions, the type of bump, and the manifests that were updated as
+    /// JSON.
+    Json,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Level {
+    /// Incompatible changes.
+    Major,
+    /// Backward compatible new functionality.
+    Minor,
+    /// Backward compatible bug fixes.
+    Patch,
+    /// Major for `Removed` entries, minor for `Added`, `Changed` or `Deprecated` entries, and
+    /// patch otherwise. Breaking changes to a `0.x` version are a minor bump.
+    Auto,
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let mut changelog = read_changelog(file)?;
+    let current = latest_release(&changelog, false)
+        .ok_or_else(|| CliError::NoReleases(file.to_path_buf()))?
+        .version
+        .clone();
+    let bump = match args.level {
+        Level::Major => VersionBump::Major,
+        Level::Minor => VersionBump::Minor,
+        Level::Patch => VersionBump::Patch,
+        Level::Auto => changelog
+            .unreleased
+            .changes
+            .suggested_bump(&current)
+            .ok_or_else(|| CliError::NoUnreleasedChanges(file.to_path_buf()))?,
+    };
+    let version = current.bump(bump).map_err(CliError::Bump)?;
+
+    let manifests = if args.no_manifest || is_stdio(file) {
+        vec![]
+    } else if args.manifests.is_empty() {
+        find_manifests(file)
+    } else {
+        args.manifests.clone()
+    };
+    // every manifest is checked before anything is written so a failure leaves all the files as
+    // they were
+    let mut updates: Vec<(PathBuf, String)> = vec![];
+    for path in manifests {
+        let mut contents = read(&path)?;
+        let path = match workspace_manifest(&path, &contents) {
+            Some(workspace) => {
+                contents = read(&workspace)?;
+                workspace
+            }
+            None => path,
+        };
+        if !updates.iter().any(|(updated, _)| *updated == path) {
+            let contents = set_version(&path, &contents, &version)?;
+            updates.push((path, contents));
+        }
+    }
+
+    changelog
+        .promote_unreleased(&PromoteOptions::new(version.clone()))
+        .map_err(CliError::Promote)?;
+    write_changelog(file, &changelog)?;
+    for (path, contents) in &updates {
+        write(path, contents)?;
+    }
+
+    if !is_stdio(file) {
+        match args.format {
+            Format::Plain => println!("{version}"),
+            Format::Json => println!(
+                "{}",
+                to_json(&serde_json::json!({
+                    "previous_version": current,
+                    "version": version,
+                    "bump": bump.to_string(),
+                    "manifests": updates.iter().map(|(path, _)| path).collect::<Vec<_>>(),
+                }))
+            ),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use crate::error::CliError;
+use keep_a_changelog_file::Changelog;
+use std::fs;
+use std::io::{ErrorKind, Read, Write};
+use std::path::Path;
+use std::sync::OnceLock;
+
+// `-` reads the changelog from stdin and writes it to stdout, for pipes and editor integrations
+pub(crate) fn is_stdio(path: &Path) -> bool {
+    path == Path::new("-")
+}
+
+pub(crate) fn read(path: &Path) -> Result<String, CliError> {
+    if is_stdio(path) {
+        let mut contents = String::new();
+        std::io::stdin()
+            .read_to_string(&mut contents)
+            .map_err(|e| CliError::Read(path.to_path_buf(), e))?;
+        return Ok(contents);
+    }
+    fs::read_to_string(path).map_err(|e| CliError::Read(path.to_path_buf(), e))
+}
+
+// stdin can only be read once, so a changelog read from it is kept for `write_changelog` to check
+// what it replaces
+static STDIN_CHANGELOG: OnceLock<String> = OnceLock::new();
+
+pub(crate) fn read_changelog(path: &Path) -> Result<Changelog, CliError> {
+    let input = read(path)?;
+    if is_stdio(path) {
+        let _ = STDIN_CHANGELOG.set(input.clone());
+    }
+    input
+        .parse()
+        .map_err(|e| CliError::Parse(path.to_path_buf(), e))
+}
+
+pub(crate) fn write(path: &Path, contents: &str) -> Result<(), CliError> {
+    if is_stdio(path) {
+        return std::io::stdout()
+            .write_all(contents.as_bytes())
+            .map_err(|e| CliError::Write(path.to_path_buf(), e));
+    }
+    fs::write(path, contents).map_err(|e| CliError::Write(path.to_path_buf(), e))
+}
+
+// every command that updates a changelog writes it back through here, so content the parser left
+// out of the changelog being replaced is never silently deleted
+pub(crate) fn write_changelog(path: &Path, changelog: &Changelog) -> Result<(), CliError> {
+    let replaced = if is_stdio(path) {
+        STDIN_CHANGELOG.get().cloned()
+    } else {
+        match fs::read_to_string(path) {
+            Ok(contents) => Some(contents),
+            Err(e) if e.kind() == ErrorKind::NotFound => None,
+            Err(e) => Err(CliError::Read(path.to_path_buf(), e))?,
+        }
+    };
+    if let Some(replaced) = replaced {
+        check_dropped_content(path, &replaced)?;
+    }
+    write(path, &changelog.to_string())
+}
+
+// content that parsing leaves out, like prose between releases, would be lost by rewriting the
+// changelog, so the changelog is refused instead
+pub(crate) fn check_dropped_content(path: &Path, input: &str) -> Result<(), CliError> {
+    let dropped =
+        Changelog::dropped_content(input).map_err(|e| CliError::Parse(path.to_path_buf(), e))?;
+    if dropped.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::DroppedContent(path.to_path_buf(), dropped))
+    }
+}
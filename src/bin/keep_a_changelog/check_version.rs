@@ -0,0 +1,104 @@
+use crate::changelog_file::{read, read_changelog};
+use crate::error::CliError;
+use crate::latest::latest_release;
+use crate::output::to_json;
+use keep_a_changelog_file::{ReleaseDate, ReleaseVersion};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+// the first version-like string, for files that only hold the version or mention it once
+const DEFAULT_VERSION_PATTERN: &str = r"v?(\d+\.\d+\.\d+(?:-[0-9A-Za-z.-]+)?(?:\+[0-9A-Za-z.-]+)?)";
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The version that must have a release, e.g. from the tag being built. A leading `v` is
+    /// ignored.
+    #[arg(
+        value_parser = parse_version,
+        required_unless_present = "version_file",
+        conflicts_with = "version_file"
+    )]
+    version: Option<ReleaseVersion>,
+    /// Read the version from a file instead, such as a `VERSION` file, a Dockerfile, or a source
+    /// file with a version constant.
+    #[arg(long, value_name = "PATH")]
+    version_file: Option<PathBuf>,
+    /// The regular expression that finds the version in `--version-file`. The first capture group
+    /// is the version, or the whole match when there isn't one. Defaults to the first
+    /// semver-like version in the file.
+    #[arg(long, requires = "version_file")]
+    pattern: Option<String>,
+    /// Fail unless the release has the highest version in the changelog.
+    #[arg(long)]
+    must_be_latest: bool,
+    /// Fail unless the release is dated today.
+    #[arg(long)]
+    released_today: bool,
+    /// What to print when the checks pass.
+    #[arg(long, value_enum, default_value_t = Format::Quiet)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// Nothing, only the exit code.
+    Quiet,
+    /// The release as JSON, including its version, date, tag, and link.
+    Json,
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let changelog = read_changelog(file)?;
+    let version = match (&args.version, &args.version_file) {
+        (Some(version), _) => version.clone(),
+        (None, Some(version_file)) => read_version(version_file, args.pattern.as_deref())?,
+        (None, None) => unreachable!("clap requires a version or a version file"),
+    };
+    let release = changelog
+        .releases
+        .get_version(&version)
+        .ok_or_else(|| CliError::ReleaseNotFound(version.to_string(), file.to_path_buf()))?;
+    if args.must_be_latest {
+        if let Some(latest) = latest_release(&changelog, false) {
+            if latest.version != release.version {
+                Err(CliError::NotLatest(
+                    release.version.clone(),
+                    latest.version.clone(),
+                ))?;
+            }
+        }
+    }
+    if args.released_today {
+        let today = ReleaseDate::today();
+        if release.date != today {
+            Err(CliError::NotReleasedToday(
+                release.version.clone(),
+                release.date.clone(),
+                today,
+            ))?;
+        }
+    }
+    if let Format::Json = args.format {
+        println!("{}", to_json(release));
+    }
+    Ok(())
+}
+
+fn read_version(path: &Path, pattern: Option<&str>) -> Result<ReleaseVersion, CliError> {
+    let pattern = pattern.unwrap_or(DEFAULT_VERSION_PATTERN);
+    let regex = Regex::new(pattern).map_err(|e| CliError::InvalidRegex(pattern.to_string(), e))?;
+    let contents = read(path)?;
+    regex
+        .captures(&contents)
+        .and_then(|captures| captures.get(1).or_else(|| captures.get(0)))
+        .and_then(|version| parse_version(version.as_str()).ok())
+        .ok_or_else(|| CliError::VersionNotFound(path.to_path_buf(), pattern.to_string()))
+}
+
+fn parse_version(value: &str) -> Result<ReleaseVersion, String> {
+    value
+        .strip_prefix('v')
+        .unwrap_or(value)
+        .parse()
+        .map_err(|e: keep_a_changelog_file::ParseVersionError| e.to_string())
+}
@@ -0,0 +1,43 @@
+use crate::changelog_file::read_changelog;
+use crate::error::CliError;
+use crate::output::to_json;
+use keep_a_changelog_file::ReleaseVersion;
+use std::path::Path;
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The version being upgraded from. Its own changes aren't included.
+    from: ReleaseVersion,
+    /// The version being upgraded to.
+    to: ReleaseVersion,
+    /// How to print the changes.
+    #[arg(long, value_enum, default_value_t = Format::Md)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// The markdown of the combined change groups.
+    Md,
+    /// The combined change groups as JSON.
+    Json,
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let changelog = read_changelog(file)?;
+    for version in [&args.from, &args.to] {
+        if !changelog.releases.contains_version(version) {
+            Err(CliError::ReleaseNotFound(
+                version.to_string(),
+                file.to_path_buf(),
+            ))?;
+        }
+    }
+    let changes = changelog.changes_between(&args.from, &args.to);
+    let output = match args.format {
+        Format::Md => changes.to_string(),
+        Format::Json => to_json(&changes),
+    };
+    println!("{}", output.trim_end());
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use crate::changelog_file::{read_changelog, write_changelog};
+use crate::error::CliError;
+use crate::output::print_json;
+use keep_a_changelog_file::ReleaseVersion;
+use std::path::Path;
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The version of the release to demote.
+    version: ReleaseVersion,
+    /// What to print once the release is demoted.
+    #[arg(long, value_enum, default_value_t = Format::Quiet)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// Nothing.
+    Quiet,
+    /// The Unreleased section, now holding the changes of the demoted release, as JSON.
+    Json,
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let mut changelog = read_changelog(file)?;
+    changelog
+        .demote(&args.version)
+        .ok_or_else(|| CliError::ReleaseNotFound(args.version.to_string(), file.to_path_buf()))?;
+    if let Format::Json = args.format {
+        print_json(file, &changelog.unreleased);
+    }
+    write_changelog(file, &changelog)
+}
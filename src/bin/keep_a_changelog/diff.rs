@@ -0,0 +1,145 @@
+use crate::changelog_file::read_changelog;
+use crate::error::CliError;
+use crate::output::to_json;
+use keep_a_changelog_file::{Changelog, Changes};
+use serde::Serialize;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The changelog before the changes.
+    old: PathBuf,
+    /// The changelog after the changes.
+    new: PathBuf,
+    /// How to print the differences.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// The sections that changed, with `+` for added entries and `-` for removed ones.
+    Text,
+    /// A list of the sections that changed with their added and removed entries by change group.
+    Json,
+}
+
+#[derive(Serialize)]
+struct SectionDiff {
+    section: String,
+    status: Status,
+    added: Changes,
+    removed: Changes,
+}
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Added,
+    Removed,
+    Changed,
+}
+
+pub(crate) fn run(_file: &Path, args: &Args) -> Result<(), CliError> {
+    let diff = diff(&read_changelog(&args.old)?, &read_changelog(&args.new)?);
+    match args.format {
+        Format::Text => {
+            for section in &diff {
+                println!("{}", to_text(section));
+            }
+        }
+        Format::Json => println!("{}", to_json(&diff)),
+    }
+    Ok(())
+}
+
+// entries are compared by their text within each change group, so reordering entries or
+// reformatting the file doesn't show up as a change
+fn diff(old: &Changelog, new: &Changelog) -> Vec<SectionDiff> {
+    let mut sections = vec![];
+    let empty = Changes::default();
+    sections.extend(section_diff(
+        "Unreleased",
+        Status::Changed,
+        &old.unreleased.changes,
+        &new.unreleased.changes,
+    ));
+    for (version, release) in &new.releases {
+        sections.extend(match old.releases.get_version(version) {
+            Some(old_release) => section_diff(
+                version.to_string(),
+                Status::Changed,
+                &old_release.changes,
+                &release.changes,
+            ),
+            None => section_diff(version.to_string(), Status::Added, &empty, &release.changes),
+        });
+    }
+    for (version, release) in &old.releases {
+        if !new.releases.contains_version(version) {
+            sections.extend(section_diff(
+                version.to_string(),
+                Status::Removed,
+                &release.changes,
+                &empty,
+            ));
+        }
+    }
+    sections
+}
+
+fn section_diff(
+    section: impl Into<String>,
+    status: Status,
+    old: &Changes,
+    new: &Changes,
+) -> Option<SectionDiff> {
+    let added = missing_from(new, old);
+    let removed = missing_from(old, new);
+    if matches!(status, Status::Changed) && added.is_empty() && removed.is_empty() {
+        return None;
+    }
+    Some(SectionDiff {
+        section: section.into(),
+        status,
+        added,
+        removed,
+    })
+}
+
+// the entries in `changes` that aren't in the same change group of `other`
+fn missing_from(changes: &Changes, other: &Changes) -> Changes {
+    changes
+        .iter()
+        .filter_map(|(change_group, items)| {
+            let other_items = other.get(change_group).unwrap_or_default();
+            let items = items
+                .iter()
+                .filter(|item| !other_items.contains(item))
+                .cloned()
+                .collect::<Vec<_>>();
+            (!items.is_empty()).then(|| (change_group.clone(), items))
+        })
+        .collect()
+}
+
+fn to_text(section: &SectionDiff) -> String {
+    let mut text = match section.status {
+        Status::Added => format!("{} (added)", section.section),
+        Status::Removed => format!("{} (removed)", section.section),
+        Status::Changed => section.section.clone(),
+    };
+    for (marker, changes) in [('+', &section.added), ('-', &section.removed)] {
+        for (change_group, items) in changes {
+            for item in items {
+                let _ = write!(
+                    text,
+                    "\n  {marker} {change_group}: {}",
+                    item.replace('\n', "\n      ")
+                );
+            }
+        }
+    }
+    text
+}
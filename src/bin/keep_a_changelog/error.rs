@@ -0,0 +1,123 @@
+use keep_a_changelog_file::{
+    BumpVersionError, ChangeGroup, Diagnostic, LinkTemplateError, ParseChangelogError,
+    PromoteUnreleasedError, ReleaseDate, ReleaseVersion,
+};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub(crate) enum CliError {
+    #[error("Could not read '{0}'\nReason: {1}")]
+    Read(PathBuf, std::io::Error),
+    #[error("Could not write '{0}'\nReason: {1}")]
+    Write(PathBuf, std::io::Error),
+    #[error("Could not parse '{0}'\n{1}")]
+    Parse(PathBuf, ParseChangelogError),
+    #[error("Could not parse '{0}' as a changelog in JSON\nReason: {1}")]
+    Json(PathBuf, serde_json::Error),
+    #[error("Could not render the template '{0}'\nReason: {1}")]
+    Template(PathBuf, String),
+    #[error("Could not create '{0}' because it already exists\nUse --force to overwrite it")]
+    AlreadyExists(PathBuf),
+    #[error("{0}")]
+    Promote(PromoteUnreleasedError),
+    #[error("Release {0} does not exist in '{1}'")]
+    ReleaseNotFound(String, PathBuf),
+    #[error("'{0}' has content that would be lost by rewriting it\n{}", .1.iter().map(|diagnostic| format!("{}:{diagnostic}", .0.display())).collect::<Vec<_>>().join("\n"))]
+    DroppedContent(PathBuf, Vec<Diagnostic>),
+    #[error("'{0}' isn't formatted\nRun `keep_a_changelog fmt` to format it")]
+    NotFormatted(PathBuf),
+    #[error("The releases in '{0}' aren't sorted\nRun `keep_a_changelog sort` to sort them")]
+    NotSorted(PathBuf),
+    #[error("'{0}' doesn't have any releases")]
+    NoReleases(PathBuf),
+    #[error("'{0}' doesn't have any unreleased changes\nRun `keep_a_changelog add` to add one")]
+    NoUnreleasedChanges(PathBuf),
+    #[error("'{0}' has {1} unreleased {entries}, at least {2} are required", entries = if *.1 == 1 { "entry" } else { "entries" })]
+    TooFewUnreleasedChanges(PathBuf, usize, usize),
+    #[error("'{0}' doesn't have any unreleased {1} changes")]
+    NoUnreleasedChangesInGroup(PathBuf, ChangeGroup),
+    #[error("Release {0} isn't the latest release, {1} is")]
+    NotLatest(ReleaseVersion, ReleaseVersion),
+    #[error("Release {0} is dated {1} instead of today ({2})")]
+    NotReleasedToday(ReleaseVersion, ReleaseDate, ReleaseDate),
+    #[error("'{0}' has {1} {problems}", problems = if *.1 == 1 { "problem" } else { "problems" })]
+    Invalid(PathBuf, usize),
+    #[error("{failed} of {total} changelogs failed validation")]
+    ValidationFailed {
+        failed: usize,
+        total: usize,
+        unreadable: bool,
+    },
+    #[error("{0}")]
+    Bump(BumpVersionError),
+    #[error("Could not find the version to update in '{0}'")]
+    ManifestVersionNotFound(PathBuf),
+    #[error("{0}")]
+    LinkTemplate(LinkTemplateError),
+    #[error("Could not find a version matching '{1}' in '{0}'")]
+    VersionNotFound(PathBuf, String),
+    #[error("Could not run git\nReason: {0}")]
+    Git(String),
+    #[error("{0} {releases} don't match the git tags", releases = if *.0 == 1 { "release" } else { "releases" })]
+    TagDrift(usize),
+    #[error("No entries to add\nPass the entries as arguments, or run it in a terminal to be prompted for them")]
+    NoEntries,
+    #[error("Can't watch stdin for changes")]
+    WatchStdin,
+    #[error("Invalid glob pattern '{0}'\nReason: {1}")]
+    InvalidPattern(String, glob::PatternError),
+    #[error("No files match '{0}'")]
+    NoMatches(String),
+    #[error("Invalid regular expression '{0}'\nReason: {1}")]
+    InvalidRegex(String, regex::Error),
+    #[error("No entries match '{0}'")]
+    NoMatchingEntries(String),
+}
+
+impl CliError {
+    // the exit codes are documented in `--help` and stay stable so scripts can rely on them:
+    // problems with the changelog itself exit with 1, invalid arguments with 2 like clap does, and
+    // problems reading or writing files or running git with 3
+    pub(crate) fn exit_code(&self) -> ExitCode {
+        match self {
+            CliError::Read(..)
+            | CliError::Write(..)
+            | CliError::Git(_)
+            | CliError::ValidationFailed {
+                unreadable: true, ..
+            } => ExitCode::from(3),
+            CliError::InvalidPattern(..)
+            | CliError::NoMatches(_)
+            | CliError::InvalidRegex(..)
+            | CliError::WatchStdin
+            | CliError::NoEntries
+            | CliError::LinkTemplate(_) => ExitCode::from(2),
+            CliError::Parse(..)
+            | CliError::Json(..)
+            | CliError::Template(..)
+            | CliError::AlreadyExists(_)
+            | CliError::Promote(_)
+            | CliError::ReleaseNotFound(..)
+            | CliError::NoReleases(_)
+            | CliError::NoUnreleasedChanges(_)
+            | CliError::TooFewUnreleasedChanges(..)
+            | CliError::NoUnreleasedChangesInGroup(..)
+            | CliError::NotLatest(..)
+            | CliError::NotReleasedToday(..)
+            | CliError::Bump(_)
+            | CliError::ManifestVersionNotFound(_)
+            | CliError::VersionNotFound(..)
+            | CliError::NoMatchingEntries(_)
+            | CliError::TagDrift(_)
+            | CliError::DroppedContent(..)
+            | CliError::NotFormatted(_)
+            | CliError::NotSorted(_)
+            | CliError::Invalid(..)
+            | CliError::ValidationFailed {
+                unreadable: false, ..
+            } => ExitCode::FAILURE,
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::changelog_file::{check_dropped_content, read, write};
+use crate::error::CliError;
+use crate::output::print_json;
+use keep_a_changelog_file::Changelog;
+use std::path::Path;
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// Don't rewrite the file, just exit with 1 if it isn't formatted.
+    #[arg(long)]
+    check: bool,
+    /// How to report whether the file was formatted.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// The path of the file when `--check` finds it isn't formatted.
+    Text,
+    /// The path of the file and whether formatting changed it, or would with `--check`, as JSON.
+    Json,
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let input = read(file)?;
+    check_dropped_content(file, &input)?;
+    let formatted = input
+        .parse::<Changelog>()
+        .map_err(|e| CliError::Parse(file.to_path_buf(), e))?
+        .to_string();
+    let changed = formatted != input;
+    match args.format {
+        Format::Text if changed && args.check => println!("{}", file.display()),
+        Format::Text => {}
+        Format::Json => print_json(
+            file,
+            &serde_json::json!({ "path": file, "changed": changed }),
+        ),
+    }
+    if !changed {
+        return Ok(());
+    }
+    if args.check {
+        Err(CliError::NotFormatted(file.to_path_buf()))?;
+    }
+    write(file, &formatted)
+}
@@ -0,0 +1,89 @@
+use crate::changelog_file::{read_changelog, write_changelog};
+use crate::error::CliError;
+use crate::output::print_json;
+use keep_a_changelog_file::{Changelog, LinkTemplate, ReleaseLink, RepositoryProvider, Section};
+use serde::Serialize;
+use std::path::Path;
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The URL of the repository the links point to.
+    #[arg(long, value_name = "REPO_URL")]
+    repo: String,
+    /// The hosting provider of the repository, for self-hosted instances that can't be detected
+    /// from the URL.
+    #[arg(long)]
+    provider: Option<RepositoryProvider>,
+    /// The prefix that turns a version into a tag name.
+    #[arg(long, default_value = "v")]
+    tag_prefix: String,
+    /// Replace existing links that don't match the generated ones.
+    #[arg(long)]
+    force: bool,
+    /// How to report the links that were generated.
+    #[arg(long, value_enum, default_value_t = Format::Quiet)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// Nothing.
+    Quiet,
+    /// The links that were added and the ones that were replaced, by section, as JSON.
+    Json,
+}
+
+#[derive(Serialize)]
+struct GeneratedLink {
+    section: String,
+    link: ReleaseLink,
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let mut changelog = read_changelog(file)?;
+    let link_template = match args.provider {
+        Some(provider) => LinkTemplate::new(&args.repo, provider),
+        None => args.repo.parse(),
+    }
+    .map_err(CliError::LinkTemplate)?
+    .with_tag_prefix(&args.tag_prefix);
+    let before = links(&changelog);
+    if args.force {
+        changelog.regenerate_links(&link_template);
+    } else {
+        changelog.generate_links(&link_template);
+    }
+    if let Format::Json = args.format {
+        let (mut added, mut replaced) = (vec![], vec![]);
+        for ((section, previous), (_, link)) in before.into_iter().zip(links(&changelog)) {
+            let Some(link) = link.filter(|link| previous.as_ref() != Some(link)) else {
+                continue;
+            };
+            let generated = GeneratedLink {
+                section: section.to_string(),
+                link,
+            };
+            if previous.is_some() {
+                replaced.push(generated);
+            } else {
+                added.push(generated);
+            }
+        }
+        print_json(
+            file,
+            &serde_json::json!({ "added": added, "replaced": replaced }),
+        );
+    }
+    write_changelog(file, &changelog)
+}
+
+// generating links never adds or removes sections, so the links before and after line up
+fn links(changelog: &Changelog) -> Vec<(Section, Option<ReleaseLink>)> {
+    std::iter::once((Section::Unreleased, changelog.unreleased.link.clone()))
+        .chain(
+            changelog.releases.iter().map(|(version, release)| {
+                (Section::Release(version.clone()), release.link.clone())
+            }),
+        )
+        .collect()
+}
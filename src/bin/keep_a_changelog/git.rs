@@ -0,0 +1,29 @@
+use crate::changelog_file::is_stdio;
+use crate::error::CliError;
+use std::path::Path;
+use std::process::Command;
+
+// runs git in the directory of the changelog and returns what it printed, so commands look at the
+// changelog's repository rather than whichever one they happen to be run from
+pub(crate) fn git(file: &Path, args: &[&str]) -> Result<String, CliError> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(working_dir(file))
+        .args(args)
+        .output()
+        .map_err(|e| CliError::Git(e.to_string()))?;
+    if !output.status.success() {
+        Err(CliError::Git(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))?;
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// a changelog read from stdin, or given without a directory, belongs to the current directory
+pub(crate) fn working_dir(file: &Path) -> &Path {
+    match file.parent() {
+        Some(parent) if !is_stdio(file) && !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    }
+}
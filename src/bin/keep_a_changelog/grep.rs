@@ -0,0 +1,117 @@
+use crate::changelog_file::read_changelog;
+use crate::error::CliError;
+use crate::output::to_json;
+use keep_a_changelog_file::{ChangeGroup, Changes, ReleaseDate, ReleaseVersion};
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+use std::path::Path;
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The regular expression to search the entries for.
+    pattern: String,
+    /// Ignore case when matching.
+    #[arg(long, short)]
+    ignore_case: bool,
+    /// Only search entries of this type of change.
+    #[arg(long, short)]
+    group: Option<ChangeGroup>,
+    /// Only search the Unreleased section, this release, and the ones with higher versions.
+    #[arg(long)]
+    since: Option<ReleaseVersion>,
+    /// How to print the matching entries.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// One line per entry, prefixed with its release and type of change.
+    Text,
+    /// The entries as a JSON array, with the version and date of their release.
+    Json,
+}
+
+// the version and date are left out for entries in the Unreleased section
+#[derive(Serialize)]
+struct Match<'a> {
+    version: Option<&'a ReleaseVersion>,
+    date: Option<&'a ReleaseDate>,
+    change_group: &'a ChangeGroup,
+    entry: &'a str,
+}
+
+impl Match<'_> {
+    fn to_text(&self) -> String {
+        let section = match (self.version, self.date) {
+            (Some(version), Some(date)) => format!("{version} ({date})"),
+            _ => "Unreleased".to_string(),
+        };
+        format!(
+            "{section} {}: {}",
+            self.change_group,
+            self.entry.replace('\n', " ")
+        )
+    }
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let changelog = read_changelog(file)?;
+    let pattern = RegexBuilder::new(&args.pattern)
+        .case_insensitive(args.ignore_case)
+        .build()
+        .map_err(|e| CliError::InvalidRegex(args.pattern.clone(), e))?;
+
+    let mut matches = search(&pattern, args, None, &changelog.unreleased.changes);
+    for (version, release) in &changelog.releases {
+        if args.since.as_ref().is_some_and(|since| version < since) {
+            continue;
+        }
+        matches.extend(search(
+            &pattern,
+            args,
+            Some((version, &release.date)),
+            &release.changes,
+        ));
+    }
+
+    match args.format {
+        Format::Text => {
+            for found in &matches {
+                println!("{}", found.to_text());
+            }
+        }
+        Format::Json => println!("{}", to_json(&matches)),
+    }
+    if matches.is_empty() {
+        Err(CliError::NoMatchingEntries(args.pattern.clone()))?;
+    }
+    Ok(())
+}
+
+fn search<'a>(
+    pattern: &Regex,
+    args: &Args,
+    release: Option<(&'a ReleaseVersion, &'a ReleaseDate)>,
+    changes: &'a Changes,
+) -> Vec<Match<'a>> {
+    changes
+        .iter()
+        .filter(|(change_group, _)| {
+            args.group
+                .as_ref()
+                .map_or(true, |group| group == *change_group)
+        })
+        .flat_map(|(change_group, items)| {
+            items
+                .iter()
+                .filter(|item| pattern.is_match(item))
+                .map(move |item| Match {
+                    version: release.map(|(version, _)| version),
+                    date: release.map(|(_, date)| date),
+                    change_group,
+                    entry: item,
+                })
+        })
+        .collect()
+}
@@ -0,0 +1,87 @@
+use crate::changelog_file::write;
+use crate::error::CliError;
+use crate::git::git;
+use crate::verify_tags::{parse_tag_template, TagTemplate};
+use keep_a_changelog_file::{Changelog, Changes, Release, ReleaseDate};
+use std::path::Path;
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// Create a release for each git tag that matches the tag template, dated from the tag.
+    #[arg(long, required = true)]
+    from_tags: bool,
+    /// Add the conventional commit subjects between tags as entries, grouped by their type.
+    #[arg(long)]
+    conventional: bool,
+    /// How a version is turned into a tag name. Tags that don't match it are ignored.
+    #[arg(long, default_value = "v{version}", value_parser = parse_tag_template)]
+    tag_template: TagTemplate,
+    /// Replace the file if it already exists.
+    #[arg(long)]
+    force: bool,
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    if file.exists() && !args.force {
+        Err(CliError::AlreadyExists(file.to_path_buf()))?;
+    }
+
+    let refs = git(
+        file,
+        &[
+            "for-each-ref",
+            "--format=%(refname:short) %(creatordate:short)",
+            "refs/tags",
+        ],
+    )?;
+    let mut tags = refs
+        .lines()
+        .filter_map(|line| {
+            let (tag, date) = line.split_once(' ')?;
+            let version = args.tag_template.version(tag)?;
+            let date = date.parse::<ReleaseDate>().ok()?;
+            Some((tag, version, date))
+        })
+        .collect::<Vec<_>>();
+    tags.sort_by(|(_, a, _), (_, b, _)| a.cmp(b));
+
+    let mut changelog = Changelog::default();
+    let mut previous_tag = None;
+    let mut releases = vec![];
+    for (tag, version, date) in tags {
+        let changes = commit_changes(file, args, previous_tag, tag)?;
+        releases.push((
+            version.clone(),
+            Release {
+                version,
+                date,
+                tag: None,
+                link: None,
+                changes,
+            },
+        ));
+        previous_tag = Some(tag);
+    }
+    changelog.unreleased.changes = commit_changes(file, args, previous_tag, "HEAD")?;
+    changelog.releases = releases.into_iter().rev().collect();
+
+    write(file, &changelog.to_string())
+}
+
+// the commits that are reachable from `to` but not from the previous tag
+fn commit_changes(
+    file: &Path,
+    args: &Args,
+    from: Option<&str>,
+    to: &str,
+) -> Result<Changes, CliError> {
+    if !args.conventional {
+        return Ok(Changes::default());
+    }
+    let range = match from {
+        Some(from) => format!("{from}..{to}"),
+        None => to.to_string(),
+    };
+    let subjects = git(file, &["log", "--format=%s", &range])?;
+    Ok(Changes::from_conventional_commits(subjects.lines()))
+}
@@ -0,0 +1,42 @@
+use crate::changelog_file::write;
+use crate::error::CliError;
+use crate::output::print_json;
+use keep_a_changelog_file::Changelog;
+use std::path::{Path, PathBuf};
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// Where to create the changelog. Overrides `--file`.
+    #[arg(long)]
+    path: Option<PathBuf>,
+    /// Replace the file if it already exists.
+    #[arg(long)]
+    force: bool,
+    /// What to print once the changelog is created.
+    #[arg(long, value_enum, default_value_t = Format::Quiet)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// Nothing.
+    Quiet,
+    /// The path of the changelog and whether it replaced an existing file as JSON.
+    Json,
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let file = args.path.as_deref().unwrap_or(file);
+    let replaced = file.exists();
+    if replaced && !args.force {
+        Err(CliError::AlreadyExists(file.to_path_buf()))?;
+    }
+    write(file, &Changelog::default().to_string())?;
+    if let Format::Json = args.format {
+        print_json(
+            file,
+            &serde_json::json!({ "path": file, "replaced": replaced }),
+        );
+    }
+    Ok(())
+}
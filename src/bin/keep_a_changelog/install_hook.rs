@@ -0,0 +1,156 @@
+use crate::changelog_file::write;
+use crate::error::CliError;
+use crate::git::{git, working_dir};
+use std::path::{Component, Path, PathBuf};
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The git hook to install.
+    #[arg(long, value_enum, default_value_t = Hook::PreCommit)]
+    hook: Hook,
+    /// Replace the hook if one already exists.
+    #[arg(long)]
+    force: bool,
+    /// Print a configuration snippet for the [pre-commit](https://pre-commit.com) framework instead
+    /// of installing a hook.
+    #[arg(long, conflicts_with_all = ["hook", "force"])]
+    pre_commit_config: bool,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Hook {
+    /// Checks the staged changelog when it's part of a commit.
+    PreCommit,
+    /// Checks the changelog before every push.
+    PrePush,
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let repo_file = repo_path(file)?;
+    let changelog = shell_quote(&repo_file);
+    if args.pre_commit_config {
+        print!("{}", pre_commit_config(&changelog, &repo_file));
+        return Ok(());
+    }
+
+    let (name, script) = match args.hook {
+        Hook::PreCommit => ("pre-commit", pre_commit_hook(&changelog)),
+        Hook::PrePush => ("pre-push", pre_push_hook(&changelog)),
+    };
+    let path = hooks_dir(file)?.join(name);
+    if path.exists() && !args.force {
+        Err(CliError::AlreadyExists(path.clone()))?;
+    }
+    write(&path, &script)?;
+    make_executable(&path)?;
+    println!("Installed {}", path.display());
+    Ok(())
+}
+
+// git knows where the hooks go for worktrees and when `core.hooksPath` is set, relative to the
+// directory it was run in
+fn hooks_dir(file: &Path) -> Result<PathBuf, CliError> {
+    Ok(working_dir(file).join(git(file, &["rev-parse", "--git-path", "hooks"])?.trim()))
+}
+
+// hooks run from the top of the repository and compare the changelog against the paths git lists,
+// so `./CHANGELOG.md` or a path from a subdirectory is rewritten the way git would write it
+fn repo_path(file: &Path) -> Result<String, CliError> {
+    let path = if file.is_absolute() {
+        let top = PathBuf::from(git(file, &["rev-parse", "--show-toplevel"])?.trim());
+        let top = top.canonicalize().unwrap_or(top);
+        let file = file.canonicalize().unwrap_or_else(|_| file.to_path_buf());
+        file.strip_prefix(&top)
+            .map_err(|_| {
+                CliError::Git(format!(
+                    "'{}' is outside the repository at '{}'",
+                    file.display(),
+                    top.display()
+                ))
+            })?
+            .to_path_buf()
+    } else {
+        // the prefix is for the changelog's directory, which git is run in
+        Path::new(git(file, &["rev-parse", "--show-prefix"])?.trim())
+            .join(file.file_name().unwrap_or(file.as_os_str()))
+    };
+
+    let mut components: Vec<String> = vec![];
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                components.pop();
+            }
+            Component::Normal(name) => components.push(name.to_string_lossy().into_owned()),
+            Component::CurDir | Component::RootDir | Component::Prefix(_) => {}
+        }
+    }
+    Ok(components.join("/"))
+}
+
+// the staged version is checked rather than the working tree, since that's what gets committed
+fn pre_commit_hook(changelog: &str) -> String {
+    format!(
+        r#"#!/bin/sh
+# Installed by `keep_a_changelog install-hook`
+changelog={changelog}
+git diff --cached --name-only --diff-filter=ACMR | grep -qxF "$changelog" || exit 0
+staged=$(mktemp) || exit 1
+trap 'rm -f "$staged"' EXIT
+git show ":$changelog" > "$staged" &&
+  keep_a_changelog --file "$staged" validate &&
+  keep_a_changelog --file "$staged" fmt --check
+"#
+    )
+}
+
+fn pre_push_hook(changelog: &str) -> String {
+    format!(
+        r#"#!/bin/sh
+# Installed by `keep_a_changelog install-hook`
+changelog={changelog}
+keep_a_changelog --file "$changelog" validate &&
+  keep_a_changelog --file "$changelog" fmt --check
+"#
+    )
+}
+
+fn pre_commit_config(changelog: &str, file: &str) -> String {
+    let pattern = regex::escape(file);
+    format!(
+        r"repos:
+  - repo: local
+    hooks:
+      - id: keep-a-changelog-validate
+        name: Validate the changelog
+        entry: keep_a_changelog --file {changelog} validate
+        language: system
+        files: ^{pattern}$
+        pass_filenames: false
+      - id: keep-a-changelog-fmt
+        name: Check the changelog is formatted
+        entry: keep_a_changelog --file {changelog} fmt --check
+        language: system
+        files: ^{pattern}$
+        pass_filenames: false
+"
+    )
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<(), CliError> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+        .map_err(|e| CliError::Write(path.to_path_buf(), e))
+}
+
+// git for Windows runs hooks through its own shell without checking permissions
+#[cfg(not(unix))]
+#[allow(clippy::unnecessary_wraps)]
+fn make_executable(_path: &Path) -> Result<(), CliError> {
+    Ok(())
+}
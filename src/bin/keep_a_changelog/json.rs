@@ -0,0 +1,30 @@
+use crate::changelog_file::{read, read_changelog, write, write_changelog};
+use crate::error::CliError;
+use crate::output::to_json;
+use keep_a_changelog_file::Changelog;
+use std::path::{Path, PathBuf};
+
+#[derive(clap::Args)]
+pub(crate) struct ToJsonArgs {
+    /// Where to write the JSON. Prints it when not given.
+    #[arg(long, short, default_value = "-")]
+    output: PathBuf,
+}
+
+#[derive(clap::Args)]
+pub(crate) struct FromJsonArgs {
+    /// The JSON to convert, in the structure written by `to-json`. Use `-` to read from stdin.
+    #[arg(default_value = "-")]
+    input: PathBuf,
+}
+
+pub(crate) fn to_json_run(file: &Path, args: &ToJsonArgs) -> Result<(), CliError> {
+    let changelog = read_changelog(file)?;
+    write(&args.output, &format!("{}\n", to_json(&changelog)))
+}
+
+pub(crate) fn from_json_run(file: &Path, args: &FromJsonArgs) -> Result<(), CliError> {
+    let changelog: Changelog = serde_json::from_str(&read(&args.input)?)
+        .map_err(|e| CliError::Json(args.input.clone(), e))?;
+    write_changelog(file, &changelog)
+}
@@ -0,0 +1,53 @@
+use crate::changelog_file::read_changelog;
+use crate::error::CliError;
+use crate::output::to_json;
+use keep_a_changelog_file::{Changelog, Release};
+use std::path::Path;
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// Ignore pre-release versions such as `1.0.0-rc.1`.
+    #[arg(long)]
+    stable_only: bool,
+    /// How to print the release.
+    #[arg(long, value_enum, default_value_t = Format::Plain)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// Only the version.
+    Plain,
+    /// The version, date, tag, and link as JSON.
+    Json,
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let changelog = read_changelog(file)?;
+    let latest = latest_release(&changelog, args.stable_only)
+        .ok_or_else(|| CliError::NoReleases(file.to_path_buf()))?;
+
+    match args.format {
+        Format::Plain => println!("{}", latest.version),
+        Format::Json => println!(
+            "{}",
+            to_json(&serde_json::json!({
+                "version": latest.version,
+                "date": latest.date,
+                "tag": latest.tag,
+                "link": latest.link,
+            }))
+        ),
+    }
+    Ok(())
+}
+
+// the highest version rather than the first, since releases aren't guaranteed to be ordered
+pub(crate) fn latest_release(changelog: &Changelog, stable_only: bool) -> Option<&Release> {
+    changelog
+        .releases
+        .iter()
+        .map(|(_, release)| release)
+        .filter(|release| !stable_only || !release.version.is_prerelease())
+        .max_by(|a, b| a.version.cmp(&b.version))
+}
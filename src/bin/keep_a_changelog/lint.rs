@@ -0,0 +1,105 @@
+use crate::changelog_file::read;
+use crate::error::CliError;
+use crate::output::to_json;
+use crate::validate::{print_diagnostics, report, Format};
+#[cfg(feature = "link-check")]
+use keep_a_changelog_file::LinkCheckOptions;
+use keep_a_changelog_file::{Changelog, ComplianceRule, DiagnosticsReport, Severity};
+use serde::Serialize;
+use std::path::Path;
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// Only check these rules, e.g. `--only linkable-versions,no-empty-releases`.
+    #[arg(long, value_delimiter = ',', value_name = "RULE")]
+    only: Vec<ComplianceRule>,
+    /// Don't check these rules.
+    #[arg(long, value_delimiter = ',', value_name = "RULE")]
+    skip: Vec<ComplianceRule>,
+    /// Report these rules as errors, which fail the lint, instead of warnings.
+    #[arg(long, value_delimiter = ',', value_name = "RULE")]
+    deny: Vec<ComplianceRule>,
+    /// How to print the diagnostics.
+    #[arg(long, visible_alias = "format", value_enum, default_value_t = Format::Text)]
+    error_format: Format,
+    /// Also check that the unreleased and release links resolve, reporting dead links as
+    /// `linkable-versions` errors.
+    #[cfg(feature = "link-check")]
+    #[arg(long)]
+    check_links: bool,
+    /// Print the rules with their codes and default levels as JSON instead of checking the
+    /// changelog.
+    #[arg(long, exclusive = true)]
+    list_checks: bool,
+}
+
+#[derive(Serialize)]
+struct Check {
+    code: &'static str,
+    level: Severity,
+    reference: String,
+}
+
+// like clippy, problems with the structure of the changelog are always reported and the rules
+// on top of them can be selected and promoted to errors
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    if args.list_checks {
+        let checks = ComplianceRule::ALL
+            .iter()
+            .map(|rule| Check {
+                code: rule.code(),
+                level: rule.default_severity(),
+                reference: rule.spec_reference(),
+            })
+            .collect::<Vec<_>>();
+        println!("{}", to_json(&checks));
+        return Ok(());
+    }
+
+    let input = read(file)?;
+    #[cfg_attr(not(feature = "link-check"), allow(unused_mut))]
+    let mut diagnostics = Changelog::diagnose(&input);
+    #[cfg(feature = "link-check")]
+    if args.check_links {
+        // a changelog that can't be parsed has already been reported by its diagnostics
+        if let Ok(dead_links) = Changelog::check_links(&input, &LinkCheckOptions::new()) {
+            diagnostics.extend(dead_links);
+            diagnostics.sort_by_key(|diagnostic| (diagnostic.line, diagnostic.column));
+        }
+    }
+    let diagnostics = diagnostics
+        .into_iter()
+        .filter(|diagnostic| match diagnostic.rule {
+            Some(rule) => {
+                (args.only.is_empty() || args.only.contains(&rule)) && !args.skip.contains(&rule)
+            }
+            None => true,
+        })
+        .map(|mut diagnostic| {
+            if diagnostic
+                .rule
+                .is_some_and(|rule| args.deny.contains(&rule))
+            {
+                diagnostic.severity = Severity::Error;
+            }
+            diagnostic
+        })
+        .collect::<Vec<_>>();
+
+    match args.error_format {
+        Format::Json => println!("{}", report(file, &diagnostics).to_json()),
+        Format::Sarif => println!(
+            "{}",
+            DiagnosticsReport::to_sarif(&[report(file, &diagnostics)])
+        ),
+        format => print_diagnostics(file, &input, &diagnostics, format),
+    }
+    let errors = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Error)
+        .count();
+    if errors > 0 {
+        Err(CliError::Invalid(file.to_path_buf(), errors))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,165 @@
+//! Command line interface for reading and updating changelogs written in the
+//! [Keep a Changelog](https://keepachangelog.com/en/1.1.0/) format.
+// the binary shares the dependencies of the library but only uses a few of them
+#![allow(unused_crate_dependencies)]
+
+mod add;
+mod bump;
+mod changelog_file;
+mod check_version;
+mod compare;
+mod demote;
+mod diff;
+mod error;
+mod fmt;
+mod gen_links;
+mod git;
+mod grep;
+mod import;
+mod init;
+mod install_hook;
+mod json;
+mod latest;
+mod lint;
+mod manifest;
+mod merge;
+mod notes;
+mod output;
+mod release;
+mod release_notes;
+mod set_link;
+mod show;
+mod sort;
+mod stats;
+mod unreleased;
+mod validate;
+mod verify_tags;
+mod yank;
+
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const EXIT_CODES: &str = "\
+Exit codes:
+  0  Success
+  1  The changelog failed a check, such as a validation error or a missing release
+  2  Invalid arguments
+  3  A file couldn't be read or written, or git couldn't be run";
+
+#[derive(Parser)]
+#[command(version, about, after_help = EXIT_CODES)]
+struct Cli {
+    /// The changelog file to read and update. Use `-` to read from stdin and write to stdout.
+    #[arg(long, short, global = true, default_value = "CHANGELOG.md")]
+    file: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Reports problems in the changelog. Exits with 1 when there are errors.
+    Validate(validate::Args),
+    /// Checks the changelog against a selection of rules, which can be promoted to errors.
+    ///
+    /// Exits with 1 when any rule reports an error. The rules that failed are named in the
+    /// diagnostics, and by code with `--format json`, rather than by a status per rule since
+    /// statuses 2 and 3 already mean invalid arguments and unreadable files.
+    Lint(lint::Args),
+    /// Creates a new changelog with the standard preamble and an empty Unreleased section.
+    Init(init::Args),
+    /// Creates a changelog from the git history with a release for each tag.
+    Import(import::Args),
+    /// Adds entries to the Unreleased section.
+    Add(add::Args),
+    /// Moves the Unreleased changes into a new release.
+    Release(release::Args),
+    /// Moves the changes in a release back into the Unreleased section and removes the release.
+    Demote(demote::Args),
+    /// Prints the changes in a release.
+    Show(show::Args),
+    /// Prints the changes in every release after one version up to another, for seeing what an
+    /// upgrade brings in.
+    Compare(compare::Args),
+    /// Rewrites the changelog in its canonical format.
+    Fmt(fmt::Args),
+    /// Reorders the releases from the highest version to the lowest, reporting the ones that moved.
+    Sort(sort::Args),
+    /// Prints the highest released version.
+    Latest(latest::Args),
+    /// Prints the releases and entries added or removed between two changelogs.
+    Diff(diff::Args),
+    /// Merges several changelogs into one, reporting releases that conflict.
+    Merge(merge::Args),
+    /// Tags a release as yanked, optionally adding an entry explaining why.
+    Yank(yank::Args),
+    /// Prints the Unreleased changes.
+    Unreleased(unreleased::Args),
+    /// Adds or replaces the link for a release or the Unreleased section.
+    SetLink(set_link::Args),
+    /// Adds the missing links for the releases and the Unreleased section from a repository URL.
+    GenLinks(gen_links::Args),
+    /// Writes the notes for a release without its heading, for publishing the release.
+    ReleaseNotes(release_notes::Args),
+    /// Checks that the changelog has a release for a version. Exits with 1 when it doesn't.
+    CheckVersion(check_version::Args),
+    /// Releases the Unreleased changes as the next version and updates the project manifest.
+    Bump(bump::Args),
+    /// Prints statistics about the releases, such as how often they happen.
+    Stats(stats::Args),
+    /// Prints the entries matching a pattern with the release they're in.
+    Grep(grep::Args),
+    /// Converts the changelog to JSON.
+    ToJson(json::ToJsonArgs),
+    /// Writes the changelog from JSON produced by `to-json`.
+    FromJson(json::FromJsonArgs),
+    /// Renders a release or the Unreleased section through a template.
+    Notes(notes::Args),
+    /// Installs a git hook that validates the changelog and checks its formatting.
+    InstallHook(install_hook::Args),
+    /// Lists releases without a git tag and tags without a release. Exits with 1 when there are
+    /// any.
+    VerifyTags(verify_tags::Args),
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match &cli.command {
+        Command::Validate(args) => validate::run(&cli.file, args),
+        Command::Lint(args) => lint::run(&cli.file, args),
+        Command::Init(args) => init::run(&cli.file, args),
+        Command::Import(args) => import::run(&cli.file, args),
+        Command::Add(args) => add::run(&cli.file, args),
+        Command::Release(args) => release::run(&cli.file, args),
+        Command::Demote(args) => demote::run(&cli.file, args),
+        Command::Show(args) => show::run(&cli.file, args),
+        Command::Compare(args) => compare::run(&cli.file, args),
+        Command::Fmt(args) => fmt::run(&cli.file, args),
+        Command::Sort(args) => sort::run(&cli.file, args),
+        Command::Latest(args) => latest::run(&cli.file, args),
+        Command::Diff(args) => diff::run(&cli.file, args),
+        Command::Merge(args) => merge::run(&cli.file, args),
+        Command::Yank(args) => yank::run(&cli.file, args),
+        Command::Unreleased(args) => unreleased::run(&cli.file, args),
+        Command::SetLink(args) => set_link::run(&cli.file, args),
+        Command::GenLinks(args) => gen_links::run(&cli.file, args),
+        Command::ReleaseNotes(args) => release_notes::run(&cli.file, args),
+        Command::CheckVersion(args) => check_version::run(&cli.file, args),
+        Command::Bump(args) => bump::run(&cli.file, args),
+        Command::Stats(args) => stats::run(&cli.file, args),
+        Command::Grep(args) => grep::run(&cli.file, args),
+        Command::ToJson(args) => json::to_json_run(&cli.file, args),
+        Command::FromJson(args) => json::from_json_run(&cli.file, args),
+        Command::Notes(args) => notes::run(&cli.file, args),
+        Command::InstallHook(args) => install_hook::run(&cli.file, args),
+        Command::VerifyTags(args) => verify_tags::run(&cli.file, args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            error.exit_code()
+        }
+    }
+}
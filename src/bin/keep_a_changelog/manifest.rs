@@ -0,0 +1,151 @@
+use crate::error::CliError;
+use keep_a_changelog_file::ReleaseVersion;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+lazy_static! {
+    static ref TOML_VERSION: Regex =
+        Regex::new(r#"^(\s*version\s*=\s*)(["'])[^"']*(["'])"#).expect("Should be a valid regex");
+    static ref TOML_INHERITED_VERSION: Regex =
+        Regex::new(r"^\s*version\s*(\.\s*workspace\s*=\s*true|=\s*\{\s*workspace\s*=\s*true\s*\})")
+            .expect("Should be a valid regex");
+    static ref TOML_TABLE: Regex =
+        Regex::new(r"^\s*\[\[?([^\[\]]+)\]\]?\s*(#.*)?$").expect("Should be a valid regex");
+    static ref JSON_VERSION: Regex =
+        Regex::new(r#"^("version"\s*:\s*")[^"]*(")"#).expect("Should be a valid regex");
+}
+
+const MANIFESTS: [&str; 3] = ["Cargo.toml", "package.json", "pyproject.toml"];
+
+// the tables that hold the version of the package in `Cargo.toml` and `pyproject.toml`
+const VERSION_TABLES: [&str; 4] = ["package", "workspace.package", "project", "tool.poetry"];
+
+// manifests next to the changelog are the ones released with it
+pub(crate) fn find_manifests(changelog: &Path) -> Vec<PathBuf> {
+    let dir = changelog.parent().unwrap_or(Path::new(""));
+    MANIFESTS
+        .iter()
+        .map(|name| dir.join(name))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+// a package that inherits its version with `version.workspace = true` is released with the
+// version in the workspace's manifest, which is the closest one above it with a `[workspace]` table
+pub(crate) fn workspace_manifest(path: &Path, contents: &str) -> Option<PathBuf> {
+    if path.file_name()? != "Cargo.toml" {
+        return None;
+    }
+    let mut table = String::new();
+    let inherited = contents.lines().any(|line| {
+        if let Some(name) = toml_table(line) {
+            table = name;
+            false
+        } else {
+            table == "package" && TOML_INHERITED_VERSION.is_match(line)
+        }
+    });
+    if !inherited {
+        return None;
+    }
+    fs::canonicalize(path)
+        .ok()?
+        .ancestors()
+        .skip(2)
+        .map(|dir| dir.join("Cargo.toml"))
+        .find(|manifest| {
+            fs::read_to_string(manifest).is_ok_and(|contents| {
+                contents
+                    .lines()
+                    .any(|line| toml_table(line).is_some_and(|name| name == "workspace"))
+            })
+        })
+}
+
+// the version is replaced in the text rather than by re-serializing the manifest so formatting
+// and comments are left alone
+pub(crate) fn set_version(
+    path: &Path,
+    contents: &str,
+    version: &ReleaseVersion,
+) -> Result<String, CliError> {
+    let is_json = path
+        .extension()
+        .is_some_and(|extension| extension == "json");
+    let updated = if is_json {
+        set_json_version(contents, version)
+    } else {
+        set_toml_version(contents, version)
+    };
+    updated.ok_or_else(|| CliError::ManifestVersionNotFound(path.to_path_buf()))
+}
+
+// only the `version` key of the top-level object is the package's, nested objects such as
+// `publishConfig` can have their own
+fn set_json_version(contents: &str, version: &ReleaseVersion) -> Option<String> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (index, c) in contents.char_indices() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match c {
+            '{' | '[' => depth += 1,
+            '}' | ']' => depth = depth.saturating_sub(1),
+            '"' => {
+                if let Some(captures) = JSON_VERSION
+                    .captures(&contents[index..])
+                    .filter(|_| depth == 1)
+                {
+                    let end = index + captures.get(0)?.end();
+                    return Some(format!(
+                        "{}{}{version}{}{}",
+                        &contents[..index],
+                        &captures[1],
+                        &captures[2],
+                        &contents[end..]
+                    ));
+                }
+                in_string = true;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+// table names are compared without whitespace or a trailing comment, `[ package ] # comment` is
+// the `package` table
+fn toml_table(line: &str) -> Option<String> {
+    TOML_TABLE
+        .captures(line.trim_end())
+        .map(|captures| captures[1].split_whitespace().collect())
+}
+
+fn set_toml_version(contents: &str, version: &ReleaseVersion) -> Option<String> {
+    let mut table = String::new();
+    let mut found = false;
+    let mut output = String::with_capacity(contents.len());
+    for line in contents.split_inclusive('\n') {
+        if let Some(name) = toml_table(line) {
+            table = name;
+        } else if !found && VERSION_TABLES.contains(&table.as_str()) && TOML_VERSION.is_match(line)
+        {
+            found = true;
+            output.push_str(&TOML_VERSION.replace(line, format!("${{1}}${{2}}{version}${{3}}")));
+            continue;
+        }
+        output.push_str(line);
+    }
+    found.then_some(output)
+}
@@ -0,0 +1,43 @@
+use crate::changelog_file::{read_changelog, write, write_changelog};
+use crate::error::CliError;
+use crate::output::to_json;
+use std::path::{Path, PathBuf};
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The changelogs to merge. Later changelogs are merged into the first one, which wins when
+    /// they conflict.
+    #[arg(required = true, num_args = 2..)]
+    changelogs: Vec<PathBuf>,
+    /// Where to write the merged changelog. Prints it when not given.
+    #[arg(long, short, default_value = "-")]
+    output: PathBuf,
+    /// How to write the merged changelog.
+    #[arg(long, value_enum, default_value_t = Format::Md)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// The changelog as markdown.
+    Md,
+    /// The changelog as JSON, in the structure written by `to-json`.
+    Json,
+}
+
+pub(crate) fn run(_file: &Path, args: &Args) -> Result<(), CliError> {
+    let mut changelogs = args.changelogs.iter();
+    let Some(first) = changelogs.next() else {
+        return Ok(());
+    };
+    let mut merged = read_changelog(first)?;
+    for path in changelogs {
+        for conflict in merged.merge(&read_changelog(path)?) {
+            eprintln!("conflict: {}: {conflict}", path.display());
+        }
+    }
+    match args.format {
+        Format::Md => write_changelog(&args.output, &merged),
+        Format::Json => write(&args.output, &format!("{}\n", to_json(&merged))),
+    }
+}
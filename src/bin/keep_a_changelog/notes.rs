@@ -0,0 +1,72 @@
+use crate::changelog_file::{read, read_changelog, write};
+use crate::error::CliError;
+use keep_a_changelog_file::{Changes, Section};
+use std::error::Error;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The version of the release to render, or `unreleased`.
+    #[arg(default_value = "unreleased")]
+    section: Section,
+    /// A [Tera](https://keats.github.io/tera/docs/) template. It can use `section`, `version`,
+    /// `date`, `tag`, `link`, and `changes`, a list of `group` and `entries` pairs in changelog
+    /// order.
+    #[arg(long, short)]
+    template: PathBuf,
+    /// Where to write the rendered notes. Prints them when not given.
+    #[arg(long, short, default_value = "-")]
+    output: PathBuf,
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let changelog = read_changelog(file)?;
+    let context = match &args.section {
+        Section::Unreleased => serde_json::json!({
+            "section": "Unreleased",
+            "link": changelog.unreleased.link,
+            "changes": changes(&changelog.unreleased.changes),
+        }),
+        Section::Release(version) => {
+            let release = changelog.releases.get_version(version).ok_or_else(|| {
+                CliError::ReleaseNotFound(version.to_string(), file.to_path_buf())
+            })?;
+            serde_json::json!({
+                "section": version,
+                "version": version,
+                "date": release.date,
+                "tag": release.tag.as_ref().map(ToString::to_string),
+                "link": release.link,
+                "changes": changes(&release.changes),
+            })
+        }
+    };
+
+    let template = read(&args.template)?;
+    let notes = tera::Context::from_value(context)
+        .and_then(|context| tera::Tera::one_off(&template, &context, false))
+        .map_err(|e| CliError::Template(args.template.clone(), error_chain(&e)))?;
+    write(&args.output, &notes)
+}
+
+// a list rather than a map so templates see the groups in the order they appear in the changelog
+fn changes(changes: &Changes) -> serde_json::Value {
+    changes
+        .iter()
+        .map(|(change_group, entries)| {
+            serde_json::json!({ "group": change_group.to_string(), "entries": entries })
+        })
+        .collect()
+}
+
+// tera puts the useful part of the message, like the line of a syntax error, in the sources
+fn error_chain(error: &tera::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(error) = source {
+        let _ = write!(message, "\n{error}");
+        source = error.source();
+    }
+    message
+}
@@ -0,0 +1,17 @@
+use crate::changelog_file::is_stdio;
+use serde::Serialize;
+use std::path::Path;
+
+pub(crate) fn to_json(value: &impl Serialize) -> String {
+    serde_json::to_string_pretty(value).expect("Output should serialize to JSON")
+}
+
+// a changelog read from stdin is written back to stdout, so what a command did is printed to
+// stderr instead of being mixed into the changelog
+pub(crate) fn print_json(file: &Path, value: &impl Serialize) {
+    if is_stdio(file) {
+        eprintln!("{}", to_json(value));
+    } else {
+        println!("{}", to_json(value));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::changelog_file::{is_stdio, read_changelog, write_changelog};
+use crate::error::CliError;
+use crate::output::to_json;
+use keep_a_changelog_file::{
+    LinkTemplate, PromoteOptions, ReleaseDate, ReleaseLink, ReleaseTag, ReleaseVersion,
+};
+use std::path::Path;
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The version of the new release.
+    #[arg(long)]
+    version: ReleaseVersion,
+    /// The date of the new release in YYYY-MM-DD format. Defaults to today.
+    #[arg(long)]
+    date: Option<ReleaseDate>,
+    /// Mark the release as `yanked` or as having `no-changes`.
+    #[arg(long, value_parser = parse_tag)]
+    tag: Option<ReleaseTag>,
+    /// The link to the release.
+    #[arg(long)]
+    link: Option<ReleaseLink>,
+    /// The URL of the repository, used to generate links for the new release, any older releases
+    /// without a link, and the Unreleased section.
+    #[arg(long, value_name = "REPO_URL", conflicts_with = "link")]
+    auto_link: Option<LinkTemplate>,
+    /// How to print the new release.
+    #[arg(long, value_enum, default_value_t = Format::Md)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// The markdown of the release's change groups.
+    Md,
+    /// The release as JSON, including its version, date, tag, and link.
+    Json,
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let mut changelog = read_changelog(file)?;
+
+    let mut promote_options = PromoteOptions::new(args.version.clone());
+    if let Some(date) = &args.date {
+        promote_options = promote_options.with_date(date.clone());
+    }
+    if let Some(tag) = &args.tag {
+        promote_options = promote_options.with_tag(tag.clone());
+    }
+    if let Some(link) = &args.link {
+        promote_options = promote_options.with_link(link.clone());
+    }
+    changelog
+        .promote_unreleased(&promote_options)
+        .map_err(CliError::Promote)?;
+    if let Some(link_template) = &args.auto_link {
+        changelog.generate_links(link_template);
+    }
+
+    write_changelog(file, &changelog)?;
+
+    // the notes go to stdout so they can be piped into whatever publishes the release, unless the
+    // changelog itself was written there
+    if is_stdio(file) {
+        return Ok(());
+    }
+    let release = &changelog.releases[&args.version];
+    match args.format {
+        Format::Md => println!("{}", release.changes),
+        Format::Json => println!("{}", to_json(release)),
+    }
+    Ok(())
+}
+
+// `no-changes` is accepted so the tag can be passed without quoting
+fn parse_tag(value: &str) -> Result<ReleaseTag, String> {
+    value
+        .replace('-', " ")
+        .parse::<ReleaseTag>()
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,47 @@
+use crate::changelog_file::{read_changelog, write};
+use crate::error::CliError;
+use crate::latest::latest_release;
+use keep_a_changelog_file::ReleaseVersion;
+use std::path::{Path, PathBuf};
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The version of the release.
+    #[arg(long, conflicts_with = "latest")]
+    version: Option<ReleaseVersion>,
+    /// Use the release with the highest version. This is the default.
+    #[arg(long)]
+    latest: bool,
+    /// Use the Unreleased changes, e.g. to draft the notes before the release is made.
+    #[arg(long, conflicts_with_all = ["version", "latest"])]
+    unreleased: bool,
+    /// Where to write the notes, e.g. for `gh release create --notes-file`. Prints them when not
+    /// given.
+    #[arg(long, short, default_value = "-")]
+    output: PathBuf,
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let changelog = read_changelog(file)?;
+    let changes = if args.unreleased {
+        &changelog.unreleased.changes
+    } else if let Some(version) = &args.version {
+        &changelog
+            .releases
+            .get_version(version)
+            .ok_or_else(|| CliError::ReleaseNotFound(version.to_string(), file.to_path_buf()))?
+            .changes
+    } else {
+        &latest_release(&changelog, false)
+            .ok_or_else(|| CliError::NoReleases(file.to_path_buf()))?
+            .changes
+    };
+    // only the change groups, since release pages already show the version and date
+    let notes = changes.to_string();
+    let notes = notes.trim_end();
+    if notes.is_empty() {
+        write(&args.output, "")
+    } else {
+        write(&args.output, &format!("{notes}\n"))
+    }
+}
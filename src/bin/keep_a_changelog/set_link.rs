@@ -0,0 +1,52 @@
+use crate::changelog_file::{read_changelog, write_changelog};
+use crate::error::CliError;
+use crate::output::print_json;
+use keep_a_changelog_file::{ReleaseLink, Section};
+use std::path::Path;
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The version of the release to link, or `unreleased`.
+    section: Section,
+    /// The URL the section links to, usually a comparison with the previous release.
+    link: ReleaseLink,
+    /// What to print once the link is set.
+    #[arg(long, value_enum, default_value_t = Format::Quiet)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// Nothing.
+    Quiet,
+    /// The section, its new link, and the link it replaced as JSON.
+    Json,
+}
+
+// link definitions are written from the sections they belong to, so there's no footer to keep in
+// sync, only the link to replace
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let mut changelog = read_changelog(file)?;
+    let link = match &args.section {
+        Section::Unreleased => &mut changelog.unreleased.link,
+        Section::Release(version) => {
+            &mut changelog
+                .releases
+                .get_version_mut(version)
+                .ok_or_else(|| CliError::ReleaseNotFound(version.to_string(), file.to_path_buf()))?
+                .link
+        }
+    };
+    let previous_link = link.replace(args.link.clone());
+    if let Format::Json = args.format {
+        print_json(
+            file,
+            &serde_json::json!({
+                "section": args.section.to_string(),
+                "link": args.link,
+                "previous_link": previous_link,
+            }),
+        );
+    }
+    write_changelog(file, &changelog)
+}
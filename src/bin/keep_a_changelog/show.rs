@@ -0,0 +1,64 @@
+use crate::changelog_file::read_changelog;
+use crate::error::CliError;
+use crate::output::to_json;
+use keep_a_changelog_file::Changes;
+use std::fmt::Write;
+use std::path::Path;
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The version of the release to show, or `unreleased`.
+    version: String,
+    /// How to print the release.
+    #[arg(long, value_enum, default_value_t = Format::Md)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// The markdown of the release's change groups.
+    Md,
+    /// The release as JSON, including its version, date, tag, and link.
+    Json,
+    /// The changes as plain text without markdown syntax.
+    Text,
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let changelog = read_changelog(file)?;
+    let output = if args.version.eq_ignore_ascii_case("unreleased") {
+        let unreleased = &changelog.unreleased;
+        match args.format {
+            Format::Md => unreleased.changes.to_string(),
+            Format::Json => to_json(unreleased),
+            Format::Text => to_text(&unreleased.changes),
+        }
+    } else {
+        let release = changelog
+            .releases
+            .get(&args.version)
+            .ok_or_else(|| CliError::ReleaseNotFound(args.version.clone(), file.to_path_buf()))?;
+        match args.format {
+            Format::Md => release.changes.to_string(),
+            Format::Json => to_json(release),
+            Format::Text => to_text(&release.changes),
+        }
+    };
+    println!("{}", output.trim_end());
+    Ok(())
+}
+
+// for announcement messages and other places that don't render markdown
+fn to_text(changes: &Changes) -> String {
+    let mut text = String::new();
+    for (change_group, items) in changes {
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        let _ = writeln!(text, "{change_group}:");
+        for item in items {
+            let _ = writeln!(text, "  * {}", item.replace('\n', "\n    "));
+        }
+    }
+    text
+}
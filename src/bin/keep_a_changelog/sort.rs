@@ -0,0 +1,61 @@
+use crate::changelog_file::{read_changelog, write_changelog};
+use crate::error::CliError;
+use crate::output::print_json;
+use keep_a_changelog_file::ReleaseVersion;
+use serde::Serialize;
+use std::path::Path;
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// Don't rewrite the file, just exit with 1 if the releases aren't in order.
+    #[arg(long)]
+    check: bool,
+    /// How to report the releases that moved.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// A `moved: <version> to position <n>` line on stderr for each release that moved.
+    Text,
+    /// The releases that moved with their new positions as JSON.
+    Json,
+}
+
+#[derive(Serialize)]
+struct Moved<'a> {
+    version: &'a ReleaseVersion,
+    position: usize,
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let mut changelog = read_changelog(file)?;
+    let moved = changelog.sort_releases();
+    let moved = changelog
+        .releases
+        .iter()
+        .enumerate()
+        .filter(|(_, (version, _))| moved.contains(version))
+        .map(|(position, (version, _))| Moved {
+            version,
+            position: position + 1,
+        })
+        .collect::<Vec<_>>();
+    match args.format {
+        // reported on stderr so the changelog can still be written to stdout
+        Format::Text => {
+            for Moved { version, position } in &moved {
+                eprintln!("moved: {version} to position {position}");
+            }
+        }
+        Format::Json => print_json(file, &serde_json::json!({ "moved": moved })),
+    }
+    if moved.is_empty() {
+        return Ok(());
+    }
+    if args.check {
+        Err(CliError::NotSorted(file.to_path_buf()))?;
+    }
+    write_changelog(file, &changelog)
+}
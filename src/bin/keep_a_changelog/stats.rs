@@ -0,0 +1,65 @@
+use crate::changelog_file::read_changelog;
+use crate::error::CliError;
+use crate::output::to_json;
+use keep_a_changelog_file::{ChangelogStatistics, ReleaseVersion};
+use std::fmt::Write;
+use std::path::Path;
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// Only count this release and the ones with higher versions.
+    #[arg(long)]
+    since: Option<ReleaseVersion>,
+    /// How to print the statistics.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// A summary for people to read.
+    Text,
+    /// The statistics as JSON, including the days between each pair of releases.
+    Json,
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let mut changelog = read_changelog(file)?;
+    if let Some(since) = &args.since {
+        changelog.releases = changelog
+            .releases
+            .into_iter()
+            .filter(|(version, _)| version >= since)
+            .collect();
+    }
+    let statistics = changelog.statistics();
+    match args.format {
+        Format::Text => print!("{}", to_text(&statistics)),
+        Format::Json => println!("{}", to_json(&statistics)),
+    }
+    Ok(())
+}
+
+fn to_text(statistics: &ChangelogStatistics) -> String {
+    let mut text = format!(
+        "Releases: {} ({} yanked)\n",
+        statistics.releases, statistics.yanked_releases
+    );
+    if let (Some(first), Some(latest)) = (&statistics.first_release, &statistics.latest_release) {
+        let _ = writeln!(text, "Released: {first} to {latest}");
+    }
+    if let Some(average) = statistics.average_days_between_releases() {
+        let min = statistics.days_between_releases.iter().min().unwrap_or(&0);
+        let max = statistics.days_between_releases.iter().max().unwrap_or(&0);
+        let _ = writeln!(
+            text,
+            "Days between releases: {average:.1} on average, {min} to {max}"
+        );
+    }
+    let _ = writeln!(text, "Entries:");
+    for (change_group, count) in &statistics.entries {
+        let _ = writeln!(text, "  {change_group}: {count}");
+    }
+    let _ = writeln!(text, "Contributors: {}", statistics.contributors.len());
+    text
+}
@@ -0,0 +1,71 @@
+use crate::changelog_file::read_changelog;
+use crate::error::CliError;
+use crate::output::to_json;
+use keep_a_changelog_file::ChangeGroup;
+use std::path::Path;
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// Fail when there are no unreleased changes, e.g. to require a changelog entry in pull
+    /// requests.
+    #[arg(long)]
+    require_nonempty: bool,
+    /// Fail when there are fewer unreleased entries than this, so a large change can't be
+    /// described by a single vague entry.
+    #[arg(long, value_name = "COUNT")]
+    min_entries: Option<usize>,
+    /// Fail unless there's an unreleased entry of this type of change. Can be repeated.
+    #[arg(long, value_name = "GROUP")]
+    require_group: Vec<ChangeGroup>,
+    /// How to print the unreleased changes.
+    #[arg(long, value_enum, default_value_t = Format::Md)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// The markdown of the Unreleased change groups.
+    Md,
+    /// The Unreleased section as JSON, including its link.
+    Json,
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let changelog = read_changelog(file)?;
+    let unreleased = &changelog.unreleased;
+    match args.format {
+        Format::Md if unreleased.changes.is_empty() => {}
+        Format::Md => println!("{}", unreleased.changes.to_string().trim_end()),
+        Format::Json => println!("{}", to_json(unreleased)),
+    }
+    if args.require_nonempty && unreleased.changes.is_empty() {
+        Err(CliError::NoUnreleasedChanges(file.to_path_buf()))?;
+    }
+    if let Some(min_entries) = args.min_entries {
+        let entries = unreleased
+            .changes
+            .iter()
+            .map(|(_, items)| items.len())
+            .sum::<usize>();
+        if entries < min_entries {
+            Err(CliError::TooFewUnreleasedChanges(
+                file.to_path_buf(),
+                entries,
+                min_entries,
+            ))?;
+        }
+    }
+    for change_group in &args.require_group {
+        if unreleased
+            .changes
+            .get(change_group)
+            .map_or(true, <[String]>::is_empty)
+        {
+            Err(CliError::NoUnreleasedChangesInGroup(
+                file.to_path_buf(),
+                change_group.clone(),
+            ))?;
+        }
+    }
+    Ok(())
+}
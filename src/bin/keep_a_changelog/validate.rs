@@ -0,0 +1,366 @@
+use crate::changelog_file::{is_stdio, read};
+use crate::error::CliError;
+use crate::output::to_json;
+use keep_a_changelog_file::{Changelog, Diagnostic, DiagnosticsReport, RenderOptions, Severity};
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The changelogs to validate, as paths, glob patterns such as `crates/*/CHANGELOG.md`, or `-`
+    /// for stdin. Defaults to `--file`.
+    paths: Vec<String>,
+    /// Fail when there are warnings as well as errors.
+    #[arg(long)]
+    warnings_as_errors: bool,
+    /// How to print the diagnostics.
+    #[arg(long, visible_alias = "format", value_enum, default_value_t = Format::Text)]
+    error_format: Format,
+    /// Skip the changelogs matching this glob pattern, e.g. `vendor/**`. Can be repeated.
+    #[arg(long, value_name = "PATTERN")]
+    exclude: Vec<String>,
+    /// Also write the `json` or `sarif` report to this file, e.g. to upload it as a build artifact.
+    #[arg(long, value_name = "PATH")]
+    report: Option<PathBuf>,
+    /// The format of the `--report` file.
+    #[arg(long, value_enum, default_value_t = ReportFormat::Json, requires = "report")]
+    report_format: ReportFormat,
+    /// Keep running and validate again whenever the changelogs change.
+    #[arg(long)]
+    watch: bool,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+pub(crate) enum Format {
+    /// `human` when stdout is a terminal, otherwise a `path:line:col: [code] message` line per
+    /// diagnostic with any details indented on the lines after it.
+    Text,
+    /// Each diagnostic with the offending lines of the changelog, in color when stdout is a
+    /// terminal and `NO_COLOR` isn't set.
+    Human,
+    /// A single `path:line:col: severity[code]: message` line per diagnostic, the format compilers
+    /// like GCC use, which editor problem matchers can parse.
+    Short,
+    /// A JSON report with the path, tool version, and diagnostics, or an array of reports when
+    /// validating several files.
+    Json,
+    /// A SARIF log with the diagnostics for all the changelogs, for code scanning tools.
+    Sarif,
+    /// GitHub Actions workflow commands, which show each diagnostic as an annotation on the
+    /// offending lines of the changelog in pull requests.
+    Github,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum ReportFormat {
+    /// The same reports as `--error-format json`.
+    Json,
+    /// A SARIF log, for code scanning tools.
+    Sarif,
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let files = if args.paths.is_empty() {
+        vec![file.to_path_buf()]
+    } else {
+        expand(&args.paths, &args.exclude)?
+    };
+    if args.watch {
+        return watch(&files, args);
+    }
+    validate(&files, args)
+}
+
+fn validate(files: &[PathBuf], args: &Args) -> Result<(), CliError> {
+    if let [file] = files {
+        let input = read(file)?;
+        let diagnostics = Changelog::diagnose(&input);
+        let reports = [report(file, &diagnostics)];
+        match args.error_format {
+            Format::Json => println!("{}", render_reports(&reports, ReportFormat::Json, true)),
+            Format::Sarif => println!("{}", render_reports(&reports, ReportFormat::Sarif, true)),
+            format => print_diagnostics(file, &input, &diagnostics, format),
+        }
+        write_report(args, &reports, true)?;
+        let failures = failures(&diagnostics, args);
+        if failures > 0 {
+            Err(CliError::Invalid(file.clone(), failures))?;
+        }
+        return Ok(());
+    }
+
+    let mut failed = 0;
+    let mut unreadable = false;
+    let mut reports = vec![];
+    for file in files {
+        let input = match read(file) {
+            Ok(input) => input,
+            Err(error) => {
+                eprintln!("error: {error}");
+                failed += 1;
+                unreadable = true;
+                continue;
+            }
+        };
+        let diagnostics = Changelog::diagnose(&input);
+        if failures(&diagnostics, args) > 0 {
+            failed += 1;
+        }
+        match args.error_format {
+            Format::Json | Format::Sarif => {}
+            format => {
+                print_diagnostics(file, &input, &diagnostics, format);
+                println!("{}: {}", file.display(), summary(&diagnostics));
+            }
+        }
+        reports.push(report(file, &diagnostics));
+    }
+    match args.error_format {
+        Format::Json => println!("{}", render_reports(&reports, ReportFormat::Json, false)),
+        Format::Sarif => println!("{}", render_reports(&reports, ReportFormat::Sarif, false)),
+        _ => {}
+    }
+    write_report(args, &reports, false)?;
+
+    if failed > 0 {
+        Err(CliError::ValidationFailed {
+            failed,
+            total: files.len(),
+            unreadable,
+        })?;
+    }
+    Ok(())
+}
+
+// a single changelog is reported as an object and several as an array, the same as they're printed
+fn render_reports(
+    reports: &[DiagnosticsReport],
+    format: ReportFormat,
+    single_file: bool,
+) -> String {
+    match (format, reports) {
+        (ReportFormat::Json, [report]) if single_file => report.to_json(),
+        (ReportFormat::Json, reports) => to_json(&reports),
+        (ReportFormat::Sarif, reports) => DiagnosticsReport::to_sarif(reports),
+    }
+}
+
+fn write_report(
+    args: &Args,
+    reports: &[DiagnosticsReport],
+    single_file: bool,
+) -> Result<(), CliError> {
+    let Some(path) = &args.report else {
+        return Ok(());
+    };
+    let contents = render_reports(reports, args.report_format, single_file);
+    fs::write(path, format!("{contents}\n")).map_err(|e| CliError::Write(path.clone(), e))
+}
+
+// polling keeps this portable without a file watching dependency, and editors that save by
+// replacing the file are handled the same as ones that write in place. The length and a hash of
+// the contents are compared as well as the modification time, since writes within the timestamp
+// resolution of the file system leave it unchanged
+fn watch(files: &[PathBuf], args: &Args) -> Result<(), CliError> {
+    if files.iter().any(|file| is_stdio(file)) {
+        Err(CliError::WatchStdin)?;
+    }
+    let modified = || {
+        files
+            .iter()
+            .map(|file| {
+                let modified = fs::metadata(file)
+                    .and_then(|metadata| metadata.modified())
+                    .ok();
+                let contents = fs::read(file).ok().map(|contents| {
+                    let mut hasher = DefaultHasher::new();
+                    contents.hash(&mut hasher);
+                    (contents.len(), hasher.finish())
+                });
+                (modified, contents)
+            })
+            .collect::<Vec<_>>()
+    };
+    loop {
+        let last_modified = modified();
+        match validate(files, args) {
+            Ok(()) => eprintln!("No problems found, watching for changes"),
+            Err(error) => eprintln!("error: {error}\nWatching for changes"),
+        }
+        while modified() == last_modified {
+            thread::sleep(Duration::from_millis(250));
+        }
+        println!();
+    }
+}
+
+// patterns are expanded here because they're often quoted so the shell doesn't expand them first,
+// and a pattern that matches nothing is reported since it's most likely a typo
+fn expand(paths: &[String], exclude: &[String]) -> Result<Vec<PathBuf>, CliError> {
+    let exclude = exclude
+        .iter()
+        .map(|pattern| {
+            glob::Pattern::new(pattern).map_err(|e| CliError::InvalidPattern(pattern.clone(), e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut files = vec![];
+    for path in paths {
+        if is_stdio(Path::new(path)) || !path.contains(['*', '?', '[']) {
+            files.push(PathBuf::from(path));
+            continue;
+        }
+        let matches = glob::glob(path)
+            .map_err(|e| CliError::InvalidPattern(path.clone(), e))?
+            .filter_map(Result::ok)
+            .collect::<Vec<_>>();
+        if matches.is_empty() {
+            Err(CliError::NoMatches(path.clone()))?;
+        }
+        files.extend(matches);
+    }
+    files.retain(|file| !exclude.iter().any(|pattern| pattern.matches_path(file)));
+    Ok(files)
+}
+
+fn failures(diagnostics: &[Diagnostic], args: &Args) -> usize {
+    diagnostics
+        .iter()
+        .filter(|diagnostic| {
+            diagnostic.severity == Severity::Error
+                || (args.warnings_as_errors && diagnostic.severity == Severity::Warning)
+        })
+        .count()
+}
+
+pub(crate) fn report(file: &Path, diagnostics: &[Diagnostic]) -> DiagnosticsReport {
+    DiagnosticsReport::new(file.display().to_string(), diagnostics.to_vec())
+}
+
+// the diagnostics are printed to stdout, so excerpts and color are only used when that's a
+// terminal and never end up in a redirected report
+fn is_interactive() -> bool {
+    std::io::stdout().is_terminal()
+}
+
+// people reading the output in a terminal get an excerpt of the changelog under each diagnostic,
+// anything else gets one line per diagnostic that tools can parse
+pub(crate) fn print_diagnostics(
+    file: &Path,
+    input: &str,
+    diagnostics: &[Diagnostic],
+    format: Format,
+) {
+    match format {
+        Format::Human => {
+            let options = RenderOptions::new()
+                .with_color(
+                    is_interactive()
+                        && std::env::var_os("NO_COLOR").map_or(true, |value| value.is_empty()),
+                )
+                .with_path(file.display().to_string());
+            for diagnostic in diagnostics {
+                println!("{}\n", diagnostic.render(input, &options));
+            }
+        }
+        Format::Short => {
+            for diagnostic in diagnostics {
+                println!("{}", format_short(file, diagnostic));
+            }
+        }
+        Format::Github => {
+            for diagnostic in diagnostics {
+                println!("{}", format_github(file, diagnostic));
+            }
+        }
+        Format::Text if is_interactive() => {
+            print_diagnostics(file, input, diagnostics, Format::Human);
+        }
+        Format::Text | Format::Json | Format::Sarif => {
+            for diagnostic in diagnostics {
+                println!("{}", format_diagnostic(file, diagnostic));
+            }
+        }
+    }
+}
+
+fn summary(diagnostics: &[Diagnostic]) -> String {
+    let count = |severity: Severity| {
+        diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count()
+    };
+    let plural =
+        |count: usize, noun: &str| format!("{count} {noun}{}", if count == 1 { "" } else { "s" });
+    match (count(Severity::Error), count(Severity::Warning)) {
+        (0, 0) => "ok".to_string(),
+        (errors, warnings) => format!(
+            "{}, {}",
+            plural(errors, "error"),
+            plural(warnings, "warning")
+        ),
+    }
+}
+
+// `path:line:col: [code] message` is understood by most editors and CI log annotations, and the
+// code is the compliance rule when there is one so it can be allowed in configuration
+fn format_diagnostic(file: &Path, diagnostic: &Diagnostic) -> String {
+    let code = diagnostic
+        .rule
+        .map_or_else(|| diagnostic.severity.to_string(), |rule| rule.to_string());
+    format!(
+        "{}:{}:{}: [{code}] {}",
+        file.display(),
+        diagnostic.line,
+        diagnostic.column,
+        diagnostic.message.replace('\n', "\n    ")
+    )
+}
+
+// the details of a message are joined onto the same line since problem matchers only read one
+fn format_short(file: &Path, diagnostic: &Diagnostic) -> String {
+    let severity = match diagnostic.rule {
+        Some(rule) => format!("{}[{rule}]", diagnostic.severity),
+        None => diagnostic.severity.to_string(),
+    };
+    format!(
+        "{}:{}:{}: {severity}: {}",
+        file.display(),
+        diagnostic.line,
+        diagnostic.column,
+        diagnostic.message.lines().collect::<Vec<_>>().join("; ")
+    )
+}
+
+// https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions#setting-an-error-message
+fn format_github(file: &Path, diagnostic: &Diagnostic) -> String {
+    let title = diagnostic
+        .rule
+        .map_or_else(|| "Invalid changelog".to_string(), |rule| rule.to_string());
+    format!(
+        "::{} file={},line={},col={},endLine={},endColumn={},title={}::{}",
+        diagnostic.severity,
+        escape_property(&file.display().to_string()),
+        diagnostic.line,
+        diagnostic.column,
+        diagnostic.end_line,
+        diagnostic.end_column,
+        escape_property(&title),
+        escape_data(&diagnostic.message)
+    )
+}
+
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
@@ -0,0 +1,114 @@
+use crate::changelog_file::read_changelog;
+use crate::error::CliError;
+use crate::git::git;
+use crate::output::to_json;
+use keep_a_changelog_file::ReleaseVersion;
+use serde::Serialize;
+use std::path::Path;
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// How a version is turned into a tag name. Tags that don't match it are ignored.
+    #[arg(long, default_value = "v{version}", value_parser = parse_tag_template)]
+    tag_template: TagTemplate,
+    /// How to print the releases and tags that don't match.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// One line for each release without a tag and each tag without a release.
+    Text,
+    /// The untagged releases and unreleased tags as JSON.
+    Json,
+}
+
+// a release without a tag, or a tag without a release, with the name the other would have
+#[derive(Serialize)]
+struct Mismatch {
+    version: ReleaseVersion,
+    tag: String,
+}
+
+#[derive(Serialize)]
+struct Drift {
+    untagged_releases: Vec<Mismatch>,
+    unreleased_tags: Vec<Mismatch>,
+}
+
+#[derive(Clone)]
+pub(crate) struct TagTemplate {
+    prefix: String,
+    suffix: String,
+}
+
+impl TagTemplate {
+    pub(crate) fn tag(&self, version: &ReleaseVersion) -> String {
+        format!("{}{version}{}", self.prefix, self.suffix)
+    }
+
+    pub(crate) fn version(&self, tag: &str) -> Option<ReleaseVersion> {
+        tag.strip_prefix(&self.prefix)?
+            .strip_suffix(&self.suffix)?
+            .parse()
+            .ok()
+    }
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let changelog = read_changelog(file)?;
+    let template = &args.tag_template;
+    let tags = git(file, &["tag", "--list"])?;
+    let tagged = tags
+        .lines()
+        .filter_map(|tag| Some((tag, template.version(tag)?)))
+        .collect::<Vec<_>>();
+
+    let drift = Drift {
+        untagged_releases: changelog
+            .releases
+            .iter()
+            .filter(|(version, _)| !tagged.iter().any(|(_, tagged)| tagged == *version))
+            .map(|(version, _)| Mismatch {
+                version: version.clone(),
+                tag: template.tag(version),
+            })
+            .collect(),
+        unreleased_tags: tagged
+            .iter()
+            .filter(|(_, version)| !changelog.releases.contains_version(version))
+            .map(|(tag, version)| Mismatch {
+                version: version.clone(),
+                tag: (*tag).to_string(),
+            })
+            .collect(),
+    };
+
+    match args.format {
+        Format::Text => {
+            for Mismatch { version, tag } in &drift.untagged_releases {
+                println!("{version}: no tag named {tag}");
+            }
+            for Mismatch { version, tag } in &drift.unreleased_tags {
+                println!("{tag}: no release {version} in the changelog");
+            }
+        }
+        Format::Json => println!("{}", to_json(&drift)),
+    }
+    let count = drift.untagged_releases.len() + drift.unreleased_tags.len();
+    if count > 0 {
+        Err(CliError::TagDrift(count))?;
+    }
+    Ok(())
+}
+
+pub(crate) fn parse_tag_template(value: &str) -> Result<TagTemplate, String> {
+    let (prefix, suffix) = value
+        .split_once("{version}")
+        .ok_or_else(|| "the template must contain {version}".to_string())?;
+    Ok(TagTemplate {
+        prefix: prefix.to_string(),
+        suffix: suffix.to_string(),
+    })
+}
@@ -0,0 +1,43 @@
+use crate::add::parse_entry;
+use crate::changelog_file::{read_changelog, write_changelog};
+use crate::error::CliError;
+use crate::output::print_json;
+use keep_a_changelog_file::{ChangeGroup, ReleaseVersion};
+use std::path::Path;
+
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The version of the release to yank.
+    version: ReleaseVersion,
+    /// An entry to add to the release explaining why it was yanked.
+    #[arg(long, value_parser = parse_entry)]
+    note: Option<String>,
+    /// The type of change the note is listed under.
+    #[arg(long, short, default_value = "changed", requires = "note")]
+    group: ChangeGroup,
+    /// What to print once the release is yanked.
+    #[arg(long, value_enum, default_value_t = Format::Quiet)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// Nothing.
+    Quiet,
+    /// The yanked release as JSON.
+    Json,
+}
+
+pub(crate) fn run(file: &Path, args: &Args) -> Result<(), CliError> {
+    let mut changelog = read_changelog(file)?;
+    let release = changelog
+        .yank(&args.version)
+        .ok_or_else(|| CliError::ReleaseNotFound(args.version.to_string(), file.to_path_buf()))?;
+    if let Some(note) = &args.note {
+        release.add(args.group.clone(), note);
+    }
+    if let Format::Json = args.format {
+        print_json(file, release);
+    }
+    write_changelog(file, &changelog)
+}
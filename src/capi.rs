@@ -0,0 +1,160 @@
+//! A C API for embedding the parser in tools that aren't written in Rust.
+//!
+//! Build a shared library with:
+//!
+//! ```sh
+//! cargo rustc --release --lib --crate-type cdylib --features capi
+//! ```
+//!
+//! The declarations are in `include/keep_a_changelog_file.h`. Strings passed in must be valid
+//! UTF-8 and nul-terminated. Every pointer returned by this API is owned by the caller and must be
+//! released with [`kacl_free`] or [`kacl_free_string`].
+#![allow(unsafe_code)]
+
+use crate::Changelog;
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+/// Parses a changelog, returning null if `input` is null, isn't valid UTF-8, or isn't a valid
+/// changelog. Use [`kacl_diagnostics`] to find out why a changelog couldn't be parsed.
+///
+/// # Safety
+///
+/// `input` must be null or point to a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn kacl_parse(input: *const c_char) -> *mut Changelog {
+    // SAFETY: the caller guarantees `input` is null or a nul-terminated string
+    match unsafe { read_str(input) }.and_then(|input| input.parse::<Changelog>().ok()) {
+        Some(changelog) => Box::into_raw(Box::new(changelog)),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Returns the [diagnostics](Changelog::diagnose) for a changelog as a JSON array, or null if
+/// `input` is null or isn't valid UTF-8.
+///
+/// # Safety
+///
+/// `input` must be null or point to a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn kacl_diagnostics(input: *const c_char) -> *mut c_char {
+    // SAFETY: the caller guarantees `input` is null or a nul-terminated string
+    match unsafe { read_str(input) } {
+        Some(input) => into_c_string(
+            &serde_json::to_string(&Changelog::diagnose(input))
+                .expect("Diagnostics should serialize to JSON"),
+        ),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Writes a changelog returned by [`kacl_parse`] back out as markdown, or returns null if
+/// `changelog` is null.
+///
+/// # Safety
+///
+/// `changelog` must be null or a pointer returned by [`kacl_parse`] that hasn't been freed.
+#[no_mangle]
+pub unsafe extern "C" fn kacl_to_string(changelog: *const Changelog) -> *mut c_char {
+    // SAFETY: the caller guarantees `changelog` is null or a live pointer from `kacl_parse`
+    match unsafe { changelog.as_ref() } {
+        Some(changelog) => into_c_string(&changelog.to_string()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Frees a changelog returned by [`kacl_parse`]. Passing null does nothing.
+///
+/// # Safety
+///
+/// `changelog` must be null or a pointer returned by [`kacl_parse`] that hasn't been freed.
+#[no_mangle]
+pub unsafe extern "C" fn kacl_free(changelog: *mut Changelog) {
+    if !changelog.is_null() {
+        // SAFETY: the caller guarantees this pointer came from `Box::into_raw` in `kacl_parse`
+        drop(unsafe { Box::from_raw(changelog) });
+    }
+}
+
+/// Frees a string returned by [`kacl_diagnostics`] or [`kacl_to_string`]. Passing null does
+/// nothing.
+///
+/// # Safety
+///
+/// `value` must be null or a string returned by this API that hasn't been freed.
+#[no_mangle]
+pub unsafe extern "C" fn kacl_free_string(value: *mut c_char) {
+    if !value.is_null() {
+        // SAFETY: the caller guarantees this pointer came from `CString::into_raw`
+        drop(unsafe { CString::from_raw(value) });
+    }
+}
+
+unsafe fn read_str<'a>(input: *const c_char) -> Option<&'a str> {
+    if input.is_null() {
+        return None;
+    }
+    // SAFETY: checked for null above and the caller guarantees the string is nul-terminated
+    unsafe { CStr::from_ptr(input) }.to_str().ok()
+}
+
+// a changelog can't contain a nul byte once parsed from a C string, but entries added through the
+// Rust API could, so those are dropped rather than truncating the output
+fn into_c_string(value: &str) -> *mut c_char {
+    CString::new(value.replace('\0', ""))
+        .expect("Nul bytes were removed")
+        .into_raw()
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_parse_and_to_string() {
+        let input = CString::new(
+            "# Changelog\n\n## [Unreleased]\n\n### Added\n\n- Added X\n\n## [1.0.0] - 2024-01-01\n",
+        )
+        .unwrap();
+        unsafe {
+            let changelog = kacl_parse(input.as_ptr());
+            assert!(!changelog.is_null());
+
+            let output = kacl_to_string(changelog);
+            assert_eq!(
+                CStr::from_ptr(output).to_str().unwrap(),
+                (*changelog).to_string()
+            );
+
+            kacl_free_string(output);
+            kacl_free(changelog);
+        }
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let input = CString::new("## Not a release header\n").unwrap();
+        unsafe {
+            assert!(kacl_parse(input.as_ptr()).is_null());
+            assert!(kacl_parse(ptr::null()).is_null());
+            assert!(kacl_to_string(ptr::null()).is_null());
+            assert!(kacl_diagnostics(ptr::null()).is_null());
+            kacl_free(ptr::null_mut());
+            kacl_free_string(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let input = CString::new("## Not a release header\n").unwrap();
+        unsafe {
+            let diagnostics = kacl_diagnostics(input.as_ptr());
+            let json: serde_json::Value =
+                serde_json::from_str(CStr::from_ptr(diagnostics).to_str().unwrap()).unwrap();
+            assert_eq!(json[0]["severity"], "error");
+            assert_eq!(json[0]["line"], 1);
+            kacl_free_string(diagnostics);
+        }
+    }
+}
@@ -4,6 +4,9 @@ use thiserror::Error;
 
 /// Changes in a release are grouped into one of several types.
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum ChangeGroup {
     /// For new features.
     Added,
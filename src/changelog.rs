@@ -1,22 +1,34 @@
 use crate::change_group::ParseChangeGroupError;
 use crate::changes::Changes;
+use crate::compliance::{compliance_report, ComplianceOptions, ComplianceReport};
+use crate::diagnostic::diagnose;
+use crate::format::format;
+use crate::import::{import_conventional_changelog, ImportChangelogError};
+use crate::line_ending::LineEndingWriter;
+use crate::merge::merge;
 use crate::release_tag::ParseReleaseTagError;
 use crate::releases::Releases;
+use crate::rst::Rst;
+use crate::statistics::statistics;
 use crate::{
-    ChangeGroup, ParseReleaseDateError, Release, ReleaseDate, ReleaseLink, ReleaseTag,
-    ReleaseVersion, Unreleased,
+    ChangeGroup, ChangelogStatistics, DiagnoseOptions, Diagnostic, FormatOptions,
+    LimitedDiagnostics, LineEnding, LinkTemplate, MergeConflict, ParseReleaseDateError, Release,
+    ReleaseDate, ReleaseLink, ReleaseTag, ReleaseVersion, Section, Severity, Unreleased,
 };
 use indexmap::IndexMap;
 use lazy_static::lazy_static;
 use markdown::mdast::Node;
+use markdown::message::Place;
+use markdown::unist::Position;
 use markdown::{to_mdast, ParseOptions};
 use regex::Regex;
+use std::borrow::Cow;
 use std::collections::HashMap;
-use std::fmt::{Display, Formatter};
+use std::fmt::{Display, Formatter, Write};
 use std::str::FromStr;
 use thiserror::Error;
 
-const CHANGELOG_HEADER: &str = "\
+pub(crate) const CHANGELOG_HEADER: &str = "\
 # Changelog
 
 All notable changes to this project will be documented in this file.
@@ -26,16 +38,38 @@ and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0
 
 /// Represents a changelog written in [Keep a Changelog](https://keepachangelog.com/en/1.1.0/) format.
 /// The changelog is a curated, chronologically ordered list of notable changes for each version of a project.
-#[derive(Debug, Eq, PartialEq, Default, Clone)]
+///
+/// Writing a changelog and parsing it back produces an equal value, so
+/// `changelog.to_string().parse::<Changelog>()` returns `changelog`. This holds when each entry
+/// is plain markdown that doesn't start or end with whitespace and when there is no
+/// [`link_separator`](Changelog::link_separator), which is only recognized by lenient parsing.
+/// The `test-util` feature provides strategies that generate such changelogs for property tests.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Changelog {
     /// The Unreleased section is always present in the changelog to communicate upcoming changes.
     pub unreleased: Unreleased,
     /// The list of releases
     pub releases: Releases,
+    /// The separator placed between the releases and the link definitions, if any. Only
+    /// recognized when parsing [leniently](ParseChangelogOptions::with_lenient).
+    pub link_separator: Option<LinkSeparator>,
+    /// The line ending used when the changelog is written. Parsing detects the line ending used by
+    /// most of the lines in the input so round-tripping a file doesn't change it. Changelogs that
+    /// only differ by their line ending are equal.
+    #[cfg_attr(feature = "json", serde(default))]
+    pub line_ending: LineEnding,
+    /// Whether the changelog was parsed from a source without an Unreleased section, which the
+    /// [compliance report](Changelog::compliance_report) reports. The changelog is always written
+    /// with one, and changelogs that only differ by this are equal.
+    #[cfg_attr(feature = "json", serde(default))]
+    pub missing_unreleased_section: bool,
 }
 
 impl Changelog {
-    /// Moves all the changes from the unreleased section of the changelog into a new release which  
+    /// Moves all the changes from the unreleased section of the changelog into a new release which
     /// is added to the top of the changelog. The version, date, and other fields of the new release
     /// can be customized using the `promote_options` argument. If no date is given in the `promote_options`
     /// then the date will default to the current date.
@@ -58,46 +92,275 @@ impl Changelog {
                 .unwrap_or_else(ReleaseDate::today),
             tag: promote_options.tag.clone(),
             link: promote_options.link.clone(),
-            changes: self.unreleased.changes.clone(),
+            changes: std::mem::take(&mut self.unreleased.changes),
         };
 
-        self.unreleased.changes = Changes::default();
+        self.releases.insert_first(new_release);
 
-        let mut new_releases: IndexMap<ReleaseVersion, Release> =
-            IndexMap::from([(new_release.version.clone(), new_release)]);
-        for (release_version, release) in self.releases.clone() {
-            new_releases.insert(release_version, release);
+        Ok(())
+    }
+
+    /// Adds links to the unreleased section and to any releases that don't already have one
+    /// using the given `link_template`. Each release links to a comparison with the release before
+    /// it, the oldest release links to its tag, and the unreleased section links to a comparison
+    /// between the latest release and `HEAD`.
+    pub fn generate_links(&mut self, link_template: &LinkTemplate) {
+        self.fill_links(link_template, false);
+    }
+
+    /// Like [`generate_links`](Changelog::generate_links) but also replaces existing links that
+    /// differ from the generated ones, such as links left pointing at an old repository.
+    pub fn regenerate_links(&mut self, link_template: &LinkTemplate) {
+        self.fill_links(link_template, true);
+    }
+
+    fn fill_links(&mut self, link_template: &LinkTemplate, replace: bool) {
+        let versions = self
+            .releases
+            .iter()
+            .map(|(version, _)| version.clone())
+            .collect::<Vec<_>>();
+
+        if self.unreleased.link.is_none() || replace {
+            if let Some(latest) = versions.first() {
+                self.unreleased.link = Some(link_template.compare_link(latest, None));
+            }
+        }
+
+        for (index, version) in versions.iter().enumerate() {
+            if let Some(release) = self.releases.get_version_mut(version) {
+                if release.link.is_none() || replace {
+                    release.link = Some(match versions.get(index + 1) {
+                        Some(previous) => link_template.compare_link(previous, Some(version)),
+                        None => link_template.tag_link(version),
+                    });
+                }
+            }
         }
+    }
+
+    /// Counts the releases, entries, and contributors in the changelog and the days between
+    /// releases. The Unreleased section isn't included.
+    #[must_use]
+    pub fn statistics(&self) -> ChangelogStatistics {
+        statistics(self)
+    }
 
-        self.releases = Releases::from_iter(new_releases);
+    /// Tags the release with `version` as [yanked](ReleaseTag::Yanked), returning it so an entry
+    /// explaining why can be [added](Release::add), or `None` if there is no such release.
+    pub fn yank(&mut self, version: &ReleaseVersion) -> Option<&mut Release> {
+        let release = self.releases.get_version_mut(version)?;
+        release.tag = Some(ReleaseTag::Yanked);
+        Some(release)
+    }
 
-        Ok(())
+    /// Removes the release with `version` and moves its changes back into the Unreleased section
+    /// ahead of any changes already there, returning the removed release or `None` if there is no
+    /// such release. This undoes [`promote_unreleased`](Changelog::promote_unreleased), for
+    /// example when a release candidate is abandoned. Links that compare against the removed
+    /// release aren't updated.
+    pub fn demote(&mut self, version: &ReleaseVersion) -> Option<Release> {
+        let mut release = self.releases.remove(version)?;
+        let unreleased = std::mem::take(&mut self.unreleased.changes);
+        self.unreleased.changes = std::mem::take(&mut release.changes);
+        self.unreleased.changes.extend(
+            unreleased
+                .iter()
+                .map(|(change_group, items)| (change_group.clone(), items.clone())),
+        );
+        Some(release)
+    }
+
+    /// Combines the changes of every release after `from` up to and including `to`, which is what
+    /// upgrading from `from` to `to` brings in. Entries are listed from the highest version to the
+    /// lowest within each change group.
+    #[must_use]
+    pub fn changes_between(&self, from: &ReleaseVersion, to: &ReleaseVersion) -> Changes {
+        let mut releases = self
+            .releases
+            .iter()
+            .filter(|(version, _)| *version > from && *version <= to)
+            .map(|(_, release)| release)
+            .collect::<Vec<_>>();
+        releases.sort_by(|a, b| b.version.cmp(&a.version));
+        releases
+            .into_iter()
+            .flat_map(|release| &release.changes)
+            .map(|(change_group, items)| (change_group.clone(), items.clone()))
+            .collect()
+    }
+
+    /// Reorders the releases from the highest version to the lowest, returning the versions of the
+    /// releases that moved in their new order. Link definitions are written in release order so
+    /// they move with their releases. Useful for repairing changelogs where backport releases were
+    /// added at the bottom.
+    pub fn sort_releases(&mut self) -> Vec<ReleaseVersion> {
+        let before = self
+            .releases
+            .iter()
+            .map(|(version, _)| version.clone())
+            .collect::<Vec<_>>();
+        self.releases.sort();
+        self.releases
+            .iter()
+            .zip(&before)
+            .filter(|((version, _), previous)| version != previous)
+            .map(|((version, _), _)| version.clone())
+            .collect()
+    }
+
+    /// Removes entries in the Unreleased section that exactly match an earlier entry in the same
+    /// change group, returning how many were removed. Useful after collecting entries from
+    /// [fragments](crate::Fragments) where the same change was recorded more than once.
+    pub fn dedupe_unreleased(&mut self) -> usize {
+        self.unreleased.changes.dedupe()
+    }
+
+    /// Merges the Unreleased section and releases of `other` into this changelog, for example
+    /// when consolidating the changelogs of two repositories.
+    ///
+    /// Entries that aren't already in the same change group are added after the existing ones and
+    /// releases that only exist in `other` are added in version order. Links and tags missing from
+    /// this changelog are taken from `other`. Dates, tags, and links that differ are returned as
+    /// conflicts and the values in this changelog are kept.
+    pub fn merge(&mut self, other: &Changelog) -> Vec<MergeConflict> {
+        merge(self, other)
+    }
+
+    /// Checks the changelog against the [Keep a Changelog guiding principles](https://keepachangelog.com/en/1.1.0/#how)
+    /// and reports where it deviates from them. Each finding references the section of the
+    /// specification that describes the principle.
+    ///
+    /// ISO 8601 release dates are required to parse a changelog so that principle can't be
+    /// violated and isn't reported.
+    #[must_use]
+    pub fn compliance_report(&self) -> ComplianceReport {
+        compliance_report(self, &ComplianceOptions::new())
+    }
+
+    /// Like [`Changelog::compliance_report`] but releases are checked for future dates relative to
+    /// `today` instead of the current date.
+    #[must_use]
+    pub fn compliance_report_as_of(&self, today: &ReleaseDate) -> ComplianceReport {
+        compliance_report(self, &ComplianceOptions::new().with_today(today.clone()))
+    }
+
+    /// Like [`Changelog::compliance_report`] but with the given options.
+    #[must_use]
+    pub fn compliance_report_with_options(&self, options: &ComplianceOptions) -> ComplianceReport {
+        compliance_report(self, options)
+    }
+
+    /// Parses a changelog with the given options. Use [`str::parse`] to parse strictly.
+    pub fn parse_with_options(
+        input: &str,
+        options: &ParseChangelogOptions,
+    ) -> Result<Changelog, ParseChangelogError> {
+        parse_changelog(input, options)
+    }
+
+    /// Parses `input` and returns a warning for each part of it that the parsed changelog doesn't
+    /// hold and so is lost when the changelog is written back, such as prose or HTML comments
+    /// between releases, a preamble other than the standard one, or link definitions that aren't
+    /// for a release. Check this before overwriting a changelog with one parsed from it.
+    pub fn dropped_content(input: &str) -> Result<Vec<Diagnostic>, ParseChangelogError> {
+        parse_changelog_source(input, &ParseChangelogOptions::new()).map(|parsed| parsed.dropped)
+    }
+
+    /// Parses `input` and checks it against the Keep a Changelog guiding principles, returning
+    /// every problem found along with where it occurred in `input`.
+    ///
+    /// A changelog that can't be parsed produces a [`Severity::Error`] diagnostic for the problem
+    /// that stopped parsing. Otherwise each finding from the
+    /// [compliance report](Changelog::compliance_report) becomes a [`Severity::Warning`] pointing
+    /// at the heading of the section it belongs to, and release headings that separate the
+    /// version and date with anything but ` - ` add a warning with the standard heading. Either
+    /// way, headings containing invisible characters add a warning for each character.
+    /// Diagnostics are ordered by where they occur.
+    #[must_use]
+    pub fn diagnose(input: &str) -> Vec<Diagnostic> {
+        diagnose(input, &DiagnoseOptions::new()).diagnostics
+    }
+
+    /// Like [`Changelog::diagnose`] but the number of diagnostics can be limited, or checking can
+    /// stop at the first problem, for callers that need a quick answer on very noisy changelogs.
+    #[must_use]
+    pub fn diagnose_with_options(input: &str, options: &DiagnoseOptions) -> LimitedDiagnostics {
+        diagnose(input, options)
+    }
+
+    /// Converts a changelog generated by [conventional-changelog](https://github.com/conventional-changelog/conventional-changelog)
+    /// or [git-cliff](https://git-cliff.org) into Keep a Changelog format.
+    ///
+    /// Commit type headings such as `Features` or `Bug Fixes` are mapped onto the closest
+    /// [`ChangeGroup`] (anything without a close match is treated as [`ChangeGroup::Changed`]) and
+    /// commit hash references are stripped from each entry.
+    pub fn from_conventional_changelog(input: &str) -> Result<Changelog, ImportChangelogError> {
+        import_conventional_changelog(input)
+    }
+
+    /// Renders the changelog as markdown, the same as its [`Display`] implementation but with the
+    /// formatting adjusted by `options`.
+    #[must_use]
+    pub fn format(&self, options: &FormatOptions) -> String {
+        format(self, options)
+    }
+
+    /// Renders the changelog as [reStructuredText](https://docutils.sourceforge.io/rst.html) for
+    /// documentation toolchains that don't consume markdown. Release headings link to their
+    /// release link targets and inline code and links within entries are converted to their
+    /// reStructuredText equivalents.
+    #[must_use]
+    pub fn to_rst(&self) -> String {
+        Rst(self).to_string()
+    }
+
+    /// Parses `input` and issues an HTTP request against the unreleased link and every release
+    /// link to verify they resolve. Each link that could not be resolved is reported as a
+    /// [`Severity::Error`] diagnostic for [`ComplianceRule::LinkableVersions`](crate::ComplianceRule::LinkableVersions) pointing at the
+    /// link definition, in the same order as the sections.
+    #[cfg(feature = "link-check")]
+    pub fn check_links(
+        input: &str,
+        options: &crate::LinkCheckOptions,
+    ) -> Result<Vec<Diagnostic>, ParseChangelogError> {
+        crate::link_check::check_links(input, options)
+    }
+}
+
+// the line ending and a missing Unreleased section describe how the changelog happens to be
+// written rather than its content
+impl PartialEq for Changelog {
+    fn eq(&self, other: &Self) -> bool {
+        self.unreleased == other.unreleased
+            && self.releases == other.releases
+            && self.link_separator == other.link_separator
     }
 }
 
+impl Eq for Changelog {}
+
 impl FromStr for Changelog {
     type Err = ParseChangelogError;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
-        parse_changelog(value).map_err(ParseChangelogError)
+        parse_changelog(value, &ParseChangelogOptions::new())
     }
 }
 
 impl Display for Changelog {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.write_markdown(&mut LineEndingWriter::new(f, self.line_ending))
+    }
+}
+
+impl Changelog {
+    fn write_markdown(&self, f: &mut impl Write) -> std::fmt::Result {
         write!(f, "{CHANGELOG_HEADER}")?;
 
         write!(f, "\n\n## [Unreleased]")?;
-        for (change_group, items) in &self.unreleased.changes {
-            write!(
-                f,
-                "\n\n### {change_group}\n\n{}",
-                items
-                    .iter()
-                    .map(|item| format!("- {item}"))
-                    .collect::<Vec<String>>()
-                    .join("\n")
-            )?;
+        if self.unreleased.changes.iter().next().is_some() {
+            write!(f, "\n\n{}", self.unreleased.changes)?;
         }
 
         let mut has_release_with_link = false;
@@ -107,16 +370,8 @@ impl Display for Changelog {
             if let Some(tag) = &release.tag {
                 write!(f, " [{tag}]")?;
             }
-            for (change_group, items) in &release.changes {
-                write!(
-                    f,
-                    "\n\n### {change_group}\n\n{}",
-                    items
-                        .iter()
-                        .map(|item| format!("- {item}"))
-                        .collect::<Vec<String>>()
-                        .join("\n")
-                )?;
+            if release.changes.iter().next().is_some() {
+                write!(f, "\n\n{}", release.changes)?;
             }
             if release.link.is_some() {
                 has_release_with_link = true;
@@ -125,6 +380,11 @@ impl Display for Changelog {
 
         if self.unreleased.link.is_some() || has_release_with_link {
             writeln!(f)?;
+            match &self.link_separator {
+                Some(LinkSeparator::ThematicBreak) => write!(f, "\n---\n")?,
+                Some(LinkSeparator::Heading(heading)) => write!(f, "\n## {heading}\n")?,
+                None => {}
+            }
         }
 
         if let Some(link) = &self.unreleased.link {
@@ -190,6 +450,42 @@ impl PromoteOptions {
     }
 }
 
+/// A separator between the last release and the link definitions at the bottom of a changelog.
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum LinkSeparator {
+    /// A horizontal rule (`---`).
+    ThematicBreak,
+    /// A level 2 heading such as `## Links`, containing the heading text.
+    Heading(String),
+}
+
+/// Options for [`Changelog::parse_with_options`].
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct ParseChangelogOptions {
+    lenient: bool,
+}
+
+impl ParseChangelogOptions {
+    /// Creates options that parse a changelog strictly, the same as [`str::parse`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts common deviations from the Keep a Changelog format instead of failing to parse:
+    /// - A `## Links` heading or horizontal rule before the link definitions.
+    /// - Release and change group headings at the wrong level, such as `# [1.2.0] - 2024-05-01`
+    ///   or `#### Added`, which are treated as the section they were intended to be.
+    #[must_use]
+    pub fn with_lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+}
+
 #[derive(Debug)]
 enum ReleaseHeaderType {
     Unreleased,
@@ -203,9 +499,39 @@ enum ReleaseLinkType {
 }
 
 /// An error that occurred during changelog parsing.
-#[derive(Debug, Error)]
-#[error(transparent)]
-pub struct ParseChangelogError(#[from] ParseChangelogErrorInternal);
+#[derive(Debug)]
+pub struct ParseChangelogError {
+    error: Box<ParseChangelogErrorInternal>,
+    position: Option<Position>,
+}
+
+impl ParseChangelogError {
+    fn new(error: ParseChangelogErrorInternal, position: Option<&Position>) -> Self {
+        Self {
+            error: Box::new(error),
+            position: position.cloned(),
+        }
+    }
+
+    /// Converts the error into a [`Diagnostic`] pointing at the part of the changelog that could
+    /// not be parsed.
+    #[must_use]
+    pub fn diagnostic(&self) -> Diagnostic {
+        Diagnostic::new(Severity::Error, self.to_string(), self.position.as_ref())
+    }
+}
+
+impl Display for ParseChangelogError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.error, f)
+    }
+}
+
+impl std::error::Error for ParseChangelogError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error.source()
+    }
+}
 
 #[derive(Debug, Error)]
 enum ParseChangelogErrorInternal {
@@ -218,6 +544,12 @@ enum ParseChangelogErrorInternal {
     #[error("Release header did not match the expected format\nExpected: [Unreleased] | [<version>] - <yyyy>-<mm>-<dd> | [<version>] - <yyyy>-<mm>-<dd> [<tag>]\nValue: {0}")]
     NoMatchForReleaseHeading(String),
 
+    #[error("Heading level should be {} for this heading\nValue: {} {0}\nSuggestion: {} {0}", "#".repeat(usize::from(*.2)), "#".repeat(usize::from(*.1)), "#".repeat(usize::from(*.2)))]
+    IncorrectHeadingDepth(String, u8, u8),
+
+    #[error("Release header has the version and date swapped\nValue: {0}\nSuggestion: {1}")]
+    SwappedVersionAndDate(String, String),
+
     #[error("Invalid version in release entry - {0}\nValue: {1}\nError: {2}")]
     InvalidVersion(String, String, String),
 
@@ -264,13 +596,62 @@ enum ParseChangelogErrorInternal {
 //     }
 //   ]
 // }
-#[allow(clippy::too_many_lines)]
-fn parse_changelog(input: &str) -> Result<Changelog, ParseChangelogErrorInternal> {
-    let changelog_ast =
-        to_mdast(input, &ParseOptions::default()).map_err(ParseChangelogErrorInternal::Markdown)?;
+fn parse_changelog(
+    input: &str,
+    options: &ParseChangelogOptions,
+) -> Result<Changelog, ParseChangelogError> {
+    parse_changelog_source(input, options).map(|parsed| parsed.changelog)
+}
 
-    let is_release_entry_heading = is_heading_of_depth(2);
-    let is_change_group_heading = is_heading_of_depth(3);
+// what parsing learned about the source besides the changelog itself
+pub(crate) struct ParsedChangelog {
+    pub(crate) changelog: Changelog,
+    // a warning for each part of the source that isn't represented in the changelog and so is
+    // dropped when it is written back
+    pub(crate) dropped: Vec<Diagnostic>,
+    // a warning for each release heading that is only accepted because parsing is forgiving
+    pub(crate) nonstandard_headings: Vec<Diagnostic>,
+    pub(crate) headings: HashMap<Section, Position>,
+    // where each section's link is defined, which only the link check reports problems against
+    #[cfg_attr(not(feature = "link-check"), allow(dead_code))]
+    pub(crate) link_definitions: HashMap<Section, Position>,
+}
+
+#[allow(clippy::too_many_lines)]
+pub(crate) fn parse_changelog_source(
+    input: &str,
+    options: &ParseChangelogOptions,
+) -> Result<ParsedChangelog, ParseChangelogError> {
+    let changelog_ast = to_mdast(input, &ParseOptions::default()).map_err(|message| {
+        let position = match message.place.as_deref() {
+            Some(Place::Position(position)) => Some(position.clone()),
+            Some(Place::Point(point)) => Some(Position {
+                start: point.clone(),
+                end: point.clone(),
+            }),
+            None => None,
+        };
+        ParseChangelogError::new(
+            ParseChangelogErrorInternal::Markdown(message),
+            position.as_ref(),
+        )
+    })?;
+
+    // headings one level off from where they belong are recognized by their text so they can be
+    // reported, or accepted as the intended section when parsing leniently
+    let is_misplaced_release_heading = |node: &Node| {
+        heading_depth(node).is_some_and(|depth| depth != 2)
+            && parse_release_heading(&heading_text(node, input)).is_ok()
+    };
+    let is_misplaced_change_group_heading = |node: &Node| {
+        heading_depth(node).is_some_and(|depth| depth != 2 && depth != 3)
+            && heading_text(node, input).parse::<ChangeGroup>().is_ok()
+    };
+    let is_release_entry_heading = |node: &Node| {
+        is_heading_of_depth(2)(node) || (options.lenient && is_misplaced_release_heading(node))
+    };
+    let is_change_group_heading =
+        |node: &Node| is_heading_of_depth(3)(node) || is_misplaced_change_group_heading(node);
     let is_list_node = |node: &Node| matches!(node, Node::List(_));
     let is_definition = |node: &Node| matches!(node, Node::Definition(_));
 
@@ -278,25 +659,86 @@ fn parse_changelog(input: &str) -> Result<Changelog, ParseChangelogErrorInternal
     let mut unreleased_link = None;
     let mut releases = IndexMap::new();
     let mut release_links = HashMap::new();
+    let mut link_separator = None;
+    let mut release_headings = HashMap::new();
+    let mut unreleased_heading = None;
+    let mut link_definitions = HashMap::new();
+    let mut in_preamble = true;
+    let mut dropped = vec![];
+    let mut nonstandard_headings = vec![];
+    let mut report_dropped = |message: String, position: Option<&Position>| {
+        dropped.push(Diagnostic::new(Severity::Warning, message, position));
+    };
 
     if let Node::Root(root) = changelog_ast {
+        // link definitions are collected up front so they can appear anywhere in the document,
+        // such as directly under the release they link to, without cutting that release short
+        let (definitions, children): (Vec<_>, Vec<_>) =
+            root.children.into_iter().partition(is_definition);
+        for definition_node in definitions {
+            if let Node::Definition(definition) = definition_node {
+                let position = definition.position.clone();
+                match parse_release_link_type(&definition.identifier, &definition.url) {
+                    Some(ReleaseLinkType::Unreleased(uri)) => {
+                        unreleased_link = Some((uri, position));
+                    }
+                    Some(ReleaseLinkType::Versioned(version, uri)) => {
+                        release_links.insert(version, (uri, position));
+                    }
+                    None => report_dropped(
+                        format!(
+                            "Link definition [{}] isn't for a release so it is dropped when the changelog is written",
+                            definition.label.as_deref().unwrap_or(&definition.identifier)
+                        ),
+                        position.as_ref(),
+                    ),
+                }
+            }
+        }
+
         // the peekable iterator here makes it easier to decide when to traverse to the next sibling
         // node in the markdown AST to construct our nested structure
-        let mut root_iter = root.children.into_iter().peekable();
+        let mut root_iter = children.into_iter().peekable();
         while root_iter.peek().is_some() {
-            if let Some(release_heading_node) = root_iter.next_if(&is_release_entry_heading) {
-                let release_entry_type = parse_release_heading(release_heading_node.to_string())?;
+            if let Some(heading_node) =
+                root_iter.next_if(|node| options.lenient && is_links_heading(node, input))
+            {
+                link_separator = Some(LinkSeparator::Heading(
+                    heading_text(&heading_node, input).into_owned(),
+                ));
+            } else if let Some(heading_node) =
+                root_iter.next_if(|node| !options.lenient && is_misplaced_release_heading(node))
+            {
+                Err(incorrect_heading_depth(&heading_node, input, 2))?;
+            } else if let Some(release_heading_node) = root_iter.next_if(is_release_entry_heading) {
+                link_separator = None;
+                in_preamble = false;
+                let release_heading = heading_text(&release_heading_node, input);
+                let release_entry_type = parse_release_heading(&release_heading)
+                    .map_err(|e| ParseChangelogError::new(e, release_heading_node.position()))?;
+                if let Some(message) = nonstandard_separator(&release_heading) {
+                    nonstandard_headings.push(Diagnostic::new(
+                        Severity::Warning,
+                        message,
+                        release_heading_node.position(),
+                    ));
+                }
                 let mut changes: IndexMap<ChangeGroup, Vec<String>> = IndexMap::new();
 
                 while root_iter.peek().is_some_and(&is_change_group_heading) {
                     if let Some(change_group_node) = root_iter.next() {
-                        let change_group = change_group_node
-                            .to_string()
-                            .parse::<ChangeGroup>()
-                            .map_err(|e| {
-                                ParseChangelogErrorInternal::InvalidChangeGroup(
-                                    change_group_node.to_string(),
-                                    e,
+                        if !options.lenient && heading_depth(&change_group_node) != Some(3) {
+                            Err(incorrect_heading_depth(&change_group_node, input, 3))?;
+                        }
+                        let change_group_text = heading_text(&change_group_node, input);
+                        let change_group =
+                            change_group_text.parse::<ChangeGroup>().map_err(|e| {
+                                ParseChangelogError::new(
+                                    ParseChangelogErrorInternal::InvalidChangeGroup(
+                                        change_group_text.to_string(),
+                                        e,
+                                    ),
+                                    change_group_node.position(),
                                 )
                             })?;
 
@@ -306,11 +748,18 @@ fn parse_changelog(input: &str) -> Result<Changelog, ParseChangelogErrorInternal
                                     for list_item in list_items {
                                         if matches!(list_item, Node::ListItem(_)) {
                                             if let Some(position) = list_item.position() {
-                                                let text = input
-                                                    [position.start.offset..position.end.offset]
-                                                    .trim_start_matches(['-', '*', ' '])
+                                                // only the list marker is skipped, so entries
+                                                // starting with emphasis or a hyphen are kept whole
+                                                let start = list_item
+                                                    .children()
+                                                    .and_then(|children| children.first())
+                                                    .and_then(Node::position)
+                                                    .map_or(position.end.offset, |content| {
+                                                        content.start.offset
+                                                    });
+                                                let text = input[start..position.end.offset]
                                                     .trim_end()
-                                                    .to_string();
+                                                    .replace("\r\n", "\n");
                                                 match change_group {
                                                     ChangeGroup::Added => {
                                                         changes
@@ -358,59 +807,215 @@ fn parse_changelog(input: &str) -> Result<Changelog, ParseChangelogErrorInternal
                     }
                 }
 
+                let position = release_heading_node.position().cloned();
                 match release_entry_type {
                     ReleaseHeaderType::Unreleased => {
+                        if let Some(previous) = unreleased_heading.replace(position.clone()) {
+                            report_dropped(
+                                "Unreleased section is repeated later so this one is dropped when the changelog is written".to_string(),
+                                previous.as_ref(),
+                            );
+                        }
+                        let link = unreleased_link
+                            .take()
+                            .map(|(uri, position)| {
+                                if let Some(position) = position {
+                                    link_definitions.insert(Section::Unreleased, position);
+                                }
+                                uri
+                            })
+                            .or_else(|| unreleased.take().and_then(|u: Unreleased| u.link));
                         unreleased = Some(Unreleased {
-                            changes: Changes::from_iter(changes.into_iter()),
-                            link: None,
+                            changes: Changes::from_iter(changes),
+                            link,
                         });
                     }
                     ReleaseHeaderType::Versioned(version, date, tag) => {
+                        // a repeated version replaces the earlier release but keeps its link
+                        if let Some(previous) =
+                            release_headings.insert(version.clone(), position.clone())
+                        {
+                            report_dropped(
+                                format!("Release {version} is repeated later so this one is dropped when the changelog is written"),
+                                previous.as_ref(),
+                            );
+                        }
+                        let link = release_links
+                            .remove(&version)
+                            .map(|(uri, position)| {
+                                if let Some(position) = position {
+                                    link_definitions
+                                        .insert(Section::Release(version.clone()), position);
+                                }
+                                uri
+                            })
+                            .or_else(|| {
+                                releases
+                                    .get(&version)
+                                    .and_then(|release: &Release| release.link.clone())
+                            });
                         releases.insert(
                             version.clone(),
                             Release {
                                 version,
                                 date,
                                 tag,
-                                link: None,
-                                changes: Changes::from_iter(changes.into_iter()),
+                                link,
+                                changes: Changes::from_iter(changes),
                             },
                         );
                     }
                 }
-            } else if let Some(definition_node) = root_iter.next_if(is_definition) {
-                if let Node::Definition(definition) = definition_node {
-                    if let Some(release_link_type) =
-                        parse_release_link_type(&definition.identifier, &definition.url)
-                    {
-                        match release_link_type {
-                            ReleaseLinkType::Unreleased(uri) => unreleased_link = Some(uri),
-                            ReleaseLinkType::Versioned(version, uri) => {
-                                release_links.insert(version, uri);
-                            }
+            } else if let Some(node) = root_iter.next() {
+                match node {
+                    // a horizontal rule doesn't hold any content so dropping it loses nothing
+                    Node::ThematicBreak(_) => {
+                        if options.lenient {
+                            link_separator = Some(LinkSeparator::ThematicBreak);
                         }
                     }
+                    node if in_preamble && is_standard_header(&node, input) => {}
+                    node if in_preamble => report_dropped(
+                        "Preamble differs from the standard one so it is replaced when the changelog is written".to_string(),
+                        node.position(),
+                    ),
+                    node => report_dropped(
+                        "Content isn't part of a change group so it is dropped when the changelog is written".to_string(),
+                        node.position(),
+                    ),
                 }
-            } else {
-                root_iter.next();
             }
         }
     }
 
-    if let Some(ref mut next_release) = unreleased {
-        next_release.link = unreleased_link;
+    // links are only written for the sections they belong to
+    let orphaned_links = unreleased_link
+        .map(|(_, position)| position)
+        .into_iter()
+        .chain(release_links.into_values().map(|(_, position)| position));
+    for position in orphaned_links {
+        report_dropped(
+            "Link definition has no matching section so it is dropped when the changelog is written"
+                .to_string(),
+            position.as_ref(),
+        );
     }
+    dropped.sort_by_key(|diagnostic| (diagnostic.line, diagnostic.column));
+
+    let headings = unreleased_heading
+        .map(|position| (Section::Unreleased, position))
+        .into_iter()
+        .chain(
+            release_headings
+                .into_iter()
+                .map(|(version, position)| (Section::Release(version), position)),
+        )
+        .filter_map(|(section, position)| Some((section, position?)))
+        .collect();
+
+    Ok(ParsedChangelog {
+        changelog: Changelog {
+            unreleased: unreleased.clone().unwrap_or_default(),
+            releases: Releases::from_iter(releases),
+            link_separator,
+            line_ending: LineEnding::detect(input),
+            missing_unreleased_section: unreleased.is_none(),
+        },
+        dropped,
+        nonstandard_headings,
+        headings,
+        link_definitions,
+    })
+}
 
-    for (version, link) in release_links {
-        if let Some(release) = releases.get_mut(&version) {
-            release.link = Some(link);
-        }
+// the title and preamble are always written as the standard header, so they only lose content
+// when they say something else, ignoring line wrapping and which version of the specification is
+// linked to
+fn is_standard_header(node: &Node, input: &str) -> bool {
+    node.position()
+        .and_then(|position| input.get(position.start.offset..position.end.offset))
+        .is_some_and(|text| STANDARD_HEADER.contains(&normalize_header(text)))
+}
+
+fn normalize_header(text: &str) -> String {
+    SPECIFICATION_VERSION
+        .replace_all(text, "keepachangelog.com/en/")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn is_links_heading(node: &Node, input: &str) -> bool {
+    is_heading_of_depth(2)(node) && heading_text(node, input).eq_ignore_ascii_case("links")
+}
+
+// heading text is sliced from the source between the first and last inline child, which skips
+// the `#` markers, closing sequence, and setext underline without re-serializing the node; any
+// markup other than plain text and link references falls back to the text content of the node
+pub(crate) fn heading_text<'a>(node: &Node, input: &'a str) -> Cow<'a, str> {
+    let text = source_heading_text(node, input).map_or_else(
+        || Cow::Owned(node.to_string().trim().to_string()),
+        Cow::Borrowed,
+    );
+    if text.chars().any(|c| unusual_character(c).is_some()) {
+        let normalized = text
+            .chars()
+            .filter_map(|c| unusual_character(c).map_or(Some(c), |(_, replacement)| replacement))
+            .collect::<String>();
+        Cow::Owned(normalized.trim().to_string())
+    } else {
+        text
     }
+}
 
-    Ok(Changelog {
-        unreleased: unreleased.unwrap_or_default(),
-        releases: Releases::from_iter(releases),
-    })
+fn source_heading_text<'a>(node: &Node, input: &'a str) -> Option<&'a str> {
+    let Node::Heading(heading) = node else {
+        return None;
+    };
+    let plain = heading
+        .children
+        .iter()
+        .all(|child| matches!(child, Node::Text(_) | Node::LinkReference(_)));
+    let start = heading.children.first()?.position()?;
+    let end = heading.children.last()?.position()?;
+    input
+        .get(start.start.offset..end.end.offset)
+        .filter(|text| plain && !text.contains('\\') && !text.contains('&'))
+        .map(str::trim)
+}
+
+// characters that are easily pasted into a heading without being noticed, along with what they
+// are read as when matching headings
+pub(crate) fn unusual_character(c: char) -> Option<(&'static str, Option<char>)> {
+    match c {
+        '\u{00A0}' => Some(("NO-BREAK SPACE", Some(' '))),
+        '\u{2007}' => Some(("FIGURE SPACE", Some(' '))),
+        '\u{202F}' => Some(("NARROW NO-BREAK SPACE", Some(' '))),
+        '\u{200B}' => Some(("ZERO WIDTH SPACE", None)),
+        '\u{2060}' => Some(("WORD JOINER", None)),
+        '\u{FEFF}' => Some(("ZERO WIDTH NO-BREAK SPACE", None)),
+        '\u{2212}' => Some(("MINUS SIGN", Some('-'))),
+        _ => None,
+    }
+}
+
+fn heading_depth(node: &Node) -> Option<u8> {
+    if let Node::Heading(heading) = node {
+        Some(heading.depth)
+    } else {
+        None
+    }
+}
+
+fn incorrect_heading_depth(node: &Node, input: &str, expected: u8) -> ParseChangelogError {
+    ParseChangelogError::new(
+        ParseChangelogErrorInternal::IncorrectHeadingDepth(
+            heading_text(node, input).into_owned(),
+            heading_depth(node).unwrap_or_default(),
+            expected,
+        ),
+        node.position(),
+    )
 }
 
 fn is_heading_of_depth(depth: u8) -> impl Fn(&Node) -> bool {
@@ -423,30 +1028,43 @@ fn is_heading_of_depth(depth: u8) -> impl Fn(&Node) -> bool {
 }
 
 const UNRELEASED: &str = "unreleased";
-const VERSION_CAPTURE: &str = r"(?P<version>\d+\.\d+\.\d+)";
+const VERSION_CAPTURE: &str =
+    r"(?P<version>\d+\.\d+\.\d+(?:-[0-9A-Za-z.-]+)?(?:\+[0-9A-Za-z.-]+)?)";
 const RELEASE_DATE_CAPTURE: &str = r"(?P<release_date>\d{4}-\d{2}-\d{2})";
 const TAG_CAPTURE: &str = r"(?P<tag>.+)";
+// hyphen-minus, en dash, and em dash are all accepted as the separator between version and date
+// since editors and word processors commonly "smarten" the hyphen
+const SEPARATOR: &str = r"(?P<separator>\s*[-\x{2013}\x{2014}]\s*)";
+const STANDARD_SEPARATOR: &str = " - ";
 
 lazy_static! {
+    static ref STANDARD_HEADER: Vec<String> = CHANGELOG_HEADER
+        .split("\n\n")
+        .map(normalize_header)
+        .collect();
+    static ref SPECIFICATION_VERSION: Regex =
+        Regex::new(r"keepachangelog\.com/en/\d+\.\d+\.\d+/").expect("Should be a valid regex");
     static ref UNRELEASED_HEADER: Regex =
         Regex::new(&format!(r"(?i)^\[?{UNRELEASED}]?$")).expect("Should be a valid regex");
     static ref VERSIONED_RELEASE_HEADER: Regex = Regex::new(&format!(
-        r"^\[?{VERSION_CAPTURE}]?\s+-\s+{RELEASE_DATE_CAPTURE}(?:\s+\[{TAG_CAPTURE}])?$"
+        r"^\[?{VERSION_CAPTURE}]?{SEPARATOR}{RELEASE_DATE_CAPTURE}(?:\s+\[{TAG_CAPTURE}])?$"
+    ))
+    .expect("Should be a valid regex");
+    static ref SWAPPED_RELEASE_HEADER: Regex = Regex::new(&format!(
+        r"^{RELEASE_DATE_CAPTURE}{SEPARATOR}\[?{VERSION_CAPTURE}]?(?:\s+\[{TAG_CAPTURE}])?$"
     ))
     .expect("Should be a valid regex");
 }
 
-fn parse_release_heading(
-    heading: String,
-) -> Result<ReleaseHeaderType, ParseChangelogErrorInternal> {
-    if UNRELEASED_HEADER.is_match(&heading) {
+fn parse_release_heading(heading: &str) -> Result<ReleaseHeaderType, ParseChangelogErrorInternal> {
+    if UNRELEASED_HEADER.is_match(heading) {
         return Ok(ReleaseHeaderType::Unreleased);
     }
 
-    if let Some(captures) = VERSIONED_RELEASE_HEADER.captures(&heading) {
+    if let Some(captures) = VERSIONED_RELEASE_HEADER.captures(heading) {
         let release_version = captures["version"].parse::<ReleaseVersion>().map_err(|e| {
             ParseChangelogErrorInternal::InvalidVersion(
-                heading.clone(),
+                heading.to_string(),
                 captures["version"].to_string(),
                 e.to_string(),
             )
@@ -456,7 +1074,7 @@ fn parse_release_heading(
             .parse::<ReleaseDate>()
             .map_err(|e| {
                 ParseChangelogErrorInternal::InvalidReleaseDate(
-                    heading.clone(),
+                    heading.to_string(),
                     captures["release_date"].to_string(),
                     e,
                 )
@@ -465,7 +1083,7 @@ fn parse_release_heading(
         let release_tag = if let Some(tag_value) = captures.name("tag") {
             Some(tag_value.as_str().parse::<ReleaseTag>().map_err(|e| {
                 ParseChangelogErrorInternal::InvalidReleaseTag(
-                    heading.clone(),
+                    heading.to_string(),
                     tag_value.as_str().to_string(),
                     e,
                 )
@@ -479,13 +1097,43 @@ fn parse_release_heading(
             release_date,
             release_tag,
         ))
+    } else if let Some(captures) = SWAPPED_RELEASE_HEADER.captures(heading) {
+        let tag = captures
+            .name("tag")
+            .map(|tag| format!(" [{}]", tag.as_str()))
+            .unwrap_or_default();
+        let suggestion = format!(
+            "[{}] - {}{tag}",
+            &captures["version"], &captures["release_date"]
+        );
+        Err(ParseChangelogErrorInternal::SwappedVersionAndDate(
+            heading.to_string(),
+            suggestion,
+        ))
     } else {
         Err(ParseChangelogErrorInternal::NoMatchForReleaseHeading(
-            heading,
+            heading.to_string(),
         ))
     }
 }
 
+// the standard separator is written back in place of any other, so this is only a warning
+fn nonstandard_separator(heading: &str) -> Option<String> {
+    let captures = VERSIONED_RELEASE_HEADER.captures(heading)?;
+    let separator = &captures["separator"];
+    if separator == STANDARD_SEPARATOR {
+        return None;
+    }
+    let tag = captures
+        .name("tag")
+        .map(|tag| format!(" [{}]", tag.as_str()))
+        .unwrap_or_default();
+    Some(format!(
+        "Release header separates the version and date with '{separator}' instead of '{STANDARD_SEPARATOR}'\nExpected: [{}]{STANDARD_SEPARATOR}{}{tag}\nValue: {heading}",
+        &captures["version"], &captures["release_date"]
+    ))
+}
+
 fn parse_release_link_type(version: &str, url: &str) -> Option<ReleaseLinkType> {
     let parsed_url = url.parse();
     if version.to_lowercase() == UNRELEASED {
@@ -510,7 +1158,7 @@ mod test {
                 Ok(value) => {
                     panic!("Expected Err but was Ok({value:?})")
                 }
-                Err(e) => match e {
+                Err(e) => match *e.error {
                     $( $pattern )|+ $( if $guard )? => {}
                     error => panic!("Expected to match but was {error:?}"),
                 },
@@ -520,39 +1168,100 @@ mod test {
 
     #[test]
     fn test_invalid_change_group() {
-        let changelog: Result<Changelog, _> = parse_changelog(&format!(
-            "{CHANGELOG_HEADER}
+        let changelog: Result<Changelog, _> = parse_changelog(
+            &format!(
+                "{CHANGELOG_HEADER}
 ## Unreleased
 
 ### Invalid
 
 - Some change        
         "
-        ));
+            ),
+            &ParseChangelogOptions::new(),
+        );
         assert_err_matches!(changelog, ParseChangelogErrorInternal::InvalidChangeGroup(group, _) if group == "Invalid");
     }
 
     #[test]
     fn test_not_a_valid_release_heading() {
         let release_heading = "Not a release header";
-        let changelog: Result<Changelog, _> =
-            parse_changelog(&format!("{CHANGELOG_HEADER}\n\n## {release_heading}"));
+        let changelog: Result<Changelog, _> = parse_changelog(
+            &format!("{CHANGELOG_HEADER}\n\n## {release_heading}"),
+            &ParseChangelogOptions::new(),
+        );
         assert_err_matches!(changelog, ParseChangelogErrorInternal::NoMatchForReleaseHeading(heading) if heading == release_heading);
     }
 
+    #[test]
+    fn test_swapped_version_and_date() {
+        let changelog: Result<Changelog, _> = parse_changelog(
+            &format!("{CHANGELOG_HEADER}\n\n## 2024-01-05 - [1.2.3] [YANKED]"),
+            &ParseChangelogOptions::new(),
+        );
+        assert_err_matches!(changelog, ParseChangelogErrorInternal::SwappedVersionAndDate(heading, suggestion) if heading == "2024-01-05 - [1.2.3] [YANKED]" && suggestion == "[1.2.3] - 2024-01-05 [YANKED]");
+    }
+
+    #[test]
+    fn test_incorrect_heading_depth() {
+        let input = format!(
+            "{CHANGELOG_HEADER}
+
+## [Unreleased]
+
+# [1.2.0] - 2024-05-01
+
+#### Added
+
+- Added feature X
+"
+        );
+        assert_err_matches!(
+            parse_changelog(&input, &ParseChangelogOptions::new()),
+            ParseChangelogErrorInternal::IncorrectHeadingDepth(heading, 1, 2) if heading == "[1.2.0] - 2024-05-01"
+        );
+        assert_eq!(
+            input.parse::<Changelog>().unwrap_err().to_string(),
+            "Heading level should be ## for this heading\nValue: # [1.2.0] - 2024-05-01\nSuggestion: ## [1.2.0] - 2024-05-01"
+        );
+
+        let input = input.replace("# [1.2.0]", "## [1.2.0]");
+        assert_err_matches!(
+            parse_changelog(&input, &ParseChangelogOptions::new()),
+            ParseChangelogErrorInternal::IncorrectHeadingDepth(heading, 4, 3) if heading == "Added"
+        );
+
+        let input = input.replace("## [1.2.0]", "# [1.2.0]");
+        let changelog =
+            Changelog::parse_with_options(&input, &ParseChangelogOptions::new().with_lenient(true))
+                .unwrap();
+        let release = changelog
+            .releases
+            .get_version(&"1.2.0".parse::<ReleaseVersion>().unwrap())
+            .unwrap();
+        assert_eq!(release.changes.iter().count(), 1);
+        assert!(changelog
+            .to_string()
+            .contains("## [1.2.0] - 2024-05-01\n\n### Added"));
+    }
+
     #[test]
     fn test_invalid_release_version() {
         let release_heading = "[00.01.02] - 2023-01-01";
-        let changelog: Result<Changelog, _> =
-            parse_changelog(&format!("{CHANGELOG_HEADER}\n\n## {release_heading}"));
+        let changelog: Result<Changelog, _> = parse_changelog(
+            &format!("{CHANGELOG_HEADER}\n\n## {release_heading}"),
+            &ParseChangelogOptions::new(),
+        );
         assert_err_matches!(changelog, ParseChangelogErrorInternal::InvalidVersion(heading, version, _) if heading == release_heading && version == "00.01.02");
     }
 
     #[test]
     fn test_invalid_release_date() {
         let release_heading = "[0.1.2] - 9999-99-99";
-        let changelog: Result<Changelog, _> =
-            parse_changelog(&format!("{CHANGELOG_HEADER}\n\n## {release_heading}"));
+        let changelog: Result<Changelog, _> = parse_changelog(
+            &format!("{CHANGELOG_HEADER}\n\n## {release_heading}"),
+            &ParseChangelogOptions::new(),
+        );
         assert_err_matches!(changelog, ParseChangelogErrorInternal::InvalidReleaseDate(heading, release_date, _) if heading == release_heading && release_date == "9999-99-99");
     }
 
@@ -572,11 +1281,420 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_entries_keep_leading_punctuation() {
+        let entries = [
+            "*Breaking*: new API",
+            "**Bold** thing",
+            "-1 regressions",
+            "_Internal_ cleanup",
+            "- nested list",
+        ];
+        let mut changelog = Changelog::default();
+        for entry in entries {
+            changelog.unreleased.add(ChangeGroup::Changed, entry);
+        }
+        let parsed: Changelog = changelog.to_string().parse().unwrap();
+        assert_eq!(parsed, changelog);
+
+        let changelog: Changelog =
+            "# Changelog\n\n## [Unreleased]\n\n### Fixed\n\n*   *Parser*: kept\n1. Numbered\n"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            changelog.unreleased.changes.iter().collect::<Vec<_>>(),
+            vec![(
+                &ChangeGroup::Fixed,
+                &vec!["*Parser*: kept".to_string(), "Numbered".to_string()]
+            )]
+        );
+    }
+
+    #[test]
+    fn test_dropped_content() {
+        let input = "\
+# Changelog
+
+All notable changes to this project will be documented in this file.
+
+The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.0.0/),
+and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).
+
+Maintained by the release team.
+
+## [Unreleased]
+
+<!-- add new entries below -->
+
+## [1.0.0] - 2024-01-01
+
+This release requires Rust 1.80.
+
+### Added
+
+- Added X ([#1])
+
+[unreleased]: https://example.com/compare/v1.0.0...HEAD
+[1.0.0]: https://example.com/releases/v1.0.0
+[#1]: https://example.com/pull/1
+";
+        let dropped = Changelog::dropped_content(input).unwrap();
+        assert_eq!(
+            dropped
+                .iter()
+                .map(|diagnostic| (diagnostic.line, diagnostic.message.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (8, "Preamble differs from the standard one so it is replaced when the changelog is written"),
+                (12, "Content isn't part of a change group so it is dropped when the changelog is written"),
+                (16, "Content isn't part of a change group so it is dropped when the changelog is written"),
+                (18, "Content isn't part of a change group so it is dropped when the changelog is written"),
+                (20, "Content isn't part of a change group so it is dropped when the changelog is written"),
+                (24, "Link definition [#1] isn't for a release so it is dropped when the changelog is written"),
+            ]
+        );
+
+        let changelog: Changelog = input.parse().unwrap();
+        assert!(Changelog::dropped_content(&changelog.to_string())
+            .unwrap()
+            .is_empty());
+        assert!(Changelog::dropped_content(
+            "# Changelog\n\n## [1.0.0] - 2024-01-01\n\n## [1.0.0] - 2023-01-01\n"
+        )
+        .unwrap()[0]
+            .message
+            .starts_with("Release 1.0.0 is repeated later"));
+    }
+
+    #[test]
+    fn test_release_heading_with_en_dash_separator() {
+        let changelog: Changelog = format!("{CHANGELOG_HEADER}\n\n## [1.0.0] \u{2013} 2024-01-01")
+            .parse()
+            .unwrap();
+        assert!(changelog
+            .releases
+            .contains_version(&"1.0.0".parse::<ReleaseVersion>().unwrap()));
+        assert!(changelog.to_string().contains("## [1.0.0] - 2024-01-01"));
+    }
+
+    #[test]
+    fn test_release_heading_with_em_dash_separator() {
+        let changelog: Changelog = format!("{CHANGELOG_HEADER}\n\n## [1.0.0] \u{2014} 2024-01-01")
+            .parse()
+            .unwrap();
+        assert!(changelog.to_string().contains("## [1.0.0] - 2024-01-01"));
+    }
+
+    #[test]
+    fn test_release_heading_without_spaces_around_separator() {
+        let changelog: Changelog = format!("{CHANGELOG_HEADER}\n\n## [1.0.0]-2024-01-01 [YANKED]")
+            .parse()
+            .unwrap();
+        assert!(changelog
+            .to_string()
+            .contains("## [1.0.0] - 2024-01-01 [YANKED]"));
+    }
+
+    #[test]
+    fn test_link_definitions_interleaved_with_releases() {
+        let changelog: Changelog = format!(
+            "{CHANGELOG_HEADER}
+
+## [Unreleased]
+
+[unreleased]: https://github.com/org/repo/compare/v1.0.0...HEAD
+
+## [1.0.0] - 2023-02-01
+
+[1.0.0]: https://github.com/org/repo/compare/v0.1.0...v1.0.0
+
+### Added
+
+- Added feature X
+
+[0.1.0]: https://github.com/org/repo/releases/tag/v0.1.0
+
+### Fixed
+
+- Fixed feature Y
+
+## [0.1.0] - 2023-01-01
+
+### Added
+
+- Initial release
+"
+        )
+        .parse()
+        .unwrap();
+
+        let release = changelog
+            .releases
+            .get_version(&"1.0.0".parse::<ReleaseVersion>().unwrap())
+            .unwrap();
+        assert_eq!(release.changes.iter().count(), 2);
+        assert_eq!(
+            release.link.as_ref().unwrap().to_string(),
+            "https://github.com/org/repo/compare/v0.1.0...v1.0.0"
+        );
+        assert!(changelog.unreleased.link.is_some());
+        assert!(changelog
+            .releases
+            .get_version(&"0.1.0".parse::<ReleaseVersion>().unwrap())
+            .unwrap()
+            .link
+            .is_some());
+    }
+
+    #[test]
+    fn test_links_heading_requires_lenient_parsing() {
+        let input = format!(
+            "{CHANGELOG_HEADER}
+
+## [Unreleased]
+
+## [1.0.0] - 2023-01-01
+
+### Added
+
+- Added feature X
+
+## Links
+
+[unreleased]: https://github.com/org/repo/compare/v1.0.0...HEAD
+[1.0.0]: https://github.com/org/repo/releases/tag/v1.0.0
+"
+        );
+        assert_err_matches!(
+            parse_changelog(&input, &ParseChangelogOptions::new()),
+            ParseChangelogErrorInternal::NoMatchForReleaseHeading(heading) if heading == "Links"
+        );
+
+        let changelog =
+            Changelog::parse_with_options(&input, &ParseChangelogOptions::new().with_lenient(true))
+                .unwrap();
+        assert_eq!(
+            changelog.link_separator,
+            Some(LinkSeparator::Heading("Links".to_string()))
+        );
+        assert!(changelog.unreleased.link.is_some());
+        assert_eq!(changelog.to_string(), input);
+    }
+
+    #[test]
+    fn test_horizontal_rule_before_links() {
+        let input = format!(
+            "{CHANGELOG_HEADER}
+
+## [Unreleased]
+
+## [1.0.0] - 2023-01-01
+
+### Added
+
+- Added feature X
+
+---
+
+[1.0.0]: https://github.com/org/repo/releases/tag/v1.0.0
+"
+        );
+        let changelog: Changelog = input.parse().unwrap();
+        assert_eq!(changelog.link_separator, None);
+
+        let changelog =
+            Changelog::parse_with_options(&input, &ParseChangelogOptions::new().with_lenient(true))
+                .unwrap();
+        assert_eq!(changelog.link_separator, Some(LinkSeparator::ThematicBreak));
+        assert_eq!(changelog.to_string(), input);
+    }
+
     #[test]
     fn test_invalid_release_tag() {
         let release_heading = "[0.1.2] - 2023-01-01 [UNKNOWN TAG]";
-        let changelog: Result<Changelog, _> =
-            parse_changelog(&format!("{CHANGELOG_HEADER}\n\n## {release_heading}"));
+        let changelog: Result<Changelog, _> = parse_changelog(
+            &format!("{CHANGELOG_HEADER}\n\n## {release_heading}"),
+            &ParseChangelogOptions::new(),
+        );
         assert_err_matches!(changelog, ParseChangelogErrorInternal::InvalidReleaseTag(heading, tag, _) if heading == release_heading && tag == "UNKNOWN TAG");
     }
+
+    #[test]
+    fn test_heading_text_from_source() {
+        let changelog: Changelog = format!(
+            "{CHANGELOG_HEADER}
+
+## [Unreleased] ##
+
+### Added ###
+
+- Added X
+
+[1.0.0] - 2024-01-01
+--------------------
+
+### *Fixed*
+
+- Fixed Y
+
+[1.0.0]: https://github.com/org/repo/releases/tag/v1.0.0
+"
+        )
+        .parse()
+        .unwrap();
+        assert!(changelog
+            .unreleased
+            .changes
+            .contains_group(&ChangeGroup::Added));
+        assert!(changelog.releases["1.0.0"]
+            .changes
+            .contains_group(&ChangeGroup::Fixed));
+    }
+
+    #[test]
+    fn test_crlf_round_trip() {
+        let input = format!(
+            "{CHANGELOG_HEADER}\n\n## [Unreleased]\n\n### Added\n\n- Added a flag that spans\n  two lines\n\n## [1.0.0] - 2024-01-01\n\n### Fixed\n\n- Fixed Y\n\n[unreleased]: https://github.com/org/repo/compare/v1.0.0...HEAD\n[1.0.0]: https://github.com/org/repo/releases/tag/v1.0.0\n"
+        )
+        .replace('\n', "\r\n");
+        let changelog: Changelog = input.parse().unwrap();
+        assert_eq!(changelog.line_ending, LineEnding::CrLf);
+        assert_eq!(
+            changelog,
+            input.replace("\r\n", "\n").parse::<Changelog>().unwrap()
+        );
+        assert_eq!(
+            changelog
+                .unreleased
+                .changes
+                .get(&ChangeGroup::Added)
+                .unwrap(),
+            ["Added a flag that spans\n  two lines"]
+        );
+        assert_eq!(changelog.to_string(), input);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_round_trip() {
+        let changelog: Changelog = format!(
+            "{CHANGELOG_HEADER}\n\n## [Unreleased]\n\n### Added\n\n- Added X\n\n## [1.0.0] - 2024-01-01 [YANKED]\n\n### Fixed\n\n- Fixed Y\n\n[1.0.0]: https://github.com/org/repo/releases/tag/v1.0.0\n"
+        )
+        .parse()
+        .unwrap();
+        let json = serde_json::to_value(&changelog).unwrap();
+        assert_eq!(json["unreleased"]["changes"]["Added"][0], "Added X");
+        assert_eq!(json["releases"][0]["version"], "1.0.0");
+        assert_eq!(json["releases"][0]["date"], "2024-01-01");
+        assert_eq!(json["releases"][0]["tag"], "Yanked");
+        assert_eq!(
+            json["releases"][0]["link"],
+            "https://github.com/org/repo/releases/tag/v1.0.0"
+        );
+        assert_eq!(
+            serde_json::from_value::<Changelog>(json.clone()).unwrap(),
+            changelog
+        );
+
+        let mut invalid = json;
+        invalid["releases"][0]["date"] = "2024-13-01".into();
+        assert!(serde_json::from_value::<Changelog>(invalid).is_err());
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_json_schema() {
+        let schema = serde_json::to_value(schemars::schema_for!(Changelog)).unwrap();
+        assert_eq!(schema["title"], "Changelog");
+        assert!(schema["properties"]["releases"].is_object());
+        assert_eq!(schema["$defs"]["Releases"]["type"], "array");
+    }
+
+    #[test]
+    fn test_prerelease_and_build_metadata_versions() {
+        let changelog: Changelog = format!(
+            "{CHANGELOG_HEADER}\n\n## [Unreleased]\n\n## [2.0.0-rc.1+build.5] - 2024-02-01\n\n## 1.0.0-beta - 2024-01-01\n\n## [0.1.0]-2023-01-01\n"
+        )
+        .parse()
+        .unwrap();
+        let versions = changelog
+            .releases
+            .iter()
+            .map(|(version, _)| version.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(versions, ["2.0.0-rc.1+build.5", "1.0.0-beta", "0.1.0"]);
+    }
+
+    #[test]
+    fn test_yank() {
+        let mut changelog: Changelog = format!(
+            "{CHANGELOG_HEADER}\n\n## [Unreleased]\n\n## [1.3.0] - 2024-01-05\n\n### Added\n\n- Added X\n"
+        )
+        .parse()
+        .unwrap();
+        let version: ReleaseVersion = "1.3.0".parse().unwrap();
+        changelog
+            .yank(&version)
+            .unwrap()
+            .add(ChangeGroup::Changed, "Yanked because it's broken on musl");
+        assert!(changelog.to_string().contains(
+            "## [1.3.0] - 2024-01-05 [YANKED]\n\n### Added\n\n- Added X\n\n### Changed\n\n- Yanked because it's broken on musl\n"
+        ));
+        assert!(changelog.yank(&"1.4.0".parse().unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_demote() {
+        let mut changelog: Changelog = format!(
+            "{CHANGELOG_HEADER}\n\n## [Unreleased]\n\n### Fixed\n\n- Fixed Z\n\n## [1.5.0-rc.1] - 2024-02-01\n\n### Added\n\n- Added Y\n\n### Fixed\n\n- Fixed Y\n\n## [1.4.0] - 2024-01-05\n\n### Added\n\n- Added X\n"
+        )
+        .parse()
+        .unwrap();
+        let release = changelog.demote(&"1.5.0-rc.1".parse().unwrap()).unwrap();
+        assert_eq!(release.date.to_string(), "2024-02-01");
+        assert!(release.changes.is_empty());
+        assert!(changelog.to_string().contains(
+            "## [Unreleased]\n\n### Added\n\n- Added Y\n\n### Fixed\n\n- Fixed Y\n- Fixed Z\n\n## [1.4.0] - 2024-01-05\n"
+        ));
+        assert!(changelog.demote(&"1.5.0-rc.1".parse().unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_changes_between() {
+        let changelog: Changelog = format!(
+            "{CHANGELOG_HEADER}\n\n## [Unreleased]\n\n### Added\n\n- Added W\n\n## [1.5.0] - 2024-03-01\n\n### Fixed\n\n- Fixed Z\n\n## [1.3.0] - 2024-02-01\n\n### Added\n\n- Added Y\n\n### Fixed\n\n- Fixed Y\n\n## [1.2.0] - 2024-01-01\n\n### Added\n\n- Added X\n"
+        )
+        .parse()
+        .unwrap();
+        assert_eq!(
+            changelog
+                .changes_between(&"1.2.0".parse().unwrap(), &"1.5.0".parse().unwrap())
+                .to_string(),
+            "### Fixed\n\n- Fixed Z\n- Fixed Y\n\n### Added\n\n- Added Y"
+        );
+        assert!(changelog
+            .changes_between(&"1.5.0".parse().unwrap(), &"1.2.0".parse().unwrap())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_sort_releases() {
+        let mut changelog: Changelog = format!(
+            "{CHANGELOG_HEADER}\n\n## [Unreleased]\n\n## [2.0.0] - 2024-03-01\n\n## [1.0.0] - 2024-01-01\n\n## [1.1.0] - 2024-02-01\n\n[2.0.0]: https://example.com/2.0.0\n[1.0.0]: https://example.com/1.0.0\n[1.1.0]: https://example.com/1.1.0\n"
+        )
+        .parse()
+        .unwrap();
+        assert_eq!(
+            changelog
+                .sort_releases()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["1.1.0", "1.0.0"]
+        );
+        assert!(changelog.to_string().ends_with(
+            "## [2.0.0] - 2024-03-01\n\n## [1.1.0] - 2024-02-01\n\n## [1.0.0] - 2024-01-01\n\n[2.0.0]: https://example.com/2.0.0\n[1.1.0]: https://example.com/1.1.0\n[1.0.0]: https://example.com/1.0.0\n"
+        ));
+        assert!(changelog.sort_releases().is_empty());
+    }
 }
@@ -1,11 +1,32 @@
-use crate::ChangeGroup;
+use crate::import::import_conventional_commits;
+use crate::{ChangeGroup, ReleaseVersion, VersionBump};
 use indexmap::IndexMap;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+lazy_static! {
+    static ref ENTRY_SCOPE: Regex =
+        Regex::new(r"^\((?P<scope>[^)]+)\)\s+").expect("Should be a valid regex");
+}
 
 /// Represents the changes that went into a release.
 #[derive(Debug, Eq, PartialEq, Default, Clone)]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Changes(IndexMap<ChangeGroup, Vec<String>>);
 
 impl Changes {
+    /// Classifies [conventional commit](https://www.conventionalcommits.org) subjects such as
+    /// `feat(cli): add a json flag` into change groups by their type, keeping the order they're
+    /// given in. The scope becomes an [entry scope](Changes::scopes) and the first letter of the
+    /// description is capitalized. Subjects that aren't conventional commits and types that don't
+    /// affect users, such as `chore` or `docs`, are skipped.
+    pub fn from_conventional_commits<'a>(subjects: impl IntoIterator<Item = &'a str>) -> Changes {
+        import_conventional_commits(subjects)
+    }
+
     /// Returns true if there are no changes.
     #[must_use]
     pub fn is_empty(&self) -> bool {
@@ -14,27 +35,354 @@ impl Changes {
 
     /// Returns an iterator over the change group/list of changes pairs
     #[must_use]
-    pub fn iter(&self) -> std::vec::IntoIter<(&ChangeGroup, &Vec<String>)> {
+    pub fn iter(&self) -> ChangesIter<'_> {
+        self.into_iter()
+    }
+
+    /// Returns the changes listed under `change_group`, if the group is present.
+    #[must_use]
+    pub fn get(&self, change_group: &ChangeGroup) -> Option<&[String]> {
+        self.0.get(change_group).map(Vec::as_slice)
+    }
+
+    /// Returns true if `change_group` is present.
+    #[must_use]
+    pub fn contains_group(&self, change_group: &ChangeGroup) -> bool {
+        self.0.contains_key(change_group)
+    }
+
+    /// Returns an iterator over the change groups in the order they appear.
+    pub fn groups(&self) -> impl Iterator<Item = &ChangeGroup> {
+        self.0.keys()
+    }
+
+    /// Removes entries whose text exactly matches an earlier entry in the same change group,
+    /// returning how many were removed.
+    pub fn dedupe(&mut self) -> usize {
+        let mut removed = 0;
+        for items in self.0.values_mut() {
+            let mut seen = HashSet::new();
+            let keep = items
+                .iter()
+                .map(|item| seen.insert(item.as_str()))
+                .collect::<Vec<_>>();
+            let mut keep = keep.into_iter();
+            let before = items.len();
+            items.retain(|_| keep.next().unwrap_or(true));
+            removed += before - items.len();
+        }
+        removed
+    }
+
+    /// Returns the scopes used by the entries, in the order they first appear. A scope is a
+    /// conventional leading component name in parentheses, such as `cli` in
+    /// `(cli) Added a --json flag`.
+    #[must_use]
+    pub fn scopes(&self) -> Vec<&str> {
+        let mut scopes = vec![];
+        for (_, items) in self {
+            for scope in items.iter().filter_map(|item| entry_scope(item)) {
+                if !scopes.contains(&scope) {
+                    scopes.push(scope);
+                }
+            }
+        }
+        scopes
+    }
+
+    /// Returns only the entries with the given scope, with the scope prefix removed. Change groups
+    /// without any matching entries are left out.
+    #[must_use]
+    pub fn with_scope(&self, scope: &str) -> Changes {
         self.into_iter()
+            .filter_map(|(change_group, items)| {
+                let items = items
+                    .iter()
+                    .filter_map(|item| match Changes::split_scope(item) {
+                        (Some(item_scope), text) if item_scope == scope => Some(text.to_string()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                (!items.is_empty()).then(|| (change_group.clone(), items))
+            })
+            .collect()
+    }
+
+    /// Splits a single entry into its scope, if it has one, and the rest of its text. For example,
+    /// `(cli) Added a --json flag` is split into `Some("cli")` and `Added a --json flag`, while
+    /// an entry without a scope is returned whole.
+    #[must_use]
+    pub fn split_scope(entry: &str) -> (Option<&str>, &str) {
+        match ENTRY_SCOPE.captures(entry) {
+            Some(captures) => (
+                captures.name("scope").map(|scope| scope.as_str()),
+                &entry[captures.get(0).map_or(0, |prefix| prefix.end())..],
+            ),
+            None => (None, entry),
+        }
+    }
+
+    /// Suggests the type of version bump from `current` for a release with these changes, or
+    /// `None` if there are no changes. `Removed` entries are treated as breaking, `Added`,
+    /// `Changed` and `Deprecated` entries as new functionality, and anything else as a fix.
+    /// Breaking changes to a `0.x` version are a minor bump rather than a jump to `1.0.0`.
+    #[must_use]
+    pub fn suggested_bump(&self, current: &ReleaseVersion) -> Option<VersionBump> {
+        let breaking = if current.is_initial_development() {
+            VersionBump::Minor
+        } else {
+            VersionBump::Major
+        };
+        self.0
+            .iter()
+            .filter(|(_, items)| !items.is_empty())
+            .map(|(change_group, _)| match change_group {
+                ChangeGroup::Removed => breaking,
+                ChangeGroup::Added | ChangeGroup::Changed | ChangeGroup::Deprecated => {
+                    VersionBump::Minor
+                }
+                ChangeGroup::Fixed | ChangeGroup::Security => VersionBump::Patch,
+            })
+            .max()
     }
 
     pub(crate) fn add(&mut self, change_group: ChangeGroup, item: impl Into<String>) {
         self.0.entry(change_group).or_default().push(item.into());
     }
 
-    pub(crate) fn from_iter<I: IntoIterator<Item = (ChangeGroup, Vec<String>)>>(
-        iterable: I,
-    ) -> Changes {
-        Self(IndexMap::from_iter(iterable))
+    pub(crate) fn items_mut(&mut self) -> impl Iterator<Item = &mut Vec<String>> {
+        self.0.values_mut()
+    }
+}
+
+impl FromIterator<(ChangeGroup, Vec<String>)> for Changes {
+    /// Collects the change group/list of changes pairs. Changes for a group that appears more
+    /// than once are combined in order.
+    fn from_iter<I: IntoIterator<Item = (ChangeGroup, Vec<String>)>>(iter: I) -> Self {
+        let mut changes = Changes::default();
+        changes.extend(iter);
+        changes
+    }
+}
+
+impl Extend<(ChangeGroup, Vec<String>)> for Changes {
+    /// Appends the changes to their group, adding groups that don't exist yet after the existing
+    /// ones.
+    fn extend<I: IntoIterator<Item = (ChangeGroup, Vec<String>)>>(&mut self, iter: I) {
+        for (change_group, items) in iter {
+            self.0.entry(change_group).or_default().extend(items);
+        }
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Changes {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary_iter::<(ChangeGroup, Vec<String>)>()?.collect()
+    }
+}
+
+impl Display for Changes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut separator = "";
+        for (change_group, items) in self {
+            write!(
+                f,
+                "{separator}### {change_group}\n\n{}",
+                items
+                    .iter()
+                    .map(|item| format!("- {item}"))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            )?;
+            separator = "\n\n";
+        }
+        Ok(())
     }
 }
 
 impl<'a> IntoIterator for &'a Changes {
     type Item = (&'a ChangeGroup, &'a Vec<String>);
 
-    type IntoIter = std::vec::IntoIter<Self::Item>;
+    type IntoIter = ChangesIter<'a>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.iter().collect::<Vec<_>>().into_iter()
+        ChangesIter(self.0.iter())
+    }
+}
+
+/// An iterator over the change group/list of changes pairs in [`Changes`].
+#[derive(Debug, Clone)]
+pub struct ChangesIter<'a>(indexmap::map::Iter<'a, ChangeGroup, Vec<String>>);
+
+impl<'a> Iterator for ChangesIter<'a> {
+    type Item = (&'a ChangeGroup, &'a Vec<String>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for ChangesIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl ExactSizeIterator for ChangesIter<'_> {}
+
+pub(crate) fn entry_scope(item: &str) -> Option<&str> {
+    Changes::split_scope(item).0
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_scopes() {
+        let changes = Changes::from_iter([
+            (
+                ChangeGroup::Added,
+                vec![
+                    "(cli) Added a --json flag".to_string(),
+                    "(core) Added streaming parser".to_string(),
+                    "Added docs".to_string(),
+                ],
+            ),
+            (
+                ChangeGroup::Fixed,
+                vec!["(cli) Fixed exit code".to_string()],
+            ),
+        ]);
+
+        assert_eq!(changes.scopes(), vec!["cli", "core"]);
+        assert_eq!(
+            changes.with_scope("cli"),
+            Changes::from_iter([
+                (ChangeGroup::Added, vec!["Added a --json flag".to_string()]),
+                (ChangeGroup::Fixed, vec!["Fixed exit code".to_string()]),
+            ])
+        );
+        assert!(changes.with_scope("docs").is_empty());
+    }
+
+    #[test]
+    fn test_split_scope() {
+        assert_eq!(
+            Changes::split_scope("(cli) Added a --json flag"),
+            (Some("cli"), "Added a --json flag")
+        );
+        assert_eq!(Changes::split_scope("Added docs"), (None, "Added docs"));
+        assert_eq!(
+            Changes::split_scope("(cli)Added docs"),
+            (None, "(cli)Added docs")
+        );
+    }
+
+    #[test]
+    fn test_suggested_bump() {
+        let bump_from = |current: &str, groups: &[ChangeGroup]| {
+            groups
+                .iter()
+                .map(|group| (group.clone(), vec!["Entry".to_string()]))
+                .collect::<Changes>()
+                .suggested_bump(&current.parse().unwrap())
+        };
+        let bump = |groups: &[ChangeGroup]| bump_from("1.2.3", groups);
+        assert_eq!(bump(&[]), None);
+        assert_eq!(
+            bump(&[ChangeGroup::Fixed, ChangeGroup::Security]),
+            Some(VersionBump::Patch)
+        );
+        assert_eq!(
+            bump(&[ChangeGroup::Fixed, ChangeGroup::Added]),
+            Some(VersionBump::Minor)
+        );
+        assert_eq!(
+            bump(&[ChangeGroup::Fixed, ChangeGroup::Changed]),
+            Some(VersionBump::Minor)
+        );
+        assert_eq!(
+            bump(&[ChangeGroup::Deprecated, ChangeGroup::Removed]),
+            Some(VersionBump::Major)
+        );
+        assert_eq!(
+            bump_from("0.4.1", &[ChangeGroup::Removed]),
+            Some(VersionBump::Minor)
+        );
+    }
+
+    #[test]
+    fn test_dedupe() {
+        let mut changes = Changes::from_iter([
+            (
+                ChangeGroup::Added,
+                vec![
+                    "Added X".to_string(),
+                    "Added Y".to_string(),
+                    "Added X".to_string(),
+                    "Added X".to_string(),
+                ],
+            ),
+            (ChangeGroup::Fixed, vec!["Added X".to_string()]),
+        ]);
+
+        assert_eq!(changes.dedupe(), 2);
+        assert_eq!(
+            changes,
+            Changes::from_iter([
+                (
+                    ChangeGroup::Added,
+                    vec!["Added X".to_string(), "Added Y".to_string()]
+                ),
+                (ChangeGroup::Fixed, vec!["Added X".to_string()]),
+            ])
+        );
+        assert_eq!(changes.dedupe(), 0);
+    }
+
+    #[test]
+    fn test_accessors() {
+        let changes = Changes::from_iter([
+            (ChangeGroup::Fixed, vec!["Fixed Y".to_string()]),
+            (ChangeGroup::Added, vec!["Added X".to_string()]),
+        ]);
+
+        assert_eq!(
+            changes.get(&ChangeGroup::Added),
+            Some(["Added X".to_string()].as_slice())
+        );
+        assert_eq!(changes.get(&ChangeGroup::Removed), None);
+        assert!(changes.contains_group(&ChangeGroup::Fixed));
+        assert!(!changes.contains_group(&ChangeGroup::Security));
+        assert_eq!(
+            changes.groups().collect::<Vec<_>>(),
+            vec![&ChangeGroup::Fixed, &ChangeGroup::Added]
+        );
+    }
+
+    #[test]
+    fn test_from_iter_and_extend() {
+        let mut changes: Changes = [
+            (ChangeGroup::Added, vec!["Added X".to_string()]),
+            (ChangeGroup::Added, vec!["Added Y".to_string()]),
+        ]
+        .into_iter()
+        .collect();
+        changes.extend([
+            (ChangeGroup::Fixed, vec!["Fixed Z".to_string()]),
+            (ChangeGroup::Added, vec!["Added Z".to_string()]),
+        ]);
+
+        assert_eq!(
+            changes.to_string(),
+            "### Added\n\n- Added X\n- Added Y\n- Added Z\n\n### Fixed\n\n- Fixed Z"
+        );
     }
 }
@@ -0,0 +1,687 @@
+use crate::{
+    Changelog, Changes, LinkTemplate, ReleaseDate, ReleaseLink, ReleaseTag, ReleaseVersion,
+    RepositoryProvider, Section, Severity,
+};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use thiserror::Error;
+
+const SPEC_URL: &str = "https://keepachangelog.com/en/1.1.0/";
+
+lazy_static! {
+    // entries that look like they were copied from `git log` rather than written for humans
+    static ref COMMIT_LOG_ENTRY: Regex = Regex::new(
+        r"(?i)^(?:[0-9a-f]{7,40}\s|merge (?:pull request|branch|remote-tracking branch)\s|(?:build|chore|ci|docs|feat|fix|perf|refactor|revert|style|test)(?:\([^)]*\))?!?:\s)"
+    )
+    .expect("Should be a valid regex");
+}
+
+/// The [Keep a Changelog](https://keepachangelog.com/en/1.1.0/) guiding principles that a changelog
+/// is checked against.
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub enum ComplianceRule {
+    /// The latest version comes first.
+    LatestVersionFirst,
+    /// The release date of each version is displayed and releases are in chronological order.
+    ChronologicalDates,
+    /// Versions and sections should be linkable.
+    LinkableVersions,
+    /// Changelogs are for humans, not machines, so git log output shouldn't be dumped into them.
+    NoCommitLogDumps,
+    /// Release dates should not be in the future, which usually means the year was mistyped.
+    NoFutureDates,
+    /// Each release lists at least one change, unless it is tagged as having no changes.
+    NoEmptyReleases,
+    /// An Unreleased section at the top tracks upcoming changes.
+    UnreleasedSection,
+}
+
+impl ComplianceRule {
+    /// Every rule, in the order they are checked.
+    pub const ALL: [ComplianceRule; 7] = [
+        ComplianceRule::LatestVersionFirst,
+        ComplianceRule::ChronologicalDates,
+        ComplianceRule::LinkableVersions,
+        ComplianceRule::NoCommitLogDumps,
+        ComplianceRule::NoFutureDates,
+        ComplianceRule::NoEmptyReleases,
+        ComplianceRule::UnreleasedSection,
+    ];
+
+    /// The severity of [diagnostics](crate::Diagnostic) for the rule. Every rule is a warning
+    /// since the changelog can still be parsed.
+    #[must_use]
+    pub fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    /// A short, stable identifier for the rule.
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        match self {
+            ComplianceRule::LatestVersionFirst => "latest-version-first",
+            ComplianceRule::ChronologicalDates => "chronological-dates",
+            ComplianceRule::LinkableVersions => "linkable-versions",
+            ComplianceRule::NoCommitLogDumps => "no-commit-log-dumps",
+            ComplianceRule::NoFutureDates => "no-future-dates",
+            ComplianceRule::NoEmptyReleases => "no-empty-releases",
+            ComplianceRule::UnreleasedSection => "unreleased-section",
+        }
+    }
+
+    /// A link to the section of the Keep a Changelog specification that describes the rule.
+    #[must_use]
+    pub fn spec_reference(&self) -> String {
+        let anchor = match self {
+            ComplianceRule::LatestVersionFirst
+            | ComplianceRule::ChronologicalDates
+            | ComplianceRule::LinkableVersions
+            | ComplianceRule::NoFutureDates
+            | ComplianceRule::NoEmptyReleases => "how",
+            ComplianceRule::NoCommitLogDumps => "log-diffs",
+            ComplianceRule::UnreleasedSection => "effort",
+        };
+        format!("{SPEC_URL}#{anchor}")
+    }
+}
+
+/// Error for when a compliance rule code cannot be parsed.
+#[derive(Debug, Error)]
+#[error("Unknown compliance rule '{0}'\nExpected: latest-version-first | chronological-dates | linkable-versions | no-commit-log-dumps | no-future-dates | no-empty-releases | unreleased-section")]
+pub struct ParseComplianceRuleError(String);
+
+impl FromStr for ComplianceRule {
+    type Err = ParseComplianceRuleError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "latest-version-first" => Ok(ComplianceRule::LatestVersionFirst),
+            "chronological-dates" => Ok(ComplianceRule::ChronologicalDates),
+            "linkable-versions" => Ok(ComplianceRule::LinkableVersions),
+            "no-commit-log-dumps" => Ok(ComplianceRule::NoCommitLogDumps),
+            "no-future-dates" => Ok(ComplianceRule::NoFutureDates),
+            "no-empty-releases" => Ok(ComplianceRule::NoEmptyReleases),
+            "unreleased-section" => Ok(ComplianceRule::UnreleasedSection),
+            _ => Err(ParseComplianceRuleError(value.to_string())),
+        }
+    }
+}
+
+impl Display for ComplianceRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// A place where the changelog does not follow one of the Keep a Changelog guiding principles.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ComplianceFinding {
+    /// The principle that was not followed.
+    pub rule: ComplianceRule,
+    /// The section of the changelog the finding applies to.
+    pub section: Section,
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl Display for ComplianceFinding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{}] {}: {} (see {})",
+            self.rule,
+            self.section,
+            self.message,
+            self.rule.spec_reference()
+        )
+    }
+}
+
+/// The result of checking a changelog against the Keep a Changelog guiding principles.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct ComplianceReport(Vec<ComplianceFinding>);
+
+impl ComplianceReport {
+    /// Returns true if no problems were found.
+    #[must_use]
+    pub fn is_compliant(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the findings in the order they appear in the changelog.
+    pub fn iter(&self) -> std::slice::Iter<'_, ComplianceFinding> {
+        self.0.iter()
+    }
+
+    /// Removes the findings for any of the `allowed` rules.
+    pub fn allow(&mut self, allowed: &[ComplianceRule]) {
+        self.0.retain(|finding| !allowed.contains(&finding.rule));
+    }
+}
+
+impl<'a> IntoIterator for &'a ComplianceReport {
+    type Item = &'a ComplianceFinding;
+    type IntoIter = std::slice::Iter<'a, ComplianceFinding>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl Display for ComplianceReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.0
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n")
+        )
+    }
+}
+
+/// Options for [`Changelog::compliance_report_with_options`].
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ComplianceOptions {
+    today: Option<ReleaseDate>,
+    allow_empty_yanked_releases: bool,
+    link_template: Option<LinkTemplate>,
+    stop_at_first_finding: bool,
+}
+
+impl Default for ComplianceOptions {
+    fn default() -> Self {
+        Self {
+            today: None,
+            allow_empty_yanked_releases: true,
+            link_template: None,
+            stop_at_first_finding: false,
+        }
+    }
+}
+
+impl ComplianceOptions {
+    /// Creates the default options, which check for future dates relative to the current date and
+    /// accept yanked releases without changes.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the date releases are checked against for future dates.
+    #[must_use]
+    pub fn with_today(mut self, today: ReleaseDate) -> Self {
+        self.today = Some(today);
+        self
+    }
+
+    /// Set whether a release tagged `[YANKED]` may have its changes removed. Releases tagged
+    /// `[NO CHANGES]` are always allowed to be empty.
+    #[must_use]
+    pub fn with_empty_yanked_releases(mut self, allow: bool) -> Self {
+        self.allow_empty_yanked_releases = allow;
+        self
+    }
+
+    /// Set the template the Unreleased link is expected to follow, so the release it compares
+    /// from is read with the template's provider and tag prefix. Without one, any provider's
+    /// compare link is recognized and the tag prefix is whatever comes before the version.
+    #[must_use]
+    pub fn with_link_template(mut self, link_template: LinkTemplate) -> Self {
+        self.link_template = Some(link_template);
+        self
+    }
+
+    // for failing fast when diagnosing, where only the first finding is reported
+    pub(crate) fn with_stop_at_first_finding(mut self, stop: bool) -> Self {
+        self.stop_at_first_finding = stop;
+        self
+    }
+}
+
+// without a template the tag prefix isn't known, so the base is the longest end of the tag that is
+// a version, which is `1.2.3` for both `v1.2.3` and `my-crate@1.2.3`, and links that don't compare
+// from a tag aren't reported
+fn is_stale_unreleased_link(
+    link: &ReleaseLink,
+    latest: &ReleaseVersion,
+    options: &ComplianceOptions,
+) -> bool {
+    if let Some(link_template) = &options.link_template {
+        return link_template.provider().has_compare_view()
+            && link_template.compare_base(link).as_ref() != Some(latest);
+    }
+    let link = link.to_string();
+    let base = [RepositoryProvider::GitHub, RepositoryProvider::Bitbucket]
+        .into_iter()
+        .find_map(|provider| provider.compare_base(&link))
+        .and_then(|tag| {
+            tag.char_indices()
+                .find_map(|(index, _)| tag[index..].parse::<ReleaseVersion>().ok())
+        });
+    base.is_some_and(|base| base != *latest)
+}
+
+pub(crate) fn compliance_report(
+    changelog: &Changelog,
+    options: &ComplianceOptions,
+) -> ComplianceReport {
+    let today = options.today.clone().unwrap_or_else(ReleaseDate::today);
+
+    let mut findings = vec![];
+
+    if changelog.missing_unreleased_section {
+        findings.push(ComplianceFinding {
+            rule: ComplianceRule::UnreleasedSection,
+            section: Section::Unreleased,
+            message: "Changelog has no Unreleased section to track upcoming changes".to_string(),
+        });
+    }
+
+    let latest = changelog.releases.iter().map(|(version, _)| version).max();
+    if let Some(latest) = latest {
+        match &changelog.unreleased.link {
+            None => findings.push(ComplianceFinding {
+                rule: ComplianceRule::LinkableVersions,
+                section: Section::Unreleased,
+                message: "Unreleased section has no link to the changes since the latest release"
+                    .to_string(),
+            }),
+            // the link is left behind when a release is added by hand without updating it
+            Some(link) if is_stale_unreleased_link(link, latest, options) => {
+                findings.push(ComplianceFinding {
+                    rule: ComplianceRule::LinkableVersions,
+                    section: Section::Unreleased,
+                    message: format!(
+                        "Unreleased section link doesn't compare against the latest release {latest}"
+                    ),
+                });
+            }
+            Some(_) => {}
+        }
+    }
+
+    check_entries(
+        &mut findings,
+        &Section::Unreleased,
+        &changelog.unreleased.changes,
+    );
+    if options.stop_at_first_finding && !findings.is_empty() {
+        findings.truncate(1);
+        return ComplianceReport(findings);
+    }
+
+    let releases = changelog.releases.iter().collect::<Vec<_>>();
+    for (index, (version, release)) in releases.iter().enumerate() {
+        let section = Section::Release((*version).clone());
+
+        if let Some((previous_version, previous_release)) = releases.get(index + 1) {
+            if version <= previous_version {
+                findings.push(ComplianceFinding {
+                    rule: ComplianceRule::LatestVersionFirst,
+                    section: section.clone(),
+                    message: format!(
+                        "Release is listed before the higher version {previous_version}"
+                    ),
+                });
+            }
+            if release.date < previous_release.date {
+                findings.push(ComplianceFinding {
+                    rule: ComplianceRule::ChronologicalDates,
+                    section: section.clone(),
+                    message: format!(
+                        "Release date {} is earlier than the date of the following release {previous_version} ({})",
+                        release.date, previous_release.date
+                    ),
+                });
+            }
+        }
+
+        if release.date > today {
+            findings.push(ComplianceFinding {
+                rule: ComplianceRule::NoFutureDates,
+                section: section.clone(),
+                message: format!("Release date {} is in the future", release.date),
+            });
+        }
+
+        if release.link.is_none() {
+            findings.push(ComplianceFinding {
+                rule: ComplianceRule::LinkableVersions,
+                section: section.clone(),
+                message: "Release has no link".to_string(),
+            });
+        }
+
+        let may_be_empty = match release.tag {
+            Some(ReleaseTag::NoChanges) => true,
+            Some(ReleaseTag::Yanked) => options.allow_empty_yanked_releases,
+            None => false,
+        };
+        if release.changes.is_empty() && !may_be_empty {
+            findings.push(ComplianceFinding {
+                rule: ComplianceRule::NoEmptyReleases,
+                section: section.clone(),
+                message: "Release has no changes and is not tagged [NO CHANGES]".to_string(),
+            });
+        }
+
+        check_entries(&mut findings, &section, &release.changes);
+        if options.stop_at_first_finding && !findings.is_empty() {
+            findings.truncate(1);
+            break;
+        }
+    }
+
+    ComplianceReport(findings)
+}
+
+fn check_entries(findings: &mut Vec<ComplianceFinding>, section: &Section, changes: &Changes) {
+    for (change_group, items) in changes {
+        for item in items {
+            if COMMIT_LOG_ENTRY.is_match(item) {
+                findings.push(ComplianceFinding {
+                    rule: ComplianceRule::NoCommitLogDumps,
+                    section: section.clone(),
+                    message: format!(
+                        "{change_group} entry looks like a commit message rather than a description of the change: {item}"
+                    ),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_all_rules_parse_from_their_code() {
+        for rule in ComplianceRule::ALL {
+            assert_eq!(rule.code().parse::<ComplianceRule>().unwrap(), rule);
+        }
+    }
+
+    #[test]
+    fn test_compliant_changelog() {
+        let changelog: Changelog = "\
+# Changelog
+
+## [Unreleased]
+
+## [1.0.0] - 2023-02-01
+
+### Added
+
+- Added feature X
+
+[unreleased]: https://github.com/org/repo/compare/v1.0.0...HEAD
+[1.0.0]: https://github.com/org/repo/releases/tag/v1.0.0
+"
+        .parse()
+        .unwrap();
+        assert!(changelog.compliance_report().is_compliant());
+    }
+
+    #[test]
+    fn test_future_dated_release() {
+        let changelog: Changelog = "\
+# Changelog
+
+## [Unreleased]
+
+## [1.1.0] - 2035-02-01
+
+### Added
+
+- Added feature Y
+
+## [1.0.0] - 2024-01-01
+
+### Added
+
+- Added feature X
+
+[unreleased]: https://github.com/org/repo/compare/v1.1.0...HEAD
+[1.1.0]: https://github.com/org/repo/compare/v1.0.0...v1.1.0
+[1.0.0]: https://github.com/org/repo/releases/tag/v1.0.0
+"
+        .parse()
+        .unwrap();
+
+        let report = changelog.compliance_report_as_of(&"2025-02-01".parse().unwrap());
+        assert_eq!(
+            report
+                .iter()
+                .map(|finding| (finding.rule, finding.section.to_string()))
+                .collect::<Vec<_>>(),
+            vec![(ComplianceRule::NoFutureDates, "1.1.0".to_string())]
+        );
+        assert!(changelog
+            .compliance_report_as_of(&"2035-02-01".parse().unwrap())
+            .is_compliant());
+    }
+
+    #[test]
+    fn test_empty_releases() {
+        let changelog: Changelog = "\
+# Changelog
+
+## [Unreleased]
+
+## [1.2.0] - 2023-03-01 [NO CHANGES]
+
+## [1.1.0] - 2023-02-01 [YANKED]
+
+## [1.0.0] - 2023-01-01
+
+[unreleased]: https://github.com/org/repo/compare/v1.2.0...HEAD
+[1.2.0]: https://github.com/org/repo/compare/v1.1.0...v1.2.0
+[1.1.0]: https://github.com/org/repo/compare/v1.0.0...v1.1.0
+[1.0.0]: https://github.com/org/repo/releases/tag/v1.0.0
+"
+        .parse()
+        .unwrap();
+
+        let empty_releases = |report: ComplianceReport| {
+            report
+                .iter()
+                .map(|finding| (finding.rule, finding.section.to_string()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            empty_releases(changelog.compliance_report()),
+            vec![(ComplianceRule::NoEmptyReleases, "1.0.0".to_string())]
+        );
+        assert_eq!(
+            empty_releases(changelog.compliance_report_with_options(
+                &ComplianceOptions::new().with_empty_yanked_releases(false)
+            )),
+            vec![
+                (ComplianceRule::NoEmptyReleases, "1.1.0".to_string()),
+                (ComplianceRule::NoEmptyReleases, "1.0.0".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_unreleased_section() {
+        let input = "\
+# Changelog
+
+## [1.0.0] - 2023-02-01
+
+### Added
+
+- Added feature X
+
+[1.0.0]: https://github.com/org/repo/releases/tag/v1.0.0
+";
+        let changelog: Changelog = input.parse().unwrap();
+        let report = changelog.compliance_report();
+        assert_eq!(
+            report
+                .iter()
+                .map(|finding| (finding.rule, finding.section.to_string()))
+                .collect::<Vec<_>>(),
+            vec![
+                (ComplianceRule::UnreleasedSection, "Unreleased".to_string()),
+                (ComplianceRule::LinkableVersions, "Unreleased".to_string()),
+            ]
+        );
+
+        let written: Changelog = changelog.to_string().parse().unwrap();
+        assert!(!written.missing_unreleased_section);
+        assert_eq!(written, changelog);
+    }
+
+    #[test]
+    fn test_stale_unreleased_link() {
+        let changelog: Changelog = "\
+# Changelog
+
+## [Unreleased]
+
+## [1.1.0] - 2023-02-01
+
+### Added
+
+- Added feature Y
+
+## [1.0.0] - 2023-01-01
+
+### Added
+
+- Added feature X
+
+[unreleased]: https://github.com/org/repo/compare/v1.0.0...HEAD
+[1.1.0]: https://github.com/org/repo/compare/v1.0.0...v1.1.0
+[1.0.0]: https://github.com/org/repo/releases/tag/v1.0.0
+"
+        .parse()
+        .unwrap();
+
+        let report = changelog.compliance_report();
+        let findings = report.iter().collect::<Vec<_>>();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule, ComplianceRule::LinkableVersions);
+        assert_eq!(findings[0].section, Section::Unreleased);
+        assert_eq!(
+            findings[0].message,
+            "Unreleased section link doesn't compare against the latest release 1.1.0"
+        );
+
+        let stale = |link: &str, options: &ComplianceOptions| {
+            let mut changelog = changelog.clone();
+            changelog.unreleased.link = Some(link.parse().unwrap());
+            changelog
+                .compliance_report_with_options(options)
+                .iter()
+                .any(|finding| finding.rule == ComplianceRule::LinkableVersions)
+        };
+        let options = ComplianceOptions::new();
+        assert!(!stale(
+            "https://github.com/org/repo/compare/v1.1.0...HEAD",
+            &options
+        ));
+        assert!(!stale(
+            "https://bitbucket.org/org/repo/branches/compare/HEAD%0Dmy-crate@1.1.0",
+            &options
+        ));
+        assert!(stale(
+            "https://github.com/org/repo/compare/v1.1.0-rc.1...HEAD",
+            &options
+        ));
+        assert!(stale(
+            "https://github.com/org/repo/compare/v11.1.0...HEAD",
+            &options
+        ));
+        assert!(!stale("https://example.com/changes/1.0.0", &options));
+
+        let options = ComplianceOptions::new().with_link_template(
+            "https://github.com/org/repo"
+                .parse::<LinkTemplate>()
+                .unwrap()
+                .with_tag_prefix("release-"),
+        );
+        assert!(!stale(
+            "https://github.com/org/repo/compare/release-1.1.0...HEAD",
+            &options
+        ));
+        assert!(stale(
+            "https://github.com/org/repo/compare/v1.1.0...HEAD",
+            &options
+        ));
+
+        // sourcehut links don't compare against a release, so they're never stale
+        let options = ComplianceOptions::new().with_link_template(
+            "https://git.sr.ht/~user/repo"
+                .parse::<LinkTemplate>()
+                .unwrap(),
+        );
+        assert!(!stale("https://git.sr.ht/~user/repo/log", &options));
+        assert!(!stale(
+            "https://git.sr.ht/~user/repo/log/HEAD?from=v1.0.0",
+            &ComplianceOptions::new()
+        ));
+    }
+
+    #[test]
+    fn test_non_compliant_changelog() {
+        let changelog: Changelog = "\
+# Changelog
+
+## [Unreleased]
+
+### Fixed
+
+- fix(parser): handle empty input
+
+## [1.0.0] - 2023-01-01
+
+### Added
+
+- Added feature X
+
+## [1.1.0] - 2023-02-01
+
+### Changed
+
+- a1b2c3d Update dependencies
+- Merge pull request #12 from org/branch
+
+[1.0.0]: https://github.com/org/repo/compare/v1.1.0...v1.0.0
+[1.1.0]: https://github.com/org/repo/releases/tag/v1.1.0
+"
+        .parse()
+        .unwrap();
+
+        let report = changelog.compliance_report();
+        let findings = report
+            .iter()
+            .map(|finding| (finding.rule, finding.section.to_string()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            findings,
+            vec![
+                (ComplianceRule::LinkableVersions, "Unreleased".to_string()),
+                (ComplianceRule::NoCommitLogDumps, "Unreleased".to_string()),
+                (ComplianceRule::LatestVersionFirst, "1.0.0".to_string()),
+                (ComplianceRule::ChronologicalDates, "1.0.0".to_string()),
+                (ComplianceRule::NoCommitLogDumps, "1.1.0".to_string()),
+                (ComplianceRule::NoCommitLogDumps, "1.1.0".to_string()),
+            ]
+        );
+        assert_eq!(
+            report.iter().next().unwrap().rule.spec_reference(),
+            "https://keepachangelog.com/en/1.1.0/#how"
+        );
+    }
+}
@@ -0,0 +1,723 @@
+use crate::changelog::{parse_changelog_source, unusual_character, ParsedChangelog};
+use crate::{ComplianceOptions, ComplianceRule, ParseChangelogOptions, Section};
+use indexmap::IndexMap;
+use markdown::unist::Position;
+use std::fmt::{Display, Formatter};
+
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/// How serious a [`Diagnostic`] is.
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "json", derive(serde::Serialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "lowercase"))]
+pub enum Severity {
+    /// The changelog could not be parsed.
+    Error,
+    /// The changelog was parsed but does not follow the Keep a Changelog guiding principles.
+    Warning,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A problem found in a changelog along with where in the source it occurred.
+///
+/// Lines and columns are 1-indexed and the end position points at the first character after the
+/// problem, so a problem on a single line spans `column..end_column`.
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "json", derive(serde::Serialize))]
+pub struct Diagnostic {
+    /// How serious the problem is.
+    pub severity: Severity,
+    /// The guiding principle that was not followed, for problems found by the compliance report
+    /// or the link check.
+    #[cfg_attr(feature = "json", serde(serialize_with = "serialize_display"))]
+    pub rule: Option<ComplianceRule>,
+    /// The section of the changelog the problem belongs to, if known.
+    #[cfg_attr(feature = "json", serde(serialize_with = "serialize_display"))]
+    pub section: Option<Section>,
+    /// A description of the problem. The first line is a summary and any following lines give
+    /// more detail.
+    pub message: String,
+    /// The line the problem starts on.
+    pub line: usize,
+    /// The column the problem starts on.
+    pub column: usize,
+    /// The line the problem ends on.
+    pub end_line: usize,
+    /// The column after the end of the problem.
+    pub end_column: usize,
+}
+
+impl Diagnostic {
+    pub(crate) fn new(
+        severity: Severity,
+        message: impl Into<String>,
+        position: Option<&Position>,
+    ) -> Self {
+        let (line, column, end_line, end_column) = position.map_or((1, 1, 1, 1), |position| {
+            (
+                position.start.line,
+                position.start.column,
+                position.end.line,
+                position.end.column,
+            )
+        });
+        Self {
+            severity,
+            rule: None,
+            section: None,
+            message: message.into(),
+            line,
+            column,
+            end_line,
+            end_column,
+        }
+    }
+
+    /// Renders the diagnostic with an excerpt of the offending lines from `source`, marking the
+    /// problem with carets. Diagnostics for a [compliance rule](ComplianceRule) show the rule
+    /// after the severity, e.g. `warning[linkable-versions]`.
+    ///
+    /// ```text
+    /// error: Release header did not match the expected format
+    ///  --> 3:1
+    ///   |
+    /// 3 | ## Not a release header
+    ///   | ^^^^^^^^^^^^^^^^^^^^^^^
+    ///   = Expected: [Unreleased] | [<version>] - <yyyy>-<mm>-<dd> | [<version>] - <yyyy>-<mm>-<dd> [<tag>]
+    ///   = Value: Not a release header
+    /// ```
+    #[must_use]
+    pub fn render(&self, source: &str, options: &RenderOptions) -> String {
+        let paint = |style: &str, text: &str| {
+            if options.color {
+                format!("{style}{text}{RESET}")
+            } else {
+                text.to_string()
+            }
+        };
+        let severity_style = match self.severity {
+            Severity::Error => RED,
+            Severity::Warning => YELLOW,
+        };
+
+        let mut message_lines = self.message.lines();
+        let summary = message_lines.next().unwrap_or_default();
+        let lines = source.lines().collect::<Vec<_>>();
+        let first_line = self
+            .line
+            .saturating_sub(options.context_lines)
+            .clamp(1, lines.len().max(1));
+        let last_line = (self.end_line + options.context_lines).min(lines.len());
+        let gutter_width = last_line.max(self.line).to_string().len();
+        let gutter = paint(BLUE, &format!("{:gutter_width$} |", ""));
+
+        let severity = match self.rule {
+            Some(rule) => format!("{}[{rule}]", self.severity),
+            None => self.severity.to_string(),
+        };
+        let path = options
+            .path
+            .as_ref()
+            .map(|path| format!("{path}:"))
+            .unwrap_or_default();
+
+        let mut output = vec![
+            format!(
+                "{}{}",
+                paint(severity_style, &severity),
+                paint(BOLD, &format!(": {summary}"))
+            ),
+            format!(
+                "{}{} {path}{}:{}",
+                " ".repeat(gutter_width),
+                paint(BLUE, "-->"),
+                self.line,
+                self.column
+            ),
+            gutter.clone(),
+        ];
+
+        for line_number in first_line..=last_line {
+            let text = lines[line_number - 1];
+            output.push(
+                format!(
+                    "{} {text}",
+                    paint(BLUE, &format!("{line_number:>gutter_width$} |"))
+                )
+                .trim_end()
+                .to_string(),
+            );
+            if (self.line..=self.end_line).contains(&line_number) {
+                let start = if line_number == self.line {
+                    self.column
+                } else {
+                    1
+                };
+                let end = if line_number == self.end_line {
+                    self.end_column
+                } else {
+                    text.chars().count() + 1
+                };
+                let width = end.saturating_sub(start).max(1);
+                output.push(format!(
+                    "{gutter} {}{}",
+                    " ".repeat(start.saturating_sub(1)),
+                    paint(severity_style, &"^".repeat(width))
+                ));
+            }
+        }
+
+        for detail in message_lines {
+            output.push(format!(
+                "{}{} {detail}",
+                " ".repeat(gutter_width + 1),
+                paint(BLUE, "=")
+            ));
+        }
+
+        output.join("\n")
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{} {}: {}",
+            self.line, self.column, self.severity, self.message
+        )
+    }
+}
+
+#[cfg(feature = "json")]
+#[allow(clippy::ref_option)]
+fn serialize_display<T: Display, S: serde::Serializer>(
+    value: &Option<T>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => serializer.collect_str(value),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Options for [`Diagnostic::render`].
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct RenderOptions {
+    color: bool,
+    context_lines: usize,
+    path: Option<String>,
+}
+
+impl RenderOptions {
+    /// Creates options that render without color or surrounding lines.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Highlights the output with ANSI color codes.
+    #[must_use]
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Includes the given number of lines before and after the problem in the excerpt.
+    #[must_use]
+    pub fn with_context_lines(mut self, context_lines: usize) -> Self {
+        self.context_lines = context_lines;
+        self
+    }
+
+    /// Shows the path of the changelog before the line and column, e.g. `--> CHANGELOG.md:3:1`.
+    #[must_use]
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+}
+
+/// Diagnostics grouped under the section of the changelog they belong to, in the order the
+/// sections first appear.
+///
+/// Displaying the groups summarizes them, e.g. `3 issues in 2.1.0, 1 issue in Unreleased`.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct DiagnosticsBySection(IndexMap<Option<Section>, Vec<Diagnostic>>);
+
+impl DiagnosticsBySection {
+    /// Returns the diagnostics for `section`.
+    #[must_use]
+    pub fn get(&self, section: &Section) -> &[Diagnostic] {
+        self.0
+            .get(&Some(section.clone()))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the diagnostics that don't belong to a known section, such as errors for a release
+    /// heading that could not be parsed.
+    #[must_use]
+    pub fn unsectioned(&self) -> &[Diagnostic] {
+        self.0.get(&None).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns an iterator over the section/diagnostics pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (Option<&Section>, &[Diagnostic])> {
+        self.0
+            .iter()
+            .map(|(section, diagnostics)| (section.as_ref(), diagnostics.as_slice()))
+    }
+}
+
+impl FromIterator<Diagnostic> for DiagnosticsBySection {
+    fn from_iter<I: IntoIterator<Item = Diagnostic>>(iter: I) -> Self {
+        let mut sections: IndexMap<Option<Section>, Vec<Diagnostic>> = IndexMap::new();
+        for diagnostic in iter {
+            sections
+                .entry(diagnostic.section.clone())
+                .or_default()
+                .push(diagnostic);
+        }
+        Self(sections)
+    }
+}
+
+impl Display for DiagnosticsBySection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let summary = self
+            .iter()
+            .map(|(section, diagnostics)| {
+                let count = diagnostics.len();
+                let issues = if count == 1 { "issue" } else { "issues" };
+                match section {
+                    Some(section) => format!("{count} {issues} in {section}"),
+                    None => format!("{count} {issues} outside of a section"),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "{summary}")
+    }
+}
+
+/// Options for [`Changelog::diagnose_with_options`](crate::Changelog::diagnose_with_options).
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct DiagnoseOptions {
+    max_diagnostics: Option<usize>,
+    fail_fast: bool,
+}
+
+impl DiagnoseOptions {
+    /// Creates options that report every diagnostic.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the number of diagnostics reported. When there are more, how many were left out is
+    /// reported as [`LimitedDiagnostics::omitted`].
+    #[must_use]
+    pub fn with_max_diagnostics(mut self, max_diagnostics: usize) -> Self {
+        self.max_diagnostics = Some(max_diagnostics);
+        self
+    }
+
+    /// Stops at the first problem found instead of checking the rest of the changelog. Each
+    /// check returns as soon as it finds a problem and the checks after it are skipped.
+    #[must_use]
+    pub fn with_fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
+    }
+}
+
+/// The diagnostics returned by
+/// [`Changelog::diagnose_with_options`](crate::Changelog::diagnose_with_options).
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "json", derive(serde::Serialize))]
+pub struct LimitedDiagnostics {
+    /// The diagnostics reported, in the order they occur in the changelog.
+    pub diagnostics: Vec<Diagnostic>,
+    /// How many more diagnostics were found than the
+    /// [limit](DiagnoseOptions::with_max_diagnostics) allowed. Checking stops at the first problem
+    /// when [failing fast](DiagnoseOptions::with_fail_fast), so nothing is counted then.
+    pub omitted: usize,
+}
+
+pub(crate) fn diagnose(input: &str, options: &DiagnoseOptions) -> LimitedDiagnostics {
+    let mut diagnostics = match parse_changelog_source(input, &ParseChangelogOptions::new()) {
+        Ok(mut parsed) if options.fail_fast && !parsed.nonstandard_headings.is_empty() => {
+            parsed.nonstandard_headings.truncate(1);
+            parsed.nonstandard_headings
+        }
+        Ok(mut parsed) => {
+            let findings = finding_diagnostics(&parsed, options);
+            parsed.nonstandard_headings.extend(findings);
+            parsed.nonstandard_headings
+        }
+        Err(e) => vec![e.diagnostic()],
+    };
+    if options.fail_fast && !diagnostics.is_empty() {
+        return LimitedDiagnostics {
+            diagnostics,
+            omitted: 0,
+        };
+    }
+
+    // hints are ordered and limited along with everything else so they're counted when omitted
+    diagnostics.extend(unusual_character_diagnostics(input, options.fail_fast));
+    diagnostics.sort_by_key(|diagnostic| (diagnostic.line, diagnostic.column));
+
+    let limit = options.max_diagnostics.unwrap_or(diagnostics.len());
+    let omitted = diagnostics.split_off(limit.min(diagnostics.len())).len();
+    LimitedDiagnostics {
+        diagnostics,
+        omitted,
+    }
+}
+
+// the compliance report works on the parsed changelog so findings are located by the heading of
+// the section they belong to
+fn finding_diagnostics(parsed: &ParsedChangelog, options: &DiagnoseOptions) -> Vec<Diagnostic> {
+    let compliance_options = ComplianceOptions::new().with_stop_at_first_finding(options.fail_fast);
+    parsed
+        .changelog
+        .compliance_report_with_options(&compliance_options)
+        .iter()
+        .map(|finding| Diagnostic {
+            rule: Some(finding.rule),
+            section: Some(finding.section.clone()),
+            ..Diagnostic::new(
+                finding.rule.default_severity(),
+                format!(
+                    "{}\nSee: {}",
+                    finding.message,
+                    finding.rule.spec_reference()
+                ),
+                parsed.headings.get(&finding.section),
+            )
+        })
+        .collect()
+}
+
+// invisible characters in headings are the usual reason a heading that looks right is rejected
+fn unusual_character_diagnostics(input: &str, stop_at_first: bool) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let mut line_offset = 0;
+    for (line_index, line) in input.split_inclusive('\n').enumerate() {
+        let markers = line.trim_start_matches(' ');
+        if markers.starts_with('#') {
+            let after_markers = line.len() - markers.trim_start_matches('#').len();
+            for (column, (offset, c)) in line.char_indices().enumerate() {
+                let Some((name, replacement)) = unusual_character(c) else {
+                    continue;
+                };
+                let effect = if offset == after_markers {
+                    "so the line is not read as a heading".to_string()
+                } else {
+                    match replacement {
+                        Some(replacement) => format!("which is read as '{replacement}'"),
+                        None => "which is ignored".to_string(),
+                    }
+                };
+                let line = line_index + 1;
+                let start = line_offset + offset;
+                diagnostics.push(Diagnostic::new(
+                    Severity::Warning,
+                    format!(
+                        "Heading contains the invisible character U+{:04X} {name}, {effect}",
+                        c as u32
+                    ),
+                    Some(&Position::new(
+                        line,
+                        column + 1,
+                        start,
+                        line,
+                        column + 2,
+                        start + c.len_utf8(),
+                    )),
+                ));
+                if stop_at_first {
+                    return diagnostics;
+                }
+            }
+        }
+        line_offset += line.len();
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::Changelog;
+
+    const INVALID_HEADING: &str = "# Changelog\n\n## Not a release header\n\n### Added\n";
+
+    #[test]
+    fn test_parse_error_diagnostic() {
+        let diagnostic = INVALID_HEADING
+            .parse::<Changelog>()
+            .unwrap_err()
+            .diagnostic();
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(
+            (
+                diagnostic.line,
+                diagnostic.column,
+                diagnostic.end_line,
+                diagnostic.end_column
+            ),
+            (3, 1, 3, 24)
+        );
+        assert!(diagnostic
+            .to_string()
+            .starts_with("3:1 error: Release header did not match the expected format"));
+    }
+
+    #[test]
+    fn test_render() {
+        let diagnostic = INVALID_HEADING
+            .parse::<Changelog>()
+            .unwrap_err()
+            .diagnostic();
+        assert_eq!(
+            diagnostic.render(INVALID_HEADING, &RenderOptions::new()),
+            "\
+error: Release header did not match the expected format
+ --> 3:1
+  |
+3 | ## Not a release header
+  | ^^^^^^^^^^^^^^^^^^^^^^^
+  = Expected: [Unreleased] | [<version>] - <yyyy>-<mm>-<dd> | [<version>] - <yyyy>-<mm>-<dd> [<tag>]
+  = Value: Not a release header"
+        );
+        assert_eq!(
+            diagnostic.render(INVALID_HEADING, &RenderOptions::new().with_context_lines(1)),
+            "\
+error: Release header did not match the expected format
+ --> 3:1
+  |
+2 |
+3 | ## Not a release header
+  | ^^^^^^^^^^^^^^^^^^^^^^^
+4 |
+  = Expected: [Unreleased] | [<version>] - <yyyy>-<mm>-<dd> | [<version>] - <yyyy>-<mm>-<dd> [<tag>]
+  = Value: Not a release header"
+        );
+        assert!(diagnostic
+            .render(
+                INVALID_HEADING,
+                &RenderOptions::new().with_path("CHANGELOG.md")
+            )
+            .contains(" --> CHANGELOG.md:3:1\n"));
+        assert!(diagnostic
+            .render(INVALID_HEADING, &RenderOptions::new().with_color(true))
+            .starts_with("\x1b[1;31merror\x1b[0m"));
+
+        let mut diagnostic = diagnostic;
+        diagnostic.column = 0;
+        assert!(diagnostic
+            .render(INVALID_HEADING, &RenderOptions::new())
+            .contains("\n  | ^^^^^^^^^^^^^^^^^^^^^^^^\n"));
+    }
+
+    #[test]
+    fn test_render_multi_line() {
+        let source = "first\nsecond line\nthird";
+        let diagnostic = Diagnostic::new(
+            Severity::Warning,
+            "Spans lines",
+            Some(&Position::new(1, 3, 2, 2, 12, 17)),
+        );
+        assert_eq!(
+            diagnostic.render(source, &RenderOptions::new()),
+            "\
+warning: Spans lines
+ --> 1:3
+  |
+1 | first
+  |   ^^^
+2 | second line
+  | ^^^^^^^^^^^"
+        );
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let diagnostics = Changelog::diagnose(
+            "# Changelog\n\n## [Unreleased]\n\n## [1.0.0] - 2023-01-01\n\n### Fixed\n\n- fix: Y\n",
+        );
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|diagnostic| (diagnostic.rule, diagnostic.line))
+                .collect::<Vec<_>>(),
+            vec![
+                (Some(ComplianceRule::LinkableVersions), 3),
+                (Some(ComplianceRule::LinkableVersions), 5),
+                (Some(ComplianceRule::NoCommitLogDumps), 5),
+            ]
+        );
+        assert!(diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.severity == Severity::Warning));
+        assert!(diagnostics[0]
+            .render("", &RenderOptions::new())
+            .starts_with("warning[linkable-versions]: "));
+
+        let diagnostics = Changelog::diagnose(INVALID_HEADING);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_diagnose_with_options() {
+        let input =
+            "# Changelog\n\n## [Unreleased]\n\n## [1.0.0] - 2023-01-01\n\n### Fixed\n\n- fix: Y\n";
+
+        let limited = Changelog::diagnose_with_options(
+            input,
+            &DiagnoseOptions::new().with_max_diagnostics(1),
+        );
+        assert_eq!(
+            limited
+                .diagnostics
+                .iter()
+                .map(|diagnostic| (diagnostic.rule, diagnostic.message.lines().next().unwrap()))
+                .collect::<Vec<_>>(),
+            vec![(
+                Some(ComplianceRule::LinkableVersions),
+                "Unreleased section has no link to the changes since the latest release"
+            )]
+        );
+        assert_eq!(limited.omitted, 2);
+
+        let limited = Changelog::diagnose_with_options(
+            input,
+            &DiagnoseOptions::new().with_max_diagnostics(3),
+        );
+        assert_eq!((limited.diagnostics.len(), limited.omitted), (3, 0));
+
+        let limited =
+            Changelog::diagnose_with_options(input, &DiagnoseOptions::new().with_fail_fast(true));
+        assert_eq!((limited.diagnostics.len(), limited.omitted), (1, 0));
+        assert_eq!(
+            limited.diagnostics[0].rule,
+            Some(ComplianceRule::LinkableVersions)
+        );
+
+        // failing fast skips the release checks once the Unreleased section has a finding
+        let report = input
+            .parse::<Changelog>()
+            .unwrap()
+            .compliance_report_with_options(
+                &ComplianceOptions::new().with_stop_at_first_finding(true),
+            );
+        assert_eq!(report.iter().count(), 1);
+    }
+
+    #[test]
+    fn test_diagnostics_by_section() {
+        let by_section = Changelog::diagnose(
+            "# Changelog\n\n## [Unreleased]\n\n## [1.0.0] - 2023-01-01\n\n### Fixed\n\n- fix: Y\n",
+        )
+        .into_iter()
+        .collect::<DiagnosticsBySection>();
+
+        assert_eq!(
+            by_section.to_string(),
+            "1 issue in Unreleased, 2 issues in 1.0.0"
+        );
+        assert_eq!(
+            by_section
+                .get(&Section::Release("1.0.0".parse().unwrap()))
+                .len(),
+            2
+        );
+        assert!(by_section
+            .get(&Section::Release("2.0.0".parse().unwrap()))
+            .is_empty());
+        assert!(by_section.unsectioned().is_empty());
+
+        let by_section = Changelog::diagnose(INVALID_HEADING)
+            .into_iter()
+            .collect::<DiagnosticsBySection>();
+        assert_eq!(by_section.unsectioned().len(), 1);
+        assert_eq!(by_section.to_string(), "1 issue outside of a section");
+    }
+
+    #[test]
+    fn test_diagnose_nonstandard_separator() {
+        let input = "# Changelog\n\n## [Unreleased]\n\n## [1.1.0]\u{2013}2024-02-01 [YANKED]\n\n## [1.0.0] - 2024-01-01\n";
+        let diagnostics = Changelog::diagnose(input)
+            .into_iter()
+            .filter(|diagnostic| diagnostic.rule.is_none())
+            .collect::<Vec<_>>();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (5, 1));
+        assert_eq!(
+            diagnostics[0].message,
+            "Release header separates the version and date with '\u{2013}' instead of ' - '\nExpected: [1.1.0] - 2024-02-01 [YANKED]\nValue: [1.1.0]\u{2013}2024-02-01 [YANKED]"
+        );
+    }
+
+    #[test]
+    fn test_diagnose_unusual_characters() {
+        let input = "# Changelog\n\n##\u{a0}[Unreleased]\n\n## [1.0.0]\u{a0}- 2024-01-01\n";
+        let diagnostics = Changelog::diagnose(input);
+        let hints = diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.message.contains("U+00A0"))
+            .map(|diagnostic| {
+                (
+                    diagnostic.line,
+                    diagnostic.column,
+                    diagnostic.message.as_str(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            hints,
+            [
+                (
+                    3,
+                    3,
+                    "Heading contains the invisible character U+00A0 NO-BREAK SPACE, so the line is not read as a heading"
+                ),
+                (
+                    5,
+                    11,
+                    "Heading contains the invisible character U+00A0 NO-BREAK SPACE, which is read as ' '"
+                ),
+            ]
+        );
+
+        let limited = Changelog::diagnose_with_options(
+            "# Changelog\n\n## [Unreleased]\n\n##\u{a0}A\n\n##\u{a0}B\n\n##\u{a0}C\n",
+            &DiagnoseOptions::new().with_max_diagnostics(1),
+        );
+        assert_eq!(limited.diagnostics.len(), 1);
+        assert_eq!(limited.diagnostics[0].line, 5);
+        assert_eq!(limited.omitted, 2);
+
+        let changelog: Changelog = input.replace("##\u{a0}", "## ").parse().unwrap();
+        assert!(changelog
+            .releases
+            .contains_version(&"1.0.0".parse().unwrap()));
+    }
+}
@@ -0,0 +1,309 @@
+use crate::{Changelog, ComplianceRule, Diagnostic};
+use serde::Serialize;
+use std::fmt::Write;
+use std::path::{Component, Path};
+
+/// The diagnostics for a changelog file in a form that CI systems and editors can consume.
+///
+/// ```json
+/// {
+///   "path": "CHANGELOG.md",
+///   "tool_version": "0.1.0",
+///   "diagnostics": [
+///     {
+///       "severity": "warning",
+///       "rule": "linkable-versions",
+///       "section": "1.0.0",
+///       "message": "Release has no link\nSee: https://keepachangelog.com/en/1.1.0/#how",
+///       "line": 5,
+///       "column": 1,
+///       "end_line": 5,
+///       "end_column": 24
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Eq, PartialEq, Clone, Serialize)]
+pub struct DiagnosticsReport {
+    /// The path of the changelog file the diagnostics were produced for.
+    pub path: String,
+    /// The version of this library that produced the diagnostics.
+    pub tool_version: String,
+    /// The problems found in the changelog.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Serialize)]
+struct DiagnosticLine<'a> {
+    path: &'a str,
+    tool_version: &'a str,
+    #[serde(flatten)]
+    diagnostic: &'a Diagnostic,
+}
+
+impl DiagnosticsReport {
+    /// Creates a report for the diagnostics produced for the changelog at `path`.
+    pub fn new(path: impl Into<String>, diagnostics: Vec<Diagnostic>) -> Self {
+        Self {
+            path: path.into(),
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            diagnostics,
+        }
+    }
+
+    /// Creates a report from the [diagnostics](Changelog::diagnose) for the changelog `input` read
+    /// from `path`.
+    pub fn diagnose(path: impl Into<String>, input: &str) -> Self {
+        Self::new(path, Changelog::diagnose(input))
+    }
+
+    /// Serializes the report as a single JSON document.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Diagnostics should serialize to JSON")
+    }
+
+    /// Serializes the report as [JSON Lines](https://jsonlines.org) with one diagnostic per line.
+    /// Each line includes the path and tool version so lines from several reports can be
+    /// concatenated into a single stream.
+    #[must_use]
+    pub fn to_json_lines(&self) -> String {
+        self.diagnostics
+            .iter()
+            .map(|diagnostic| {
+                let line = DiagnosticLine {
+                    path: &self.path,
+                    tool_version: &self.tool_version,
+                    diagnostic,
+                };
+                serde_json::to_string(&line).expect("Diagnostics should serialize to JSON") + "\n"
+            })
+            .collect()
+    }
+
+    /// Serializes the reports as a single [SARIF](https://sarifweb.azurewebsites.net) log, the
+    /// format code scanning tools such as GitHub code scanning import. Diagnostics for a
+    /// [compliance rule](ComplianceRule) use the rule's code as their rule id. Report paths are
+    /// written as percent-encoded URIs, and diagnostics for a changelog read from stdin (`-`) have
+    /// no location since there is no file to point at.
+    #[must_use]
+    pub fn to_sarif(reports: &[DiagnosticsReport]) -> String {
+        let mut rules: Vec<ComplianceRule> = vec![];
+        let mut results = vec![];
+        for report in reports {
+            for diagnostic in &report.diagnostics {
+                if let Some(rule) = diagnostic.rule {
+                    if !rules.contains(&rule) {
+                        rules.push(rule);
+                    }
+                }
+                let mut result = serde_json::json!({
+                    "level": diagnostic.severity,
+                    "message": { "text": diagnostic.message },
+                });
+                if let Some(uri) = artifact_uri(&report.path) {
+                    result["locations"] = serde_json::json!([{
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": uri },
+                            "region": {
+                                "startLine": diagnostic.line,
+                                "startColumn": diagnostic.column,
+                                "endLine": diagnostic.end_line,
+                                "endColumn": diagnostic.end_column
+                            }
+                        }
+                    }]);
+                }
+                if let Some(rule) = diagnostic.rule {
+                    result["ruleId"] = rule.code().into();
+                }
+                results.push(result);
+            }
+        }
+        let rules = rules
+            .iter()
+            .map(|rule| serde_json::json!({ "id": rule.code(), "helpUri": rule.spec_reference() }))
+            .collect::<Vec<_>>();
+        let log = serde_json::json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "keep_a_changelog_file",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                        "rules": rules
+                    }
+                },
+                "results": results
+            }]
+        });
+        serde_json::to_string_pretty(&log).expect("Diagnostics should serialize to JSON")
+    }
+}
+
+// SARIF locations are URI references, so paths are joined with `/` whatever the platform separator
+// is and every byte outside the unreserved set is percent-encoded, which keeps spaces, `#` and `%`
+// in file names from being read as URI syntax
+fn artifact_uri(path: &str) -> Option<String> {
+    if path == "-" {
+        return None;
+    }
+    let path = Path::new(path);
+    let mut uri = String::new();
+    if path.has_root() {
+        uri.push_str("file://");
+    }
+    let mut segments = vec![];
+    for component in path.components() {
+        match component {
+            Component::Prefix(prefix) => {
+                uri.push('/');
+                uri.push_str(&prefix.as_os_str().to_string_lossy());
+            }
+            Component::RootDir => {}
+            Component::CurDir => segments.push(".".to_string()),
+            Component::ParentDir => segments.push("..".to_string()),
+            Component::Normal(segment) => {
+                segments.push(percent_encode(&segment.to_string_lossy()));
+            }
+        }
+    }
+    if path.has_root() {
+        uri.push('/');
+    }
+    uri.push_str(&segments.join("/"));
+    Some(uri)
+}
+
+fn percent_encode(segment: &str) -> String {
+    segment.bytes().fold(String::new(), |mut encoded, byte| {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+        encoded
+    })
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    const CHANGELOG: &str =
+        "# Changelog\n\n## [Unreleased]\n\n## [1.0.0] - 2023-01-01\n\n### Fixed\n\n- fix: Y\n";
+
+    #[test]
+    fn test_to_json() {
+        let report = DiagnosticsReport::diagnose("CHANGELOG.md", CHANGELOG);
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+
+        assert_eq!(json["path"], "CHANGELOG.md");
+        assert_eq!(json["tool_version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(json["diagnostics"].as_array().unwrap().len(), 3);
+        assert_eq!(
+            json["diagnostics"][1],
+            serde_json::json!({
+                "severity": "warning",
+                "rule": "linkable-versions",
+                "section": "1.0.0",
+                "message": "Release has no link\nSee: https://keepachangelog.com/en/1.1.0/#how",
+                "line": 5,
+                "column": 1,
+                "end_line": 5,
+                "end_column": 24
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_sarif() {
+        let sarif = DiagnosticsReport::to_sarif(&[
+            DiagnosticsReport::diagnose("CHANGELOG.md", CHANGELOG),
+            DiagnosticsReport::diagnose("other/CHANGELOG.md", "## Not a release header\n"),
+        ]);
+        let json: serde_json::Value = serde_json::from_str(&sarif).unwrap();
+
+        assert_eq!(json["version"], "2.1.0");
+        let run = &json["runs"][0];
+        assert_eq!(
+            run["tool"]["driver"]["rules"][0],
+            serde_json::json!({
+                "id": "linkable-versions",
+                "helpUri": "https://keepachangelog.com/en/1.1.0/#how"
+            })
+        );
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[1]["ruleId"], "linkable-versions");
+        assert_eq!(results[1]["level"], "warning");
+        assert_eq!(
+            results[1]["locations"][0]["physicalLocation"],
+            serde_json::json!({
+                "artifactLocation": { "uri": "CHANGELOG.md" },
+                "region": { "startLine": 5, "startColumn": 1, "endLine": 5, "endColumn": 24 }
+            })
+        );
+        assert_eq!(results[3]["level"], "error");
+        assert!(results[3].get("ruleId").is_none());
+    }
+
+    #[test]
+    fn test_to_sarif_uri() {
+        let sarif = DiagnosticsReport::to_sarif(&[
+            DiagnosticsReport::diagnose("docs/release notes/CHANGELOG#1.md", "## Not a release\n"),
+            DiagnosticsReport::diagnose("-", "## Not a release\n"),
+        ]);
+        let json: serde_json::Value = serde_json::from_str(&sarif).unwrap();
+
+        let results = json["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "docs/release%20notes/CHANGELOG%231.md"
+        );
+        assert!(results[1].get("locations").is_none());
+        assert_eq!(results[1]["level"], "error");
+    }
+
+    #[test]
+    fn test_artifact_uri() {
+        assert_eq!(artifact_uri("CHANGELOG.md").unwrap(), "CHANGELOG.md");
+        assert_eq!(
+            artifact_uri("../50% off/ñ.md").unwrap(),
+            "../50%25%20off/%C3%B1.md"
+        );
+        #[cfg(unix)]
+        assert_eq!(
+            artifact_uri("/repo/CHANGELOG.md").unwrap(),
+            "file:///repo/CHANGELOG.md"
+        );
+        #[cfg(windows)]
+        assert_eq!(
+            artifact_uri(r"docs\CHANGELOG.md").unwrap(),
+            "docs/CHANGELOG.md"
+        );
+        assert_eq!(artifact_uri("-"), None);
+    }
+
+    #[test]
+    fn test_to_json_lines() {
+        let report = DiagnosticsReport::diagnose("CHANGELOG.md", "## Not a release header\n");
+        let json_lines = report.to_json_lines();
+        let lines = json_lines.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 1);
+
+        let json: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(json["path"], "CHANGELOG.md");
+        assert_eq!(json["severity"], "error");
+        assert_eq!(json["rule"], serde_json::Value::Null);
+        assert_eq!(json["line"], 1);
+
+        assert_eq!(
+            DiagnosticsReport::new("CHANGELOG.md", vec![]).to_json_lines(),
+            ""
+        );
+    }
+}
@@ -0,0 +1,319 @@
+use crate::changes::entry_scope;
+use crate::{Changelog, Changes, LineEnding};
+use std::cmp::Ordering;
+
+/// How entries within each change group are ordered by [`Changelog::format`].
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum EntrySort {
+    /// Entries are sorted alphabetically, ignoring case.
+    Alphabetical,
+    /// Entries are grouped by their [scope](Changes::scopes) and sorted alphabetically within each
+    /// scope. Entries without a scope come last.
+    Scope,
+}
+
+/// How long entries are wrapped by [`Changelog::format`].
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub enum LineWrap {
+    /// Entries keep the line breaks they were written with.
+    #[default]
+    Preserve,
+    /// Each entry is written on a single line.
+    Unwrap,
+    /// Entries are re-wrapped so no line is longer than the given number of columns, unless it
+    /// contains a single word that doesn't fit. Continuation lines are indented under the entry
+    /// and never start with a word such as `#`, `-`, or `1.` that would begin a heading, list, or
+    /// other block, so a line can run past the limit to keep such a word on it.
+    Columns(usize),
+}
+
+/// Options for [`Changelog::format`].
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct FormatOptions {
+    sort_entries: Option<EntrySort>,
+    line_wrap: LineWrap,
+    line_ending: Option<LineEnding>,
+}
+
+impl FormatOptions {
+    /// Creates options that format the changelog the same as its [`Display`](std::fmt::Display)
+    /// implementation.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how entries within each change group are sorted. Entries keep their original order
+    /// when this isn't set.
+    #[must_use]
+    pub fn with_sort_entries(mut self, sort_entries: EntrySort) -> Self {
+        self.sort_entries = Some(sort_entries);
+        self
+    }
+
+    /// Set how long entries are wrapped. Entries containing block content such as nested lists
+    /// or code blocks always keep their original line breaks.
+    #[must_use]
+    pub fn with_line_wrap(mut self, line_wrap: LineWrap) -> Self {
+        self.line_wrap = line_wrap;
+        self
+    }
+
+    /// Set the line ending to write, overriding the [line ending](Changelog::line_ending) of the
+    /// changelog.
+    #[must_use]
+    pub fn with_line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = Some(line_ending);
+        self
+    }
+}
+
+pub(crate) fn format(changelog: &Changelog, options: &FormatOptions) -> String {
+    // entries are only copied when there is something to rewrite
+    if options.sort_entries.is_none()
+        && options.line_wrap == LineWrap::Preserve
+        && options.line_ending.is_none()
+    {
+        return changelog.to_string();
+    }
+
+    let mut changelog = changelog.clone();
+
+    if let Some(sort_entries) = options.sort_entries {
+        sort(&mut changelog.unreleased.changes, sort_entries);
+        for release in changelog.releases.values_mut() {
+            sort(&mut release.changes, sort_entries);
+        }
+    }
+
+    if options.line_wrap != LineWrap::Preserve {
+        wrap(&mut changelog.unreleased.changes, options.line_wrap);
+        for release in changelog.releases.values_mut() {
+            wrap(&mut release.changes, options.line_wrap);
+        }
+    }
+
+    if let Some(line_ending) = options.line_ending {
+        changelog.line_ending = line_ending;
+    }
+
+    changelog.to_string()
+}
+
+fn sort(changes: &mut Changes, sort_entries: EntrySort) {
+    for items in changes.items_mut() {
+        items.sort_by(|a, b| match sort_entries {
+            EntrySort::Alphabetical => alphabetical(a, b),
+            EntrySort::Scope => match (entry_scope(a), entry_scope(b)) {
+                (Some(a_scope), Some(b_scope)) => {
+                    alphabetical(a_scope, b_scope).then_with(|| alphabetical(a, b))
+                }
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => alphabetical(a, b),
+            },
+        });
+    }
+}
+
+fn wrap(changes: &mut Changes, line_wrap: LineWrap) {
+    for items in changes.items_mut() {
+        for item in items.iter_mut().filter(|item| !has_block_content(item)) {
+            let words = item.split_whitespace().collect::<Vec<_>>();
+            *item = match line_wrap {
+                LineWrap::Preserve => continue,
+                LineWrap::Unwrap => words.join(" "),
+                LineWrap::Columns(columns) => wrap_words(&words, columns),
+            };
+        }
+    }
+}
+
+// re-flowing text that isn't a plain paragraph would change how the markdown renders
+fn has_block_content(item: &str) -> bool {
+    item.lines().skip(1).any(|line| {
+        let line = line.trim_start();
+        line.is_empty()
+            || line.starts_with(['-', '*', '+', '>', '#', '|'])
+            || line.starts_with("```")
+            || line.starts_with("~~~")
+            || line.split_once(['.', ')']).is_some_and(|(number, _)| {
+                !number.is_empty() && number.chars().all(|c| c.is_ascii_digit())
+            })
+    })
+}
+
+// the first line is prefixed with the "- " list marker and following lines are indented to match
+fn wrap_words(words: &[&str], columns: usize) -> String {
+    const INDENT: &str = "  ";
+    let mut lines: Vec<String> = vec![];
+    let mut line = String::new();
+    for word in words {
+        if !line.is_empty()
+            && INDENT.len() + line.chars().count() + 1 + word.chars().count() > columns
+            && !starts_block(word)
+        {
+            lines.push(line);
+            line = String::new();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    lines.push(line);
+    lines.join(&format!("\n{INDENT}"))
+}
+
+// a line starting with one of these words would be read as a heading, list, quote, code fence,
+// or setext underline instead of continuing the entry, so the line isn't broken before them
+fn starts_block(word: &str) -> bool {
+    let ordered_list_marker = word
+        .strip_suffix(['.', ')'])
+        .is_some_and(|number| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()));
+    word == "+"
+        || word.chars().all(|c| matches!(c, '-' | '=' | '*' | '_'))
+        || (word.len() <= 6 && word.chars().all(|c| c == '#'))
+        || word.starts_with(['>', '<'])
+        || word.starts_with("```")
+        || word.starts_with("~~~")
+        || ordered_list_marker
+}
+
+fn alphabetical(a: &str, b: &str) -> Ordering {
+    a.to_lowercase()
+        .cmp(&b.to_lowercase())
+        .then_with(|| a.cmp(b))
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::ChangeGroup;
+
+    const CHANGELOG: &str = "\
+# Changelog
+
+## [Unreleased]
+
+### Added
+
+- zebra support
+- (core) Streaming parser
+- Apple support
+- (cli) Added a --json flag
+";
+
+    #[test]
+    fn test_format_without_options() {
+        let changelog: Changelog = CHANGELOG.parse().unwrap();
+        assert_eq!(
+            changelog.format(&FormatOptions::new()),
+            changelog.to_string()
+        );
+    }
+
+    #[test]
+    fn test_sort_entries_alphabetically() {
+        let changelog: Changelog = CHANGELOG.parse().unwrap();
+        let formatted =
+            changelog.format(&FormatOptions::new().with_sort_entries(EntrySort::Alphabetical));
+        assert!(formatted.ends_with(
+            "\
+### Added
+
+- (cli) Added a --json flag
+- (core) Streaming parser
+- Apple support
+- zebra support
+"
+        ));
+    }
+
+    #[test]
+    fn test_line_wrap() {
+        let changelog: Changelog = "\
+# Changelog
+
+## [Unreleased]
+
+### Added
+
+- Added a flag that controls how the output is formatted when it is written
+  to a terminal
+- Added options:
+  - one
+  - two
+"
+        .parse()
+        .unwrap();
+
+        let formatted = changelog.format(&FormatOptions::new());
+        assert!(formatted.contains(
+            "- Added a flag that controls how the output is formatted when it is written\n  to a terminal\n"
+        ));
+
+        let formatted = changelog.format(&FormatOptions::new().with_line_wrap(LineWrap::Unwrap));
+        assert!(formatted.contains(
+            "- Added a flag that controls how the output is formatted when it is written to a terminal\n"
+        ));
+
+        let formatted =
+            changelog.format(&FormatOptions::new().with_line_wrap(LineWrap::Columns(40)));
+        assert!(formatted.ends_with(
+            "\
+- Added a flag that controls how the
+  output is formatted when it is written
+  to a terminal
+- Added options:
+  - one
+  - two
+"
+        ));
+    }
+
+    #[test]
+    fn test_line_wrap_keeps_block_markers_inline() {
+        let entry = "Fixed the parser so that a - in an entry is kept, and # is kept too, as are > and 1. and ``` and ---";
+        let mut changelog = Changelog::default();
+        changelog.unreleased.add(ChangeGroup::Fixed, entry);
+
+        for columns in 10..40 {
+            let formatted =
+                changelog.format(&FormatOptions::new().with_line_wrap(LineWrap::Columns(columns)));
+            let reparsed: Changelog = formatted.parse().unwrap();
+            let entries = reparsed.unreleased.changes.iter().collect::<Vec<_>>();
+            assert_eq!(entries.len(), 1, "{formatted}");
+            assert_eq!(entries[0].1.len(), 1, "{formatted}");
+            assert_eq!(
+                entries[0].1[0].split_whitespace().collect::<Vec<_>>(),
+                entry.split_whitespace().collect::<Vec<_>>()
+            );
+            assert!(!reparsed.to_string().contains("\n  # "), "{formatted}");
+        }
+    }
+
+    #[test]
+    fn test_sort_entries_by_scope() {
+        let changelog: Changelog = CHANGELOG.replace("(core)", "(api)").parse().unwrap();
+        let formatted = changelog.format(&FormatOptions::new().with_sort_entries(EntrySort::Scope));
+        assert!(formatted.ends_with(
+            "\
+### Added
+
+- (api) Streaming parser
+- (cli) Added a --json flag
+- Apple support
+- zebra support
+"
+        ));
+    }
+
+    #[test]
+    fn test_line_ending() {
+        let changelog: Changelog = CHANGELOG.parse().unwrap();
+        let formatted = changelog.format(&FormatOptions::new().with_line_ending(LineEnding::CrLf));
+        assert_eq!(formatted, changelog.to_string().replace('\n', "\r\n"));
+    }
+}
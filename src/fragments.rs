@@ -0,0 +1,423 @@
+use crate::{BumpVersionError, ChangeGroup, ReleaseVersion, Unreleased, VersionBump};
+use std::cmp::Ordering;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// A single change stored in its own file, also known as a news fragment. Keeping each change in a
+/// separate file (e.g., `changelog.d/1234.added.md`) avoids the merge conflicts that happen when
+/// every pull request edits the same Unreleased section.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Fragment {
+    /// The path of the fragment file.
+    pub path: PathBuf,
+    /// The identifier of the fragment, usually an issue or pull request number.
+    pub id: String,
+    /// The change group the fragment belongs to.
+    pub change_group: ChangeGroup,
+    /// The text of the change.
+    pub text: String,
+    /// The version increment requested by the fragment. Only changeset fragments carry this.
+    pub bump: Option<VersionBump>,
+}
+
+/// The set of news fragments found in a fragment directory.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct Fragments(Vec<Fragment>);
+
+/// Errors that can occur while reading or removing news fragments.
+#[derive(Debug, Error)]
+pub enum FragmentError {
+    /// The fragment directory or a fragment file could not be read or removed.
+    #[error("I/O error for fragment path {0}\nError: {1}")]
+    Io(PathBuf, #[source] std::io::Error),
+    /// The fragment file name did not match `<id>.<change group>[.md]`.
+    #[error("Could not determine the change group for fragment {0}\nExpected: <id>.<added | changed | deprecated | removed | fixed | security>[.md]")]
+    UnrecognizedFileName(PathBuf),
+    /// The fragment file contained no text.
+    #[error("Fragment {0} is empty")]
+    Empty(PathBuf),
+    /// The changeset file did not have valid frontmatter.
+    #[error("Invalid changeset {0}\nError: {1}")]
+    InvalidChangeset(PathBuf, String),
+}
+
+impl Fragments {
+    /// Reads all the fragments in `directory`. Fragment files are named `<id>.<change group>` with an
+    /// optional `.md` extension where the change group is one of the Keep a Changelog groups or one
+    /// of the towncrier types (`feature`, `bugfix`, `doc`, `removal`, `deprecation`, `misc`).
+    ///
+    /// Hidden files and `README` files are ignored. A missing directory has no fragments.
+    pub fn collect(directory: impl AsRef<Path>) -> Result<Fragments, FragmentError> {
+        let directory = directory.as_ref();
+        if !directory.exists() {
+            return Ok(Fragments::default());
+        }
+
+        let entries =
+            fs::read_dir(directory).map_err(|e| FragmentError::Io(directory.to_path_buf(), e))?;
+
+        let mut fragments = vec![];
+        for entry in entries {
+            let path = entry
+                .map_err(|e| FragmentError::Io(directory.to_path_buf(), e))?
+                .path();
+            if path.is_file() && !is_ignored(&path) {
+                fragments.push(read_fragment(path)?);
+            }
+        }
+        fragments.sort_by(|a, b| compare_ids(&a.id, &b.id).then_with(|| a.path.cmp(&b.path)));
+
+        Ok(Fragments(fragments))
+    }
+
+    /// Reads all the [changesets](https://github.com/changesets/changesets) in `directory` (usually
+    /// `.changeset`). Each changeset lists the packages it affects along with a `major`, `minor`, or
+    /// `patch` bump in its frontmatter followed by a summary of the change.
+    ///
+    /// When `package` is given, only changesets that affect that package are collected. Since
+    /// changesets don't have change groups, the bump type is used to pick one: `major` changes are
+    /// [`ChangeGroup::Changed`], `minor` changes are [`ChangeGroup::Added`], and `patch` changes
+    /// are [`ChangeGroup::Fixed`].
+    pub fn collect_changesets(
+        directory: impl AsRef<Path>,
+        package: Option<&str>,
+    ) -> Result<Fragments, FragmentError> {
+        let directory = directory.as_ref();
+        if !directory.exists() {
+            return Ok(Fragments::default());
+        }
+
+        let entries =
+            fs::read_dir(directory).map_err(|e| FragmentError::Io(directory.to_path_buf(), e))?;
+
+        let mut fragments = vec![];
+        for entry in entries {
+            let path = entry
+                .map_err(|e| FragmentError::Io(directory.to_path_buf(), e))?
+                .path();
+            let is_markdown = path.extension().is_some_and(|extension| extension == "md");
+            if path.is_file() && is_markdown && !is_ignored(&path) {
+                if let Some(fragment) = read_changeset(path, package)? {
+                    fragments.push(fragment);
+                }
+            }
+        }
+        fragments.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(Fragments(fragments))
+    }
+
+    /// Suggests the version that should follow `current` based on the largest bump requested by
+    /// the fragments. Returns `None` if no fragment requests a bump.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bumped part of `current` would overflow.
+    pub fn next_version(
+        &self,
+        current: &ReleaseVersion,
+    ) -> Result<Option<ReleaseVersion>, BumpVersionError> {
+        self.0
+            .iter()
+            .filter_map(|fragment| fragment.bump)
+            .max()
+            .map(|bump| current.bump(bump))
+            .transpose()
+    }
+
+    /// Returns true if there are no fragments.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the fragments.
+    pub fn iter(&self) -> std::slice::Iter<'_, Fragment> {
+        self.0.iter()
+    }
+
+    /// Adds every fragment to the given `unreleased` section under its change group.
+    pub fn add_to(&self, unreleased: &mut Unreleased) {
+        for fragment in &self.0 {
+            unreleased.add(fragment.change_group.clone(), fragment.text.clone());
+        }
+    }
+
+    /// Removes the fragment files from disk. This should be called once the fragments have been
+    /// consumed, typically right after the unreleased changes are promoted to a new release.
+    pub fn delete(self) -> Result<(), FragmentError> {
+        for fragment in self.0 {
+            fs::remove_file(&fragment.path).map_err(|e| FragmentError::Io(fragment.path, e))?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> IntoIterator for &'a Fragments {
+    type Item = &'a Fragment;
+    type IntoIter = std::slice::Iter<'a, Fragment>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+fn is_ignored(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.') || name.to_lowercase().starts_with("readme"))
+}
+
+fn read_fragment(path: PathBuf) -> Result<Fragment, FragmentError> {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    let file_name = file_name.strip_suffix(".md").unwrap_or(file_name);
+
+    // towncrier allows a counter after the type (e.g., `123.added.1`) for multiple fragments that
+    // share an id so the first segment that names a change group is used
+    let mut segments = file_name.split('.');
+    let id = segments.next().unwrap_or_default().to_string();
+    let change_group = segments
+        .find_map(parse_fragment_type)
+        .filter(|_| !id.is_empty())
+        .ok_or_else(|| FragmentError::UnrecognizedFileName(path.clone()))?;
+
+    let contents = fs::read_to_string(&path).map_err(|e| FragmentError::Io(path.clone(), e))?;
+    let contents = contents.trim();
+    // a fragment written as a single list item is unwrapped since it will be rendered as one
+    let text = contents
+        .strip_prefix("- ")
+        .or_else(|| contents.strip_prefix("* "))
+        .unwrap_or(contents)
+        .trim()
+        .lines()
+        .collect::<Vec<_>>()
+        .join("\n  ");
+    if text.is_empty() {
+        return Err(FragmentError::Empty(path));
+    }
+
+    Ok(Fragment {
+        path,
+        id,
+        change_group,
+        text,
+        bump: None,
+    })
+}
+
+fn read_changeset(path: PathBuf, package: Option<&str>) -> Result<Option<Fragment>, FragmentError> {
+    let contents = fs::read_to_string(&path).map_err(|e| FragmentError::Io(path.clone(), e))?;
+
+    let (frontmatter, summary) = contents
+        .trim_start()
+        .strip_prefix("---")
+        .and_then(|rest| rest.split_once("\n---"))
+        .ok_or_else(|| {
+            FragmentError::InvalidChangeset(path.clone(), "missing frontmatter".to_string())
+        })?;
+
+    let mut bump = None;
+    for line in frontmatter.lines().filter(|line| !line.trim().is_empty()) {
+        let (name, value) = line.rsplit_once(':').ok_or_else(|| {
+            FragmentError::InvalidChangeset(path.clone(), format!("invalid frontmatter '{line}'"))
+        })?;
+        let name = name.trim().trim_matches(['"', '\'']);
+        let value = value
+            .trim()
+            .parse::<VersionBump>()
+            .map_err(|e| FragmentError::InvalidChangeset(path.clone(), e.to_string()))?;
+        if package.map_or(true, |package| package == name) {
+            bump = bump.max(Some(value));
+        }
+    }
+
+    // a changeset without any (matching) packages doesn't contribute a change
+    let Some(bump) = bump else {
+        return Ok(None);
+    };
+
+    let text = summary
+        .trim_start_matches('-')
+        .trim()
+        .lines()
+        .collect::<Vec<_>>()
+        .join("\n  ");
+    if text.is_empty() {
+        return Err(FragmentError::Empty(path));
+    }
+
+    let id = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let change_group = match bump {
+        VersionBump::Major => ChangeGroup::Changed,
+        VersionBump::Minor => ChangeGroup::Added,
+        VersionBump::Patch => ChangeGroup::Fixed,
+    };
+
+    Ok(Some(Fragment {
+        path,
+        id,
+        change_group,
+        text,
+        bump: Some(bump),
+    }))
+}
+
+fn parse_fragment_type(value: &str) -> Option<ChangeGroup> {
+    match value.to_lowercase().as_str() {
+        "feature" => Some(ChangeGroup::Added),
+        "bugfix" => Some(ChangeGroup::Fixed),
+        "doc" | "misc" => Some(ChangeGroup::Changed),
+        "removal" => Some(ChangeGroup::Removed),
+        "deprecation" => Some(ChangeGroup::Deprecated),
+        other => other.parse().ok(),
+    }
+}
+
+// numeric ids (issue numbers) sort numerically so that `9` comes before `10`
+fn compare_ids(a: &str, b: &str) -> Ordering {
+    match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::Changes;
+    use tempfile::TempDir;
+
+    // removed when dropped, even if the test panics
+    fn fragment_dir(name: &str, files: &[(&str, &str)]) -> TempDir {
+        let dir = tempfile::Builder::new()
+            .prefix(&format!("keep_a_changelog_file-{name}-"))
+            .tempdir()
+            .unwrap();
+        for (file_name, contents) in files {
+            fs::write(dir.path().join(file_name), contents).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_collect_fragments() {
+        let dir = fragment_dir(
+            "collect",
+            &[
+                ("10.fixed.md", "Fixed feature X\n"),
+                ("9.feature.md", "- Added feature Y"),
+                ("9.bugfix.1.md", "Fixed feature Z\nacross multiple lines"),
+                ("README.md", "Add a fragment per change"),
+                (".gitignore", "!.gitignore"),
+            ],
+        );
+
+        let fragments = Fragments::collect(&dir).unwrap();
+        let mut unreleased = Unreleased::default();
+        fragments.add_to(&mut unreleased);
+
+        assert_eq!(
+            unreleased.changes,
+            Changes::from_iter([
+                (ChangeGroup::Added, vec!["Added feature Y".to_string()]),
+                (
+                    ChangeGroup::Fixed,
+                    vec![
+                        "Fixed feature Z\n  across multiple lines".to_string(),
+                        "Fixed feature X".to_string()
+                    ]
+                ),
+            ])
+        );
+
+        fragments.delete().unwrap();
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_unrecognized_fragment() {
+        let dir = fragment_dir("unrecognized", &[("10.improvement.md", "Something")]);
+        assert!(matches!(
+            Fragments::collect(&dir),
+            Err(FragmentError::UnrecognizedFileName(_))
+        ));
+    }
+
+    #[test]
+    fn test_empty_fragment() {
+        let dir = fragment_dir("empty", &[("10.added.md", "\n")]);
+        assert!(matches!(
+            Fragments::collect(&dir),
+            Err(FragmentError::Empty(_))
+        ));
+    }
+
+    #[test]
+    fn test_collect_changesets() {
+        let dir = fragment_dir(
+            "changesets",
+            &[
+                (
+                    "funny-dogs-dance.md",
+                    "---\n\"my-crate\": minor\n\"other-package\": major\n---\n\nAdded feature Y\n",
+                ),
+                (
+                    "quiet-cats-sleep.md",
+                    "---\nmy-crate: patch\n---\n\nFixed feature X\n",
+                ),
+                (
+                    "brave-owls-fly.md",
+                    "---\nother-package: major\n---\n\nRemoved Z\n",
+                ),
+                ("config.json", "{}"),
+                ("README.md", "Changesets docs"),
+            ],
+        );
+
+        let fragments = Fragments::collect_changesets(&dir, Some("my-crate")).unwrap();
+        let mut unreleased = Unreleased::default();
+        fragments.add_to(&mut unreleased);
+
+        assert_eq!(
+            unreleased.changes,
+            Changes::from_iter([
+                (ChangeGroup::Added, vec!["Added feature Y".to_string()]),
+                (ChangeGroup::Fixed, vec!["Fixed feature X".to_string()]),
+            ])
+        );
+        assert_eq!(
+            fragments.next_version(&"1.2.3".parse().unwrap()).unwrap(),
+            Some("1.3.0".parse().unwrap())
+        );
+
+        let fragments = Fragments::collect_changesets(&dir, None).unwrap();
+        assert_eq!(
+            fragments.next_version(&"1.2.3".parse().unwrap()).unwrap(),
+            Some("2.0.0".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_invalid_changeset() {
+        let dir = fragment_dir("invalid-changeset", &[("bad.md", "no frontmatter")]);
+        assert!(matches!(
+            Fragments::collect_changesets(&dir, None),
+            Err(FragmentError::InvalidChangeset(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_missing_fragment_directory() {
+        assert!(Fragments::collect("does/not/exist").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,317 @@
+use crate::{Changelog, Release, ReleaseVersion};
+use serde::Deserialize;
+use std::fmt::{Debug, Formatter};
+use thiserror::Error;
+
+const GITHUB_API_URL: &str = "https://api.github.com";
+const PER_PAGE: usize = 100;
+
+/// A client for synchronizing changelog releases with [GitHub Releases](https://docs.github.com/en/repositories/releasing-projects-on-github/about-releases).
+pub struct GitHubReleases {
+    owner: String,
+    repo: String,
+    token: String,
+    api_url: String,
+    tag_prefix: String,
+    agent: ureq::Agent,
+}
+
+/// A release as returned by the GitHub Releases API.
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+pub struct GitHubRelease {
+    /// The GitHub identifier for the release.
+    pub id: u64,
+    /// The name of the tag the release was created from.
+    pub tag_name: String,
+    /// The title of the release.
+    pub name: Option<String>,
+    /// The release notes.
+    pub body: Option<String>,
+}
+
+/// A difference between the releases in the changelog and the releases on GitHub.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum ReleaseDrift {
+    /// The release is in the changelog but there is no GitHub Release for its tag.
+    MissingOnGitHub(ReleaseVersion),
+    /// There is a GitHub Release for the tag but no matching release in the changelog.
+    MissingInChangelog(String),
+    /// The GitHub Release notes don't match the changes listed in the changelog.
+    NotesDiffer(ReleaseVersion),
+}
+
+/// Errors that can occur when talking to the GitHub Releases API.
+#[derive(Debug, Error)]
+pub enum GitHubError {
+    /// The repository is not in `<owner>/<repo>` format.
+    #[error("Invalid GitHub repository '{0}'\nExpected: <owner>/<repo>")]
+    InvalidRepository(String),
+    /// The request to the GitHub API failed.
+    #[error("GitHub API request to {0} failed\nError: {1}")]
+    Request(String, String),
+    /// The response from the GitHub API could not be read.
+    #[error("Could not read GitHub API response from {0}\nError: {1}")]
+    Response(String, String),
+}
+
+// written by hand to keep the token out of debug output
+impl Debug for GitHubReleases {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GitHubReleases")
+            .field("owner", &self.owner)
+            .field("repo", &self.repo)
+            .field("api_url", &self.api_url)
+            .field("tag_prefix", &self.tag_prefix)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GitHubReleases {
+    /// Construct a new [`GitHubReleases`] client for the given `repository` (in `<owner>/<repo>`
+    /// format) that authenticates with `token`.
+    pub fn new(repository: &str, token: impl Into<String>) -> Result<Self, GitHubError> {
+        let (owner, repo) = repository
+            .split_once('/')
+            .filter(|(owner, repo)| !owner.is_empty() && !repo.is_empty() && !repo.contains('/'))
+            .ok_or_else(|| GitHubError::InvalidRepository(repository.to_string()))?;
+        Ok(Self {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            token: token.into(),
+            api_url: GITHUB_API_URL.to_string(),
+            tag_prefix: "v".to_string(),
+            agent: ureq::Agent::new_with_defaults(),
+        })
+    }
+
+    /// Set the base URL of the GitHub API. Defaults to `https://api.github.com` but can be changed
+    /// for GitHub Enterprise Server instances.
+    #[must_use]
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Set the prefix used to turn a version into a tag name. Defaults to `v`.
+    #[must_use]
+    pub fn with_tag_prefix(mut self, tag_prefix: impl Into<String>) -> Self {
+        self.tag_prefix = tag_prefix.into();
+        self
+    }
+
+    /// Creates or updates the GitHub Release for the given `release` so that its notes match the
+    /// changes listed in the changelog.
+    pub fn push_release(&self, release: &Release) -> Result<GitHubRelease, GitHubError> {
+        let tag_name = format!("{}{}", self.tag_prefix, release.version);
+        let body = release.changes.to_string();
+
+        let existing = self
+            .pull_releases()?
+            .into_iter()
+            .find(|github_release| github_release.tag_name == tag_name);
+
+        let (url, request) = if let Some(github_release) = existing {
+            let url = self.releases_url(Some(github_release.id));
+            let request = self.authorize(self.agent.patch(&url));
+            (url, request)
+        } else {
+            let url = self.releases_url(None);
+            let request = self.authorize(self.agent.post(&url));
+            (url, request)
+        };
+
+        let payload = serde_json::json!({
+            "tag_name": tag_name,
+            "name": tag_name,
+            "body": body,
+        });
+
+        let mut response = request
+            .header("Content-Type", "application/json")
+            .send(payload.to_string())
+            .map_err(|e| GitHubError::Request(url.clone(), e.to_string()))?;
+
+        let response_body = response
+            .body_mut()
+            .read_to_string()
+            .map_err(|e| GitHubError::Response(url.clone(), e.to_string()))?;
+
+        serde_json::from_str(&response_body).map_err(|e| GitHubError::Response(url, e.to_string()))
+    }
+
+    /// Returns all the releases for the repository from GitHub.
+    pub fn pull_releases(&self) -> Result<Vec<GitHubRelease>, GitHubError> {
+        let mut releases = vec![];
+        let mut page = 1;
+        loop {
+            let url = format!(
+                "{}?per_page={PER_PAGE}&page={page}",
+                self.releases_url(None)
+            );
+
+            let mut response = self
+                .authorize(self.agent.get(&url))
+                .call()
+                .map_err(|e| GitHubError::Request(url.clone(), e.to_string()))?;
+
+            let response_body = response
+                .body_mut()
+                .read_to_string()
+                .map_err(|e| GitHubError::Response(url.clone(), e.to_string()))?;
+
+            let page_of_releases: Vec<GitHubRelease> = serde_json::from_str(&response_body)
+                .map_err(|e| GitHubError::Response(url, e.to_string()))?;
+
+            let is_last_page = page_of_releases.len() < PER_PAGE;
+            releases.extend(page_of_releases);
+            if is_last_page {
+                return Ok(releases);
+            }
+            page += 1;
+        }
+    }
+
+    /// Compares the releases in the `changelog` with the releases on GitHub and reports any
+    /// differences.
+    pub fn detect_drift(&self, changelog: &Changelog) -> Result<Vec<ReleaseDrift>, GitHubError> {
+        Ok(detect_drift(
+            changelog,
+            &self.pull_releases()?,
+            &self.tag_prefix,
+        ))
+    }
+
+    fn releases_url(&self, id: Option<u64>) -> String {
+        let url = format!(
+            "{}/repos/{}/{}/releases",
+            self.api_url, self.owner, self.repo
+        );
+        match id {
+            Some(id) => format!("{url}/{id}"),
+            None => url,
+        }
+    }
+
+    fn authorize<B>(&self, request: ureq::RequestBuilder<B>) -> ureq::RequestBuilder<B> {
+        request
+            .header("Accept", "application/vnd.github+json")
+            .header("Authorization", &format!("Bearer {}", self.token))
+            .header("User-Agent", env!("CARGO_PKG_NAME"))
+            .header("X-GitHub-Api-Version", "2022-11-28")
+    }
+}
+
+fn detect_drift(
+    changelog: &Changelog,
+    github_releases: &[GitHubRelease],
+    tag_prefix: &str,
+) -> Vec<ReleaseDrift> {
+    let mut drift = vec![];
+
+    for (version, release) in &changelog.releases {
+        let tag_name = format!("{tag_prefix}{version}");
+        match github_releases
+            .iter()
+            .find(|github_release| github_release.tag_name == tag_name)
+        {
+            Some(github_release) => {
+                let github_notes = github_release.body.as_deref().unwrap_or_default();
+                if normalize_notes(github_notes) != normalize_notes(&release.changes.to_string()) {
+                    drift.push(ReleaseDrift::NotesDiffer(version.clone()));
+                }
+            }
+            None => drift.push(ReleaseDrift::MissingOnGitHub(version.clone())),
+        }
+    }
+
+    for github_release in github_releases {
+        let in_changelog = github_release
+            .tag_name
+            .strip_prefix(tag_prefix)
+            .and_then(|version| version.parse::<ReleaseVersion>().ok())
+            .is_some_and(|version| changelog.releases.contains_version(&version));
+        if !in_changelog {
+            drift.push(ReleaseDrift::MissingInChangelog(
+                github_release.tag_name.clone(),
+            ));
+        }
+    }
+
+    drift
+}
+
+// GitHub stores release notes with CRLF line endings when they are edited through the web UI
+fn normalize_notes(notes: &str) -> String {
+    notes.replace("\r\n", "\n").trim().to_string()
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn github_release(tag_name: &str, body: &str) -> GitHubRelease {
+        GitHubRelease {
+            id: 1,
+            tag_name: tag_name.to_string(),
+            name: Some(tag_name.to_string()),
+            body: Some(body.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_invalid_repository() {
+        assert!(matches!(
+            GitHubReleases::new("not-a-repository", "token"),
+            Err(GitHubError::InvalidRepository(_))
+        ));
+        assert!(matches!(
+            GitHubReleases::new("owner/repo/extra", "token"),
+            Err(GitHubError::InvalidRepository(_))
+        ));
+    }
+
+    #[test]
+    fn test_detect_drift() {
+        let changelog: Changelog = "\
+# Changelog
+
+## [Unreleased]
+
+## [1.1.0] - 2023-02-01
+
+### Fixed
+
+- Fixed feature Y
+
+## [1.0.0] - 2023-01-01
+
+### Added
+
+- Added feature X
+
+## [0.9.0] - 2022-12-01
+
+### Added
+
+- Added feature W
+"
+        .parse()
+        .unwrap();
+
+        let github_releases = [
+            github_release("v1.1.0", "### Fixed\r\n\r\n- Fixed feature Y\r\n"),
+            github_release("v1.0.0", "Some other notes"),
+            github_release("v0.1.0", "### Added\n\n- Initial release"),
+        ];
+
+        assert_eq!(
+            detect_drift(&changelog, &github_releases, "v"),
+            vec![
+                ReleaseDrift::NotesDiffer("1.0.0".parse().unwrap()),
+                ReleaseDrift::MissingOnGitHub("0.9.0".parse().unwrap()),
+                ReleaseDrift::MissingInChangelog("v0.1.0".to_string()),
+            ]
+        );
+    }
+}
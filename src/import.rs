@@ -0,0 +1,561 @@
+use crate::changes::Changes;
+use crate::releases::Releases;
+use crate::{
+    ChangeGroup, Changelog, LineEnding, ParseReleaseDateError, ParseVersionError, Release,
+    ReleaseDate, ReleaseVersion, Unreleased,
+};
+use indexmap::IndexMap;
+use lazy_static::lazy_static;
+use markdown::mdast::Node;
+use markdown::{to_mdast, ParseOptions};
+use regex::Regex;
+use thiserror::Error;
+
+/// An error that occurred while importing a changelog written in another format.
+#[derive(Debug, Error)]
+pub enum ImportChangelogError {
+    /// The input could not be parsed as markdown.
+    #[error("Could not parse changelog as markdown\nError: {0}")]
+    Markdown(String),
+    /// A release heading contained a version that is not valid semver.
+    #[error("Invalid version in release heading - {0}\nError: {1}")]
+    InvalidVersion(String, #[source] ParseVersionError),
+    /// A release heading contained an invalid date.
+    #[error("Invalid date in release heading - {0}\nError: {1}")]
+    InvalidReleaseDate(String, #[source] ParseReleaseDateError),
+    /// A release heading did not contain a date.
+    #[error("Release heading is missing a date - {0}")]
+    MissingReleaseDate(String),
+}
+
+lazy_static! {
+    // matches headings like:
+    // - conventional-changelog: `[1.2.0](https://github.com/org/repo/compare/v1.1.0...v1.2.0) (2024-01-05)`
+    // - git-cliff: `[1.2.0] - 2024-01-05`
+    static ref RELEASE_HEADING: Regex = Regex::new(
+        r"^(?:<a name=[^>]*></a>\s*)?\[?v?(?P<version>\d+\.\d+\.\d+[0-9A-Za-z.+-]*)]?(?:\((?P<link>[^)\s]+)\))?(?:\s*[-\x{2013}\x{2014}]?\s*\(?(?P<release_date>\d{4}-\d{2}-\d{2})\)?)?"
+    )
+    .expect("Should be a valid regex");
+    // an entry in GitHub's generated notes: `<title> by @<author> in <pull request url>`
+    static ref GITHUB_NOTES_ENTRY: Regex = Regex::new(
+        r"^(?P<title>.+?)\s+by\s+@\S+\s+in\s+(?P<url>https?://\S+/pull/(?P<number>\d+))$"
+    )
+    .expect("Should be a valid regex");
+    static ref GITHUB_FULL_CHANGELOG: Regex =
+        Regex::new(r"\*\*Full Changelog\*\*:\s*(?P<url>\S+)").expect("Should be a valid regex");
+    static ref UNRELEASED_HEADING: Regex =
+        Regex::new(r"(?i)^\[?unreleased]?").expect("Should be a valid regex");
+    // commit hash references such as ` ([abc1234](https://github.com/org/repo/commit/abc1234...))`
+    // or ` (abc1234)`
+    // a conventional commit subject: `<type>[(<scope>)][!]: <description>`
+    static ref CONVENTIONAL_COMMIT: Regex = Regex::new(
+        r"^(?P<type>[A-Za-z]+)(?:\((?P<scope>[^)]+)\))?!?:\s*(?P<description>\S.*)$"
+    )
+    .expect("Should be a valid regex");
+    static ref COMMIT_HASH: Regex = Regex::new(
+        r"\s*(?:\(\[`?[0-9a-f]{7,40}`?]\([^)\s]*\)\)|\[`?[0-9a-f]{7,40}`?]\([^)\s]*\)|\(`?[0-9a-f]{7,40}`?\))"
+    )
+    .expect("Should be a valid regex");
+}
+
+pub(crate) fn import_conventional_changelog(
+    input: &str,
+) -> Result<Changelog, ImportChangelogError> {
+    let ast = to_mdast(input, &ParseOptions::default())
+        .map_err(|e| ImportChangelogError::Markdown(e.to_string()))?;
+
+    let mut unreleased = Unreleased::default();
+    let mut releases: IndexMap<ReleaseVersion, Release> = IndexMap::new();
+    let mut current_section: Option<Option<ReleaseVersion>> = None;
+    let mut current_group = ChangeGroup::Changed;
+
+    let Node::Root(root) = ast else {
+        return Ok(Changelog::default());
+    };
+
+    for node in root.children {
+        match &node {
+            Node::Heading(_) => {
+                let heading = source_text(input, &node).trim_start_matches('#').trim();
+                if UNRELEASED_HEADING.is_match(heading) {
+                    current_section = Some(None);
+                    current_group = ChangeGroup::Changed;
+                } else if let Some(release) = parse_release_heading(heading)? {
+                    current_section = Some(Some(release.version.clone()));
+                    current_group = ChangeGroup::Changed;
+                    releases.insert(release.version.clone(), release);
+                } else if current_section.is_some() {
+                    current_group = map_change_group(heading);
+                }
+            }
+            Node::List(list) => {
+                let changes = match &current_section {
+                    Some(None) => &mut unreleased.changes,
+                    Some(Some(version)) => match releases.get_mut(version) {
+                        Some(release) => &mut release.changes,
+                        None => continue,
+                    },
+                    None => continue,
+                };
+                for list_item in &list.children {
+                    let text = strip_list_marker(source_text(input, list_item)).trim_end();
+                    let text = COMMIT_HASH.replace_all(text, "");
+                    changes.add(current_group.clone(), text.trim());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(Changelog {
+        unreleased,
+        releases: Releases::from_iter(releases),
+        link_separator: None,
+        line_ending: LineEnding::detect(input),
+        missing_unreleased_section: false,
+    })
+}
+
+pub(crate) fn import_github_release_notes(
+    version: ReleaseVersion,
+    date: ReleaseDate,
+    notes: &str,
+) -> Result<Release, ImportChangelogError> {
+    let ast = to_mdast(notes, &ParseOptions::default())
+        .map_err(|e| ImportChangelogError::Markdown(e.to_string()))?;
+
+    let mut changes = Changes::default();
+    let mut in_changes_section = false;
+    let mut heading_group = None;
+
+    if let Node::Root(root) = ast {
+        for node in root.children {
+            match &node {
+                Node::Heading(heading) => {
+                    let text = node.to_string();
+                    if heading.depth <= 2 {
+                        in_changes_section = text.to_lowercase().starts_with("what");
+                        heading_group = None;
+                    } else {
+                        // release.yml categories appear as sub headings of "What's Changed"
+                        in_changes_section = true;
+                        heading_group = classify_github_category(&text);
+                    }
+                }
+                Node::List(list) if in_changes_section => {
+                    for list_item in &list.children {
+                        let text = strip_list_marker(source_text(notes, list_item)).trim_end();
+                        let (entry, change_group) = match GITHUB_NOTES_ENTRY.captures(text) {
+                            Some(captures) => (
+                                format!(
+                                    "{} ([#{}]({}))",
+                                    &captures["title"], &captures["number"], &captures["url"]
+                                ),
+                                classify_entry(&captures["title"]),
+                            ),
+                            None => (text.to_string(), classify_entry(text)),
+                        };
+                        changes.add(heading_group.clone().unwrap_or(change_group), entry);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let link = GITHUB_FULL_CHANGELOG
+        .captures(notes)
+        .and_then(|captures| captures["url"].parse().ok());
+
+    Ok(Release {
+        version,
+        date,
+        tag: None,
+        link,
+        changes,
+    })
+}
+
+// commit types that don't change anything a user of the project would notice
+const UNNOTABLE_COMMIT_TYPES: [&str; 6] = ["build", "chore", "ci", "docs", "style", "test"];
+
+pub(crate) fn import_conventional_commits<'a>(
+    subjects: impl IntoIterator<Item = &'a str>,
+) -> Changes {
+    let mut changes = Changes::default();
+    for subject in subjects {
+        let Some(captures) = CONVENTIONAL_COMMIT.captures(subject.trim()) else {
+            continue;
+        };
+        let commit_type = captures["type"].to_lowercase();
+        if UNNOTABLE_COMMIT_TYPES.contains(&commit_type.as_str()) {
+            continue;
+        }
+        let mut description = captures["description"].chars();
+        let description = description
+            .next()
+            .map(|first| first.to_uppercase().chain(description).collect::<String>())
+            .unwrap_or_default();
+        let entry = match captures.name("scope") {
+            Some(scope) => format!("({}) {description}", scope.as_str()),
+            None => description,
+        };
+        changes.add(map_change_group(&commit_type), entry);
+    }
+    changes
+}
+
+fn classify_github_category(heading: &str) -> Option<ChangeGroup> {
+    let heading = heading.to_lowercase();
+    if heading.contains("feature") || heading.contains("added") {
+        Some(ChangeGroup::Added)
+    } else if heading.contains("fix") || heading.contains("bug") {
+        Some(ChangeGroup::Fixed)
+    } else if heading.contains("deprecat") {
+        Some(ChangeGroup::Deprecated)
+    } else if heading.contains("remov") {
+        Some(ChangeGroup::Removed)
+    } else if heading.contains("secur") {
+        Some(ChangeGroup::Security)
+    } else {
+        None
+    }
+}
+
+// a best-effort guess at the change group using conventional commit prefixes or the leading verb
+fn classify_entry(title: &str) -> ChangeGroup {
+    let title = title.trim().to_lowercase();
+    if title.starts_with("feat") || title.starts_with("add") {
+        ChangeGroup::Added
+    } else if title.starts_with("fix") || title.starts_with("bug") {
+        ChangeGroup::Fixed
+    } else if title.starts_with("deprecate") {
+        ChangeGroup::Deprecated
+    } else if title.starts_with("remove") || title.starts_with("drop") {
+        ChangeGroup::Removed
+    } else if title.starts_with("security") || title.contains("cve-") {
+        ChangeGroup::Security
+    } else {
+        ChangeGroup::Changed
+    }
+}
+
+fn parse_release_heading(heading: &str) -> Result<Option<Release>, ImportChangelogError> {
+    let Some(captures) = RELEASE_HEADING.captures(heading) else {
+        return Ok(None);
+    };
+
+    let version = captures["version"]
+        .parse::<ReleaseVersion>()
+        .map_err(|e| ImportChangelogError::InvalidVersion(heading.to_string(), e))?;
+
+    let date = captures
+        .name("release_date")
+        .ok_or_else(|| ImportChangelogError::MissingReleaseDate(heading.to_string()))?
+        .as_str()
+        .parse::<ReleaseDate>()
+        .map_err(|e| ImportChangelogError::InvalidReleaseDate(heading.to_string(), e))?;
+
+    let link = captures
+        .name("link")
+        .and_then(|link| link.as_str().parse().ok());
+
+    Ok(Some(Release {
+        version,
+        date,
+        tag: None,
+        link,
+        changes: Changes::default(),
+    }))
+}
+
+// conventional-changelog and git-cliff group commits by type which need to be mapped onto the
+// fixed set of Keep a Changelog change groups
+fn map_change_group(heading: &str) -> ChangeGroup {
+    let heading = heading
+        .trim_start_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase();
+
+    if let Ok(change_group) = heading.parse::<ChangeGroup>() {
+        return change_group;
+    }
+
+    if heading.starts_with("feat") {
+        ChangeGroup::Added
+    } else if heading.starts_with("bug") || heading.starts_with("fix") {
+        ChangeGroup::Fixed
+    } else if heading.starts_with("deprecat") {
+        ChangeGroup::Deprecated
+    } else if heading.starts_with("remov") {
+        ChangeGroup::Removed
+    } else if heading.starts_with("secur") {
+        ChangeGroup::Security
+    } else {
+        ChangeGroup::Changed
+    }
+}
+
+fn strip_list_marker(text: &str) -> &str {
+    text.strip_prefix(['-', '*', '+'])
+        .unwrap_or(text)
+        .trim_start()
+}
+
+fn source_text<'a>(input: &'a str, node: &Node) -> &'a str {
+    node.position().map_or("", |position| {
+        &input[position.start.offset..position.end.offset]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_import_conventional_changelog() {
+        let changelog = import_conventional_changelog(
+            "\
+# Changelog
+
+All notable changes to this project will be documented in this file. See [standard-version](https://github.com/conventional-changelog/standard-version) for commit guidelines.
+
+## [1.2.0](https://github.com/org/repo/compare/v1.1.0...v1.2.0) (2024-01-05)
+
+### Features
+
+* **cli:** add a json flag ([#12](https://github.com/org/repo/issues/12)) ([abc1234](https://github.com/org/repo/commit/abc1234def5678))
+
+### Bug Fixes
+
+* handle empty input ([def5678](https://github.com/org/repo/commit/def5678abc1234))
+
+### Performance Improvements
+
+* cache parsed headings (0123abc)
+
+### [1.1.0](https://github.com/org/repo/compare/v1.0.0...v1.1.0) (2023-12-01)
+
+### BREAKING CHANGES
+
+* drop support for node 12
+",
+        )
+        .unwrap();
+
+        let release = changelog
+            .releases
+            .get_version(&"1.2.0".parse().unwrap())
+            .unwrap();
+        assert_eq!(release.date, "2024-01-05".parse().unwrap());
+        assert_eq!(
+            release.link,
+            Some(
+                "https://github.com/org/repo/compare/v1.1.0...v1.2.0"
+                    .parse()
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            release.changes,
+            Changes::from_iter([
+                (
+                    ChangeGroup::Added,
+                    vec![
+                        "**cli:** add a json flag ([#12](https://github.com/org/repo/issues/12))"
+                            .to_string()
+                    ]
+                ),
+                (ChangeGroup::Fixed, vec!["handle empty input".to_string()]),
+                (
+                    ChangeGroup::Changed,
+                    vec!["cache parsed headings".to_string()]
+                ),
+            ])
+        );
+
+        let release = changelog
+            .releases
+            .get_version(&"1.1.0".parse().unwrap())
+            .unwrap();
+        assert_eq!(
+            release.changes,
+            Changes::from_iter([(
+                ChangeGroup::Changed,
+                vec!["drop support for node 12".to_string()]
+            )])
+        );
+    }
+
+    #[test]
+    fn test_import_git_cliff() {
+        let changelog = import_conventional_changelog(
+            "\
+# Changelog
+
+All notable changes to this project will be documented in this file.
+
+## [unreleased]
+
+### 🚀 Features
+
+- *(parser)* Support nested lists
+
+## [0.1.0] - 2024-01-01
+
+### 🐛 Bug Fixes
+
+- Fix panic on empty file
+
+### 🚜 Refactor
+
+- Split parser module
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            changelog.unreleased.changes,
+            Changes::from_iter([(
+                ChangeGroup::Added,
+                vec!["*(parser)* Support nested lists".to_string()]
+            )])
+        );
+        assert_eq!(
+            changelog
+                .releases
+                .get_version(&"0.1.0".parse().unwrap())
+                .unwrap()
+                .changes,
+            Changes::from_iter([
+                (
+                    ChangeGroup::Fixed,
+                    vec!["Fix panic on empty file".to_string()]
+                ),
+                (
+                    ChangeGroup::Changed,
+                    vec!["Split parser module".to_string()]
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_import_github_release_notes() {
+        let release = import_github_release_notes(
+            "1.1.0".parse().unwrap(),
+            "2024-02-01".parse().unwrap(),
+            "\
+## What's Changed
+* Add a json flag by @octocat in https://github.com/org/repo/pull/12
+* fix: crash on empty input by @hubot in https://github.com/org/repo/pull/13
+* Bump serde from 1.0.1 to 1.0.2 by @dependabot in https://github.com/org/repo/pull/14
+
+## New Contributors
+* @hubot made their first contribution in https://github.com/org/repo/pull/13
+
+**Full Changelog**: https://github.com/org/repo/compare/v1.0.0...v1.1.0
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            release.link,
+            Some(
+                "https://github.com/org/repo/compare/v1.0.0...v1.1.0"
+                    .parse()
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            release.changes,
+            Changes::from_iter([
+                (
+                    ChangeGroup::Added,
+                    vec!["Add a json flag ([#12](https://github.com/org/repo/pull/12))".to_string()]
+                ),
+                (
+                    ChangeGroup::Fixed,
+                    vec![
+                        "fix: crash on empty input ([#13](https://github.com/org/repo/pull/13))"
+                            .to_string()
+                    ]
+                ),
+                (
+                    ChangeGroup::Changed,
+                    vec![
+                        "Bump serde from 1.0.1 to 1.0.2 ([#14](https://github.com/org/repo/pull/14))"
+                            .to_string()
+                    ]
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_import_github_release_notes_with_categories() {
+        let release = import_github_release_notes(
+            "2.0.0".parse().unwrap(),
+            "2024-02-01".parse().unwrap(),
+            "\
+## What's Changed
+### Breaking Changes 🛠
+* Rename the config file by @octocat in https://github.com/org/repo/pull/20
+### Exciting New Features 🎉
+* Support YAML by @octocat in https://github.com/org/repo/pull/21
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            release.changes,
+            Changes::from_iter([
+                (
+                    ChangeGroup::Changed,
+                    vec![
+                        "Rename the config file ([#20](https://github.com/org/repo/pull/20))"
+                            .to_string()
+                    ]
+                ),
+                (
+                    ChangeGroup::Added,
+                    vec!["Support YAML ([#21](https://github.com/org/repo/pull/21))".to_string()]
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_import_conventional_commits() {
+        let changes = import_conventional_commits([
+            "feat(cli): add a json flag",
+            "fix!: crash on empty input",
+            "chore: bump serde",
+            "Merge branch 'main'",
+            "perf: cache parsed headings",
+        ]);
+
+        assert_eq!(
+            changes,
+            Changes::from_iter([
+                (
+                    ChangeGroup::Added,
+                    vec!["(cli) Add a json flag".to_string()]
+                ),
+                (ChangeGroup::Fixed, vec!["Crash on empty input".to_string()]),
+                (
+                    ChangeGroup::Changed,
+                    vec!["Cache parsed headings".to_string()]
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_import_release_without_date() {
+        assert!(matches!(
+            import_conventional_changelog("## [1.0.0](https://example.com)\n\n* change"),
+            Err(ImportChangelogError::MissingReleaseDate(_))
+        ));
+    }
+}
@@ -1,32 +1,125 @@
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "capi")]
+pub mod capi;
 mod change_group;
 mod changelog;
 mod changes;
+mod compliance;
+mod diagnostic;
+#[cfg(feature = "json")]
+mod diagnostics_report;
+mod format;
+mod fragments;
+#[cfg(feature = "github")]
+mod github;
+mod import;
+mod line_ending;
+#[cfg(feature = "link-check")]
+mod link_check;
+mod link_template;
+mod merge;
 mod release;
 mod release_date;
 mod release_link;
 mod release_tag;
 mod release_version;
 mod releases;
+mod rst;
+mod section;
+mod statistics;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 mod unreleased;
+#[cfg(feature = "workspace")]
+mod workspace;
+
+// clap, glob, and tera are only used by the command line binary
+#[cfg(feature = "cli")]
+use clap as _;
+#[cfg(test)]
+use criterion as _;
+#[cfg(feature = "cli")]
+use glob as _;
+#[cfg(test)]
+use tempfile as _;
+#[cfg(feature = "cli")]
+use tera as _;
 
 pub use crate::change_group::ChangeGroup;
 pub use crate::changelog::Changelog;
+pub use crate::changelog::LinkSeparator;
 pub use crate::changelog::ParseChangelogError;
+pub use crate::changelog::ParseChangelogOptions;
 pub use crate::changelog::PromoteOptions;
 pub use crate::changelog::PromoteUnreleasedError;
 pub use crate::changes::Changes;
+pub use crate::changes::ChangesIter;
+pub use crate::compliance::ComplianceFinding;
+pub use crate::compliance::ComplianceOptions;
+pub use crate::compliance::ComplianceReport;
+pub use crate::compliance::ComplianceRule;
+pub use crate::compliance::ParseComplianceRuleError;
+pub use crate::diagnostic::DiagnoseOptions;
+pub use crate::diagnostic::Diagnostic;
+pub use crate::diagnostic::DiagnosticsBySection;
+pub use crate::diagnostic::LimitedDiagnostics;
+pub use crate::diagnostic::RenderOptions;
+pub use crate::diagnostic::Severity;
+#[cfg(feature = "json")]
+pub use crate::diagnostics_report::DiagnosticsReport;
+pub use crate::format::EntrySort;
+pub use crate::format::FormatOptions;
+pub use crate::format::LineWrap;
+pub use crate::fragments::Fragment;
+pub use crate::fragments::FragmentError;
+pub use crate::fragments::Fragments;
+#[cfg(feature = "github")]
+pub use crate::github::GitHubError;
+#[cfg(feature = "github")]
+pub use crate::github::GitHubRelease;
+#[cfg(feature = "github")]
+pub use crate::github::GitHubReleases;
+#[cfg(feature = "github")]
+pub use crate::github::ReleaseDrift;
+pub use crate::import::ImportChangelogError;
+pub use crate::line_ending::LineEnding;
+#[cfg(feature = "link-check")]
+pub use crate::link_check::LinkCheckOptions;
+pub use crate::link_template::LinkTemplate;
+pub use crate::link_template::LinkTemplateError;
+pub use crate::link_template::ParseRepositoryProviderError;
+pub use crate::link_template::RepositoryProvider;
+pub use crate::merge::MergeConflict;
 pub use crate::release::Release;
 pub use crate::release_date::ParseReleaseDateError;
 pub use crate::release_date::ReleaseDate;
 pub use crate::release_link::ParseReleaseLinkError;
 pub use crate::release_link::ReleaseLink;
 pub use crate::release_tag::ReleaseTag;
+pub use crate::release_version::BumpVersionError;
+pub use crate::release_version::ParseVersionBumpError;
 pub use crate::release_version::ParseVersionError;
 pub use crate::release_version::ReleaseVersion;
+pub use crate::release_version::VersionBump;
 pub use crate::releases::Releases;
+pub use crate::releases::ReleasesIntoIter;
+pub use crate::releases::ReleasesIter;
+pub use crate::section::Section;
+pub use crate::statistics::ChangelogStatistics;
 pub use crate::unreleased::Unreleased;
+#[cfg(feature = "workspace")]
+pub use crate::workspace::CombinedReleaseNotes;
+#[cfg(feature = "workspace")]
+pub use crate::workspace::MemberReport;
+#[cfg(feature = "workspace")]
+pub use crate::workspace::Workspace;
+#[cfg(feature = "workspace")]
+pub use crate::workspace::WorkspaceError;
+#[cfg(feature = "workspace")]
+pub use crate::workspace::WorkspaceMember;
+#[cfg(feature = "workspace")]
+pub use crate::workspace::WorkspaceReport;
 
 #[cfg(test)]
 mod test {
@@ -0,0 +1,92 @@
+use std::fmt::Write;
+
+/// The line ending used when writing a changelog.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum LineEnding {
+    /// Lines end with `\n`.
+    #[default]
+    Lf,
+    /// Lines end with `\r\n`, as is common for files checked out on Windows.
+    CrLf,
+}
+
+impl LineEnding {
+    /// Detects the line ending used by most of the lines in `input`. Input without any `\r\n`
+    /// line endings is treated as [`LineEnding::Lf`].
+    #[must_use]
+    pub fn detect(input: &str) -> Self {
+        let crlf = input.matches("\r\n").count();
+        let lf = input.matches('\n').count() - crlf;
+        if crlf > lf {
+            LineEnding::CrLf
+        } else {
+            LineEnding::Lf
+        }
+    }
+
+    /// The characters that end a line.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+// everything in the model uses `\n` so other line endings are only applied as the text is written
+pub(crate) struct LineEndingWriter<'a, W> {
+    inner: &'a mut W,
+    line_ending: LineEnding,
+}
+
+impl<'a, W: Write> LineEndingWriter<'a, W> {
+    pub(crate) fn new(inner: &'a mut W, line_ending: LineEnding) -> Self {
+        Self { inner, line_ending }
+    }
+}
+
+impl<W: Write> Write for LineEndingWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        if self.line_ending == LineEnding::Lf {
+            return self.inner.write_str(s);
+        }
+        let mut lines = s.split('\n');
+        if let Some(first) = lines.next() {
+            self.inner.write_str(first)?;
+        }
+        for line in lines {
+            self.inner.write_str(self.line_ending.as_str())?;
+            self.inner.write_str(line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(LineEnding::detect(""), LineEnding::Lf);
+        assert_eq!(LineEnding::detect("a\nb\n"), LineEnding::Lf);
+        assert_eq!(LineEnding::detect("a\r\nb\r\n"), LineEnding::CrLf);
+        assert_eq!(LineEnding::detect("a\r\nb\r\nc\n"), LineEnding::CrLf);
+        assert_eq!(LineEnding::detect("a\r\nb\nc\n"), LineEnding::Lf);
+    }
+
+    #[test]
+    fn test_writer() {
+        let mut output = String::new();
+        write!(
+            LineEndingWriter::new(&mut output, LineEnding::CrLf),
+            "a\nb\n"
+        )
+        .unwrap_or_default();
+        assert_eq!(output, "a\r\nb\r\n");
+    }
+}
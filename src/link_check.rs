@@ -0,0 +1,191 @@
+use crate::changelog::parse_changelog_source;
+use crate::{
+    Changelog, ComplianceRule, Diagnostic, ParseChangelogError, ParseChangelogOptions, ReleaseLink,
+    Section, Severity,
+};
+use std::time::Duration;
+
+/// Options for customizing how release links are checked.
+#[derive(Debug)]
+pub struct LinkCheckOptions {
+    timeout: Duration,
+}
+
+impl LinkCheckOptions {
+    /// Construct a new [`LinkCheckOptions`] instance.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Set the maximum amount of time to wait for each link to respond. Defaults to 10 seconds.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl Default for LinkCheckOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// a link that doesn't resolve leaves its version without a working link, so dead links are
+// reported under the linkable versions rule, as errors since the link is broken rather than missing
+pub(crate) fn check_links(
+    input: &str,
+    options: &LinkCheckOptions,
+) -> Result<Vec<Diagnostic>, ParseChangelogError> {
+    let parsed = parse_changelog_source(input, &ParseChangelogOptions::new())?;
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(options.timeout))
+        .build()
+        .into();
+
+    Ok(links(&parsed.changelog)
+        .filter_map(|(section, link)| {
+            let reason = check_link(&agent, link).err()?;
+            let rule = ComplianceRule::LinkableVersions;
+            Some(Diagnostic {
+                rule: Some(rule),
+                section: Some(section.clone()),
+                ..Diagnostic::new(
+                    Severity::Error,
+                    format!(
+                        "Link for {section} doesn't resolve: {reason}\nLink: {link}\nSee: {}",
+                        rule.spec_reference()
+                    ),
+                    parsed.link_definitions.get(&section),
+                )
+            })
+        })
+        .collect())
+}
+
+fn links(changelog: &Changelog) -> impl Iterator<Item = (Section, &ReleaseLink)> {
+    changelog
+        .unreleased
+        .link
+        .iter()
+        .map(|link| (Section::Unreleased, link))
+        .chain(changelog.releases.iter().filter_map(|(version, release)| {
+            release
+                .link
+                .as_ref()
+                .map(|link| (Section::Release(version.clone()), link))
+        }))
+}
+
+fn check_link(agent: &ureq::Agent, link: &ReleaseLink) -> Result<(), String> {
+    let url = link.to_string();
+    match agent.head(&url).call() {
+        Ok(_) => Ok(()),
+        // some servers don't support HEAD requests so fall back to GET before reporting the link
+        Err(ureq::Error::StatusCode(405)) => agent
+            .get(&url)
+            .call()
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn options() -> LinkCheckOptions {
+        LinkCheckOptions::new().with_timeout(Duration::from_secs(5))
+    }
+
+    // answers `/ok` with 200, `/missing` with 404, and HEAD requests for `/get-only` with 405
+    // so only a GET request for it succeeds
+    fn serve() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut header = String::new();
+                while reader.read_line(&mut header).unwrap() > 2 {
+                    header.clear();
+                }
+                let status = match request_line.split_whitespace().collect::<Vec<_>>()[..] {
+                    [_, "/ok", _] | ["GET", "/get-only", _] => "200 OK",
+                    ["HEAD", "/get-only", _] => "405 Method Not Allowed",
+                    _ => "404 Not Found",
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                )
+                .unwrap();
+            }
+        });
+        format!("http://{address}")
+    }
+
+    #[test]
+    fn test_dead_links_are_reported() {
+        let server = serve();
+        let input = format!(
+            "# Changelog\n\n## [Unreleased]\n\n## [1.1.0] - 2024-02-01\n\n### Fixed\n\n- Y\n\n## [1.0.0] - 2024-01-01\n\n### Added\n\n- X\n\n[unreleased]: {server}/get-only\n[1.1.0]: {server}/missing\n[1.0.0]: {server}/ok\n"
+        );
+
+        let diagnostics = Changelog::check_links(&input, &options()).unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        let diagnostic = &diagnostics[0];
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(diagnostic.rule, Some(ComplianceRule::LinkableVersions));
+        assert_eq!(
+            diagnostic.section,
+            Some(Section::Release("1.1.0".parse().unwrap()))
+        );
+        assert!(diagnostic
+            .message
+            .starts_with("Link for 1.1.0 doesn't resolve: "));
+        assert!(diagnostic.message.contains("404"));
+        assert_eq!((diagnostic.line, diagnostic.column), (18, 1));
+    }
+
+    #[test]
+    fn test_unreachable_links_are_reported() {
+        let input = "# Changelog\n\n## [Unreleased]\n\n[unreleased]: http://127.0.0.1:1/compare/v1.0.0...HEAD\n";
+
+        let diagnostics = Changelog::check_links(
+            input,
+            &LinkCheckOptions::new().with_timeout(Duration::from_secs(1)),
+        )
+        .unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].section, Some(Section::Unreleased));
+        assert_eq!(diagnostics[0].line, 5);
+    }
+
+    #[test]
+    fn test_no_links_to_check() {
+        assert!(
+            Changelog::check_links("# Changelog\n\n## [Unreleased]\n", &options())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_invalid_changelog() {
+        assert!(Changelog::check_links("## Not a release header\n", &options()).is_err());
+    }
+}
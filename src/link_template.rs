@@ -0,0 +1,384 @@
+use crate::{ReleaseLink, ReleaseVersion};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use thiserror::Error;
+
+lazy_static! {
+    static ref RANGE_COMPARE: Regex =
+        Regex::new(r"/compare/(?<from>[^/]+?)\.{2,3}[^/]+$").expect("Should be a valid regex");
+    static ref BITBUCKET_COMPARE: Regex =
+        Regex::new(r"/branches/compare/[^/]+%0D(?<from>[^/]+)$").expect("Should be a valid regex");
+}
+
+/// The hosting service for a source repository. Each provider uses a different URL structure for
+/// comparing two revisions and for linking to a tag. sourcehut can't compare two revisions, so
+/// its releases link to their tags.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum RepositoryProvider {
+    /// [GitHub](https://github.com)
+    GitHub,
+    /// [GitLab](https://gitlab.com) (including self-hosted instances)
+    GitLab,
+    /// [Gitea](https://about.gitea.com) and [Forgejo](https://forgejo.org) instances such as [Codeberg](https://codeberg.org)
+    Gitea,
+    /// [Bitbucket](https://bitbucket.org)
+    Bitbucket,
+    /// [sourcehut](https://sr.ht), which has no view comparing two revisions
+    Sourcehut,
+}
+
+impl RepositoryProvider {
+    /// Guesses the provider from the host of a repository URL.
+    #[must_use]
+    pub fn from_host(host: &str) -> Option<Self> {
+        let host = host.to_lowercase();
+        if host == "github.com" {
+            Some(RepositoryProvider::GitHub)
+        } else if host == "bitbucket.org" {
+            Some(RepositoryProvider::Bitbucket)
+        } else if host == "git.sr.ht" {
+            Some(RepositoryProvider::Sourcehut)
+        } else if host == "codeberg.org" || host.contains("gitea") || host.contains("forgejo") {
+            Some(RepositoryProvider::Gitea)
+        } else if host.contains("gitlab") {
+            Some(RepositoryProvider::GitLab)
+        } else {
+            None
+        }
+    }
+}
+
+impl RepositoryProvider {
+    // whether the provider has a view of the commits between two revisions, which sourcehut
+    // doesn't since the `from` parameter of its log only picks where the history starts
+    pub(crate) fn has_compare_view(self) -> bool {
+        self != RepositoryProvider::Sourcehut
+    }
+
+    // the revision a compare link starts from, in the URL structure `LinkTemplate::compare_link`
+    // generates for the provider
+    pub(crate) fn compare_base(self, link: &str) -> Option<&str> {
+        let compare = match self {
+            RepositoryProvider::GitHub | RepositoryProvider::GitLab | RepositoryProvider::Gitea => {
+                &*RANGE_COMPARE
+            }
+            RepositoryProvider::Bitbucket => &*BITBUCKET_COMPARE,
+            RepositoryProvider::Sourcehut => return None,
+        };
+        compare
+            .captures(link)
+            .and_then(|captures| captures.name("from"))
+            .map(|from| from.as_str())
+    }
+}
+
+impl Display for RepositoryProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RepositoryProvider::GitHub => write!(f, "github"),
+            RepositoryProvider::GitLab => write!(f, "gitlab"),
+            RepositoryProvider::Gitea => write!(f, "gitea"),
+            RepositoryProvider::Bitbucket => write!(f, "bitbucket"),
+            RepositoryProvider::Sourcehut => write!(f, "sourcehut"),
+        }
+    }
+}
+
+/// Error for when a repository provider name cannot be parsed.
+#[derive(Debug, Error)]
+#[error("Could not parse repository provider '{0}'\nExpected: github | gitlab | gitea | bitbucket | sourcehut")]
+pub struct ParseRepositoryProviderError(String);
+
+impl FromStr for RepositoryProvider {
+    type Err = ParseRepositoryProviderError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "github" => Ok(RepositoryProvider::GitHub),
+            "gitlab" => Ok(RepositoryProvider::GitLab),
+            "gitea" | "forgejo" | "codeberg" => Ok(RepositoryProvider::Gitea),
+            "bitbucket" => Ok(RepositoryProvider::Bitbucket),
+            "sourcehut" | "srht" => Ok(RepositoryProvider::Sourcehut),
+            _ => Err(ParseRepositoryProviderError(value.to_string())),
+        }
+    }
+}
+
+/// Generates release links for a repository using the URL structure of its hosting provider.
+///
+/// Parsing a [`LinkTemplate`] from a repository URL selects the provider from the URL host. Use
+/// [`LinkTemplate::new`] to set the provider explicitly for self-hosted instances that can't be
+/// detected.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct LinkTemplate {
+    repository: String,
+    provider: RepositoryProvider,
+    tag_prefix: String,
+}
+
+/// Error for when a link template cannot be created from a repository URL.
+#[derive(Debug, Error)]
+pub enum LinkTemplateError {
+    /// The repository URL is not a valid URI.
+    #[error("Could not parse repository URL '{0}' as a URI.\nReason: {1}")]
+    InvalidUrl(String, String),
+    /// The provider could not be detected from the repository URL.
+    #[error(
+        "Could not detect the repository provider for '{0}'. Specify the provider explicitly."
+    )]
+    UnknownProvider(String),
+}
+
+impl LinkTemplate {
+    /// Construct a new [`LinkTemplate`] for the given repository URL and provider.
+    pub fn new(
+        repository_url: &str,
+        provider: RepositoryProvider,
+    ) -> Result<Self, LinkTemplateError> {
+        let repository = normalize_repository_url(repository_url);
+        uriparse::URI::try_from(repository.as_str()).map_err(|e| {
+            LinkTemplateError::InvalidUrl(repository_url.to_string(), e.to_string())
+        })?;
+        Ok(Self {
+            repository,
+            provider,
+            tag_prefix: "v".to_string(),
+        })
+    }
+
+    /// Set the prefix used to turn a version into a tag name. Defaults to `v`.
+    #[must_use]
+    pub fn with_tag_prefix(mut self, tag_prefix: impl Into<String>) -> Self {
+        self.tag_prefix = tag_prefix.into();
+        self
+    }
+
+    /// The provider used to generate links.
+    #[must_use]
+    pub fn provider(&self) -> RepositoryProvider {
+        self.provider
+    }
+
+    /// Returns a link comparing the tag for `from` with the tag for `to`. When `to` is `None`, the
+    /// comparison is made against `HEAD` which is what the Unreleased link should point to.
+    ///
+    /// sourcehut has no comparison view, so its links point to the tag for `to` instead, or to the
+    /// log of the repository when `to` is `None`.
+    #[must_use]
+    pub fn compare_link(&self, from: &ReleaseVersion, to: Option<&ReleaseVersion>) -> ReleaseLink {
+        let repository = &self.repository;
+        let from = self.tag(from);
+        let to = to.map_or_else(|| "HEAD".to_string(), |to| self.tag(to));
+        let link = match self.provider {
+            RepositoryProvider::GitHub | RepositoryProvider::Gitea => {
+                format!("{repository}/compare/{from}...{to}")
+            }
+            RepositoryProvider::GitLab => format!("{repository}/-/compare/{from}...{to}"),
+            RepositoryProvider::Bitbucket => {
+                format!("{repository}/branches/compare/{to}%0D{from}")
+            }
+            RepositoryProvider::Sourcehut if to == "HEAD" => format!("{repository}/log"),
+            RepositoryProvider::Sourcehut => format!("{repository}/refs/{to}"),
+        };
+        ReleaseLink::from_generated(link)
+    }
+
+    /// Returns a link to the tag for the given `version`. This is used for the first release in a
+    /// changelog since there is no previous release to compare against.
+    #[must_use]
+    pub fn tag_link(&self, version: &ReleaseVersion) -> ReleaseLink {
+        let repository = &self.repository;
+        let tag = self.tag(version);
+        let link = match self.provider {
+            RepositoryProvider::GitHub | RepositoryProvider::Gitea => {
+                format!("{repository}/releases/tag/{tag}")
+            }
+            RepositoryProvider::GitLab => format!("{repository}/-/tags/{tag}"),
+            RepositoryProvider::Bitbucket => format!("{repository}/src/{tag}"),
+            RepositoryProvider::Sourcehut => format!("{repository}/refs/{tag}"),
+        };
+        ReleaseLink::from_generated(link)
+    }
+
+    /// Returns the version a compare link generated by this template starts from, or `None` if the
+    /// link isn't a comparison from a tag with the template's prefix. Links for sourcehut never
+    /// compare from a version, so this is always `None` for them.
+    #[must_use]
+    pub fn compare_base(&self, link: &ReleaseLink) -> Option<ReleaseVersion> {
+        self.provider
+            .compare_base(&link.to_string())?
+            .strip_prefix(&self.tag_prefix)?
+            .parse()
+            .ok()
+    }
+
+    fn tag(&self, version: &ReleaseVersion) -> String {
+        format!("{}{version}", self.tag_prefix)
+    }
+}
+
+impl FromStr for LinkTemplate {
+    type Err = LinkTemplateError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let repository = normalize_repository_url(value);
+        let uri = uriparse::URI::try_from(repository.as_str())
+            .map_err(|e| LinkTemplateError::InvalidUrl(value.to_string(), e.to_string()))?;
+        let provider = uri
+            .host()
+            .and_then(|host| RepositoryProvider::from_host(&host.to_string()))
+            .ok_or_else(|| LinkTemplateError::UnknownProvider(value.to_string()))?;
+        LinkTemplate::new(value, provider)
+    }
+}
+
+fn normalize_repository_url(value: &str) -> String {
+    let value = value.trim().trim_end_matches('/');
+    value.strip_suffix(".git").unwrap_or(value).to_string()
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn compare(repository: &str) -> String {
+        repository
+            .parse::<LinkTemplate>()
+            .unwrap()
+            .compare_link(&"1.0.0".parse().unwrap(), Some(&"1.1.0".parse().unwrap()))
+            .to_string()
+    }
+
+    #[test]
+    fn test_github_links() {
+        let template: LinkTemplate = "https://github.com/org/repo.git".parse().unwrap();
+        assert_eq!(template.provider(), RepositoryProvider::GitHub);
+        assert_eq!(
+            compare("https://github.com/org/repo"),
+            "https://github.com/org/repo/compare/v1.0.0...v1.1.0"
+        );
+        assert_eq!(
+            template
+                .compare_link(&"1.0.0".parse().unwrap(), None)
+                .to_string(),
+            "https://github.com/org/repo/compare/v1.0.0...HEAD"
+        );
+        assert_eq!(
+            template.tag_link(&"1.0.0".parse().unwrap()).to_string(),
+            "https://github.com/org/repo/releases/tag/v1.0.0"
+        );
+    }
+
+    #[test]
+    fn test_gitlab_links() {
+        assert_eq!(
+            compare("https://gitlab.com/group/subgroup/repo/"),
+            "https://gitlab.com/group/subgroup/repo/-/compare/v1.0.0...v1.1.0"
+        );
+        assert_eq!(
+            compare("https://gitlab.example.com/group/repo"),
+            "https://gitlab.example.com/group/repo/-/compare/v1.0.0...v1.1.0"
+        );
+    }
+
+    #[test]
+    fn test_gitea_links() {
+        assert_eq!(
+            compare("https://codeberg.org/org/repo"),
+            "https://codeberg.org/org/repo/compare/v1.0.0...v1.1.0"
+        );
+    }
+
+    #[test]
+    fn test_bitbucket_links() {
+        assert_eq!(
+            compare("https://bitbucket.org/org/repo"),
+            "https://bitbucket.org/org/repo/branches/compare/v1.1.0%0Dv1.0.0"
+        );
+    }
+
+    #[test]
+    fn test_sourcehut_links() {
+        assert_eq!(
+            compare("https://git.sr.ht/~user/repo"),
+            "https://git.sr.ht/~user/repo/refs/v1.1.0"
+        );
+        let template = "https://git.sr.ht/~user/repo"
+            .parse::<LinkTemplate>()
+            .unwrap();
+        assert_eq!(
+            template
+                .compare_link(&"1.0.0".parse().unwrap(), None)
+                .to_string(),
+            "https://git.sr.ht/~user/repo/log"
+        );
+        assert_eq!(
+            template.compare_base(&template.compare_link(&"1.0.0".parse().unwrap(), None)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_explicit_provider_and_tag_prefix() {
+        let template =
+            LinkTemplate::new("https://git.example.com/repo", RepositoryProvider::GitLab)
+                .unwrap()
+                .with_tag_prefix("");
+        assert_eq!(
+            template
+                .compare_link(&"1.0.0".parse().unwrap(), Some(&"1.1.0".parse().unwrap()))
+                .to_string(),
+            "https://git.example.com/repo/-/compare/1.0.0...1.1.0"
+        );
+    }
+
+    #[test]
+    fn test_compare_base() {
+        let version: ReleaseVersion = "1.10.0".parse().unwrap();
+        for repository in [
+            "https://github.com/org/repo",
+            "https://gitlab.com/org/repo",
+            "https://codeberg.org/org/repo",
+            "https://bitbucket.org/org/repo",
+        ] {
+            let template = repository
+                .parse::<LinkTemplate>()
+                .unwrap()
+                .with_tag_prefix("release-");
+            assert_eq!(
+                template.compare_base(&template.compare_link(&version, None)),
+                Some(version.clone()),
+                "{repository}"
+            );
+            assert_eq!(
+                template.compare_base(&template.tag_link(&version)),
+                None,
+                "{repository}"
+            );
+        }
+
+        let template = "https://github.com/org/repo"
+            .parse::<LinkTemplate>()
+            .unwrap();
+        let link = |link: &str| link.parse::<ReleaseLink>().unwrap();
+        assert_eq!(
+            template.compare_base(&link("https://github.com/org/repo/compare/v1.1.0..HEAD")),
+            Some("1.1.0".parse().unwrap())
+        );
+        assert_eq!(
+            template.compare_base(&link("https://github.com/org/repo/compare/1.1.0...HEAD")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_unknown_provider() {
+        assert!(matches!(
+            "https://git.example.com/repo".parse::<LinkTemplate>(),
+            Err(LinkTemplateError::UnknownProvider(_))
+        ));
+    }
+}
@@ -0,0 +1,236 @@
+use crate::{Changelog, Changes, ReleaseDate, ReleaseLink, ReleaseTag, ReleaseVersion, Section};
+use std::fmt::{Display, Formatter};
+
+/// A difference between two changelogs that [`Changelog::merge`] couldn't reconcile. The value
+/// from the changelog being merged into is kept.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum MergeConflict {
+    /// The release has a different date in each changelog.
+    Date {
+        /// The version of the release.
+        version: ReleaseVersion,
+        /// The date that was kept.
+        ours: ReleaseDate,
+        /// The date from the changelog that was merged in.
+        theirs: ReleaseDate,
+    },
+    /// The release has a different tag in each changelog.
+    Tag {
+        /// The version of the release.
+        version: ReleaseVersion,
+        /// The tag that was kept.
+        ours: ReleaseTag,
+        /// The tag from the changelog that was merged in.
+        theirs: ReleaseTag,
+    },
+    /// The section has a different link in each changelog.
+    Link {
+        /// The section with the link.
+        section: Section,
+        /// The link that was kept.
+        ours: ReleaseLink,
+        /// The link from the changelog that was merged in.
+        theirs: ReleaseLink,
+    },
+}
+
+impl Display for MergeConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeConflict::Date {
+                version,
+                ours,
+                theirs,
+            } => write!(
+                f,
+                "Release {version} is dated {ours} in one changelog and {theirs} in the other, keeping {ours}"
+            ),
+            MergeConflict::Tag {
+                version,
+                ours,
+                theirs,
+            } => write!(
+                f,
+                "Release {version} is tagged [{ours}] in one changelog and [{theirs}] in the other, keeping [{ours}]"
+            ),
+            MergeConflict::Link {
+                section,
+                ours,
+                theirs,
+            } => write!(
+                f,
+                "{section} links to {ours} in one changelog and {theirs} in the other, keeping {ours}"
+            ),
+        }
+    }
+}
+
+pub(crate) fn merge(changelog: &mut Changelog, other: &Changelog) -> Vec<MergeConflict> {
+    let mut conflicts = vec![];
+
+    merge_changes(&mut changelog.unreleased.changes, &other.unreleased.changes);
+    merge_option(
+        &mut changelog.unreleased.link,
+        other.unreleased.link.as_ref(),
+        |ours, theirs| {
+            conflicts.push(MergeConflict::Link {
+                section: Section::Unreleased,
+                ours,
+                theirs,
+            });
+        },
+    );
+
+    for (version, theirs) in &other.releases {
+        let Some(ours) = changelog.releases.get_version_mut(version) else {
+            changelog.releases.insert_ordered(theirs.clone());
+            continue;
+        };
+        merge_changes(&mut ours.changes, &theirs.changes);
+        if ours.date != theirs.date {
+            conflicts.push(MergeConflict::Date {
+                version: version.clone(),
+                ours: ours.date.clone(),
+                theirs: theirs.date.clone(),
+            });
+        }
+        merge_option(&mut ours.tag, theirs.tag.as_ref(), |ours, theirs| {
+            conflicts.push(MergeConflict::Tag {
+                version: version.clone(),
+                ours,
+                theirs,
+            });
+        });
+        merge_option(&mut ours.link, theirs.link.as_ref(), |ours, theirs| {
+            conflicts.push(MergeConflict::Link {
+                section: Section::Release(version.clone()),
+                ours,
+                theirs,
+            });
+        });
+    }
+
+    conflicts
+}
+
+// entries are added after the existing ones in their group unless the same text is already there
+fn merge_changes(ours: &mut Changes, theirs: &Changes) {
+    for (change_group, items) in theirs {
+        for item in items {
+            if !ours
+                .get(change_group)
+                .is_some_and(|existing| existing.contains(item))
+            {
+                ours.add(change_group.clone(), item.clone());
+            }
+        }
+    }
+}
+
+// a value missing from one changelog is filled in from the other, it's only a conflict when both
+// changelogs have a value and they differ
+fn merge_option<T: Clone + PartialEq>(
+    ours: &mut Option<T>,
+    theirs: Option<&T>,
+    mut conflict: impl FnMut(T, T),
+) {
+    match (ours.as_ref(), theirs) {
+        (None, Some(theirs)) => *ours = Some(theirs.clone()),
+        (Some(existing), Some(theirs)) if existing != theirs => {
+            conflict(existing.clone(), theirs.clone());
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use crate::{Changelog, MergeConflict};
+
+    #[test]
+    fn test_merge() {
+        let mut changelog: Changelog = "\
+# Changelog
+
+## [Unreleased]
+
+### Added
+
+- Added X
+
+## [1.2.0] - 2024-02-01
+
+### Fixed
+
+- Fixed Y
+
+## [1.0.0] - 2023-01-01
+
+[unreleased]: https://github.com/org/a/compare/v1.2.0...HEAD
+"
+        .parse()
+        .unwrap();
+        let other: Changelog = "\
+# Changelog
+
+## [Unreleased]
+
+### Added
+
+- Added X
+- Added Z
+
+## [1.2.0] - 2024-02-02 [YANKED]
+
+### Fixed
+
+- Fixed W
+
+## [1.1.0] - 2023-06-01
+
+## [1.0.0] - 2023-01-01
+
+[unreleased]: https://github.com/org/b/compare/v1.2.0...HEAD
+"
+        .parse()
+        .unwrap();
+
+        let conflicts = changelog.merge(&other);
+
+        assert_eq!(
+            conflicts
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "Unreleased links to https://github.com/org/a/compare/v1.2.0...HEAD in one changelog and https://github.com/org/b/compare/v1.2.0...HEAD in the other, keeping https://github.com/org/a/compare/v1.2.0...HEAD",
+                "Release 1.2.0 is dated 2024-02-01 in one changelog and 2024-02-02 in the other, keeping 2024-02-01",
+            ]
+        );
+        assert!(matches!(conflicts[1], MergeConflict::Date { .. }));
+        assert_eq!(
+            changelog
+                .unreleased
+                .changes
+                .iter()
+                .map(|(_, items)| items.clone())
+                .collect::<Vec<_>>(),
+            vec![vec!["Added X", "Added Z"]]
+        );
+        assert_eq!(
+            changelog
+                .releases
+                .iter()
+                .map(|(version, _)| version.to_string())
+                .collect::<Vec<_>>(),
+            vec!["1.2.0", "1.1.0", "1.0.0"]
+        );
+        let release = &changelog.releases["1.2.0"];
+        assert_eq!(release.tag, Some(crate::ReleaseTag::Yanked));
+        assert_eq!(
+            release.changes.to_string(),
+            "### Fixed\n\n- Fixed Y\n- Fixed W"
+        );
+    }
+}
@@ -1,4 +1,6 @@
+use crate::change_group::ChangeGroup;
 use crate::changes::Changes;
+use crate::import::{import_github_release_notes, ImportChangelogError};
 use crate::release_date::ReleaseDate;
 use crate::release_link::ReleaseLink;
 use crate::release_tag::ReleaseTag;
@@ -6,6 +8,9 @@ use crate::release_version::ReleaseVersion;
 
 /// Represents release information such as the version, date, link to release, list of changes, and so on.
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Release {
     /// The version of the release in [semver](https://semver.org/spec/v2.0.0.html) format.
     pub version: ReleaseVersion,
@@ -18,3 +23,76 @@ pub struct Release {
     /// An ordered map of the changes in a release grouped by the type of change.
     pub changes: Changes,
 }
+
+impl Release {
+    /// Converts the [release notes automatically generated by GitHub](https://docs.github.com/en/repositories/releasing-projects-on-github/automatically-generated-release-notes)
+    /// into a [`Release`].
+    ///
+    /// Pull requests listed under "What's Changed" become entries linking to their pull request.
+    /// The change group is taken from the release notes category when one is configured, otherwise
+    /// it is guessed from the pull request title. The "Full Changelog" link becomes the release link
+    /// and the "New Contributors" section is dropped.
+    pub fn from_github_release_notes(
+        version: ReleaseVersion,
+        date: ReleaseDate,
+        notes: &str,
+    ) -> Result<Release, ImportChangelogError> {
+        import_github_release_notes(version, date, notes)
+    }
+
+    /// Adds the given `item` to the release under the provided `change_group` heading.
+    pub fn add(&mut self, change_group: ChangeGroup, item: impl Into<String>) {
+        self.changes.add(change_group, item);
+    }
+
+    /// Returns the anchor GitHub generates for the release heading, e.g. `120---2024-01-05` for
+    /// `## [1.2.0] - 2024-01-05`, which can be used to link directly to the release.
+    #[must_use]
+    pub fn anchor(&self) -> String {
+        let tag = self
+            .tag
+            .as_ref()
+            .map(|tag| format!(" [{tag}]"))
+            .unwrap_or_default();
+        heading_anchor(&format!("[{}] - {}{tag}", self.version, self.date))
+    }
+}
+
+// follows the slug algorithm GitHub uses for heading anchors: lowercase the text, drop punctuation
+// other than hyphens and underscores, then replace each space with a hyphen
+pub(crate) fn heading_anchor(heading: &str) -> String {
+    heading
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | ' '))
+        .map(|c| if c == ' ' { '-' } else { c })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use crate::Changelog;
+
+    #[test]
+    fn test_anchor() {
+        let changelog: Changelog = "\
+# Changelog
+
+## [Unreleased]
+
+## [1.2.0] - 2024-01-05 [YANKED]
+
+## [1.1.0] - 2023-12-01
+"
+        .parse()
+        .unwrap();
+        let anchors = changelog
+            .releases
+            .iter()
+            .map(|(_, release)| release.anchor())
+            .collect::<Vec<_>>();
+        assert_eq!(anchors, vec!["120---2024-01-05-yanked", "110---2023-12-01"]);
+        assert_eq!(changelog.unreleased.anchor(), "unreleased");
+    }
+}
@@ -3,7 +3,9 @@ use std::str::FromStr;
 use thiserror::Error;
 
 /// Release dates are in ISO 8601 date format (YYYY-MM-DD)
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "json", derive(serde::Serialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ReleaseDate(String);
 
 impl ReleaseDate {
@@ -12,6 +14,20 @@ impl ReleaseDate {
     pub fn today() -> Self {
         chrono::Utc::now().into()
     }
+
+    /// Returns the number of days from `earlier` to this date, which is negative if `earlier` is
+    /// actually later.
+    #[must_use]
+    pub fn days_since(&self, earlier: &ReleaseDate) -> i64 {
+        (self.naive_date() - earlier.naive_date()).num_days()
+    }
+
+    fn naive_date(&self) -> chrono::NaiveDate {
+        format!("{}T00:00:00Z", self.0)
+            .parse::<chrono::DateTime<chrono::Utc>>()
+            .expect("Should be a valid date since it was validated on parsing")
+            .date_naive()
+    }
 }
 
 /// An error for release dates that cannot be parsed.
@@ -30,6 +46,26 @@ impl FromStr for ReleaseDate {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for ReleaseDate {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let year = u.int_in_range(1970..=2100)?;
+        let month = u.int_in_range(1..=12)?;
+        let day = u.int_in_range(1..=28)?;
+        Ok(ReleaseDate(format!("{year:04}-{month:02}-{day:02}")))
+    }
+}
+
+// deserialized values are validated the same way as parsed ones
+#[cfg(feature = "json")]
+impl<'de> serde::Deserialize<'de> for ReleaseDate {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 impl Display for ReleaseDate {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -4,8 +4,17 @@ use thiserror::Error;
 
 /// URI to the set of changes in a release.
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "json", derive(serde::Serialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ReleaseLink(String);
 
+impl ReleaseLink {
+    // links built by a LinkTemplate are derived from an already validated repository URL
+    pub(crate) fn from_generated(value: String) -> Self {
+        ReleaseLink(value)
+    }
+}
+
 /// Error for when a release link cannot be parsed.
 #[derive(Debug, Error)]
 #[error("Could not parse release link '{0}' as a URI.\nReason: {1}")]
@@ -21,6 +30,24 @@ impl FromStr for ReleaseLink {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for ReleaseLink {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let path = u.arbitrary::<u32>()?;
+        Ok(ReleaseLink(format!("https://example.com/{path}")))
+    }
+}
+
+// deserialized values are validated the same way as parsed ones
+#[cfg(feature = "json")]
+impl<'de> serde::Deserialize<'de> for ReleaseLink {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 impl Display for ReleaseLink {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
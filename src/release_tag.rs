@@ -7,6 +7,9 @@ use thiserror::Error;
 /// - If a release version was bumped but there were no changes which can be common in projects that
 ///   use a fixed version strategy to release a set of artifacts.
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum ReleaseTag {
     /// A yanked release.
     Yanked,
@@ -1,16 +1,120 @@
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 use thiserror::Error;
 
 /// The version of a release in [Semantic Versioning](https://semver.org/) format.
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
+#[cfg_attr(feature = "json", derive(serde::Serialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ReleaseVersion(String);
 
+/// An error for when a version can't be bumped because a part of it would overflow.
+#[derive(Debug, Error)]
+#[error("Could not bump the {1} version of {0} any further")]
+pub struct BumpVersionError(ReleaseVersion, VersionBump);
+
 /// An error for when the version cannot be parsed into [Semantic Versioning](https://semver.org/) format.
 #[derive(Debug, Error)]
 #[error("Could not parse version '{0}' as semver.\nReason: {1}")]
 pub struct ParseVersionError(String, String);
 
+impl ReleaseVersion {
+    /// Returns the version that follows this one for the given type of `bump`. Any pre-release or
+    /// build metadata is dropped. A pre-release of the version the bump would produce is promoted
+    /// to that version instead, so a major bump of `2.0.0-rc.1` is `2.0.0`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the incremented part of the version would overflow.
+    pub fn bump(&self, bump: VersionBump) -> Result<ReleaseVersion, BumpVersionError> {
+        let version = self
+            .0
+            .parse::<semver::Version>()
+            .expect("should be a valid semver version");
+        // a pre-release comes before its release, so the release is the next version when the
+        // parts below the bumped one are already zero
+        let promote = !version.pre.is_empty()
+            && match bump {
+                VersionBump::Major => version.minor == 0 && version.patch == 0,
+                VersionBump::Minor => version.patch == 0,
+                VersionBump::Patch => true,
+            };
+        let increment = |part: u64| {
+            part.checked_add(1)
+                .ok_or_else(|| BumpVersionError(self.clone(), bump))
+        };
+        let next = if promote {
+            semver::Version::new(version.major, version.minor, version.patch)
+        } else {
+            match bump {
+                VersionBump::Major => semver::Version::new(increment(version.major)?, 0, 0),
+                VersionBump::Minor => {
+                    semver::Version::new(version.major, increment(version.minor)?, 0)
+                }
+                VersionBump::Patch => {
+                    semver::Version::new(version.major, version.minor, increment(version.patch)?)
+                }
+            }
+        };
+        Ok(ReleaseVersion(next.to_string()))
+    }
+
+    // SemVer reserves 1.0.0 for the first stable release, anything may change before it
+    pub(crate) fn is_initial_development(&self) -> bool {
+        self.0
+            .parse::<semver::Version>()
+            .is_ok_and(|version| version.major == 0)
+    }
+
+    /// Returns `true` if the version has a pre-release component, such as `1.0.0-rc.1`.
+    #[must_use]
+    pub fn is_prerelease(&self) -> bool {
+        self.0
+            .parse::<semver::Version>()
+            .is_ok_and(|version| !version.pre.is_empty())
+    }
+}
+
+/// The type of version increment for a release, ordered from smallest to largest.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+pub enum VersionBump {
+    /// Backward compatible bug fixes.
+    Patch,
+    /// Backward compatible new functionality.
+    Minor,
+    /// Incompatible changes.
+    Major,
+}
+
+impl Display for VersionBump {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VersionBump::Patch => write!(f, "patch"),
+            VersionBump::Minor => write!(f, "minor"),
+            VersionBump::Major => write!(f, "major"),
+        }
+    }
+}
+
+/// An error for when a version bump type cannot be parsed.
+#[derive(Debug, Error)]
+#[error("Could not parse version bump '{0}'\nExpected: major | minor | patch")]
+pub struct ParseVersionBumpError(String);
+
+impl FromStr for VersionBump {
+    type Err = ParseVersionBumpError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "major" => Ok(VersionBump::Major),
+            "minor" => Ok(VersionBump::Minor),
+            "patch" => Ok(VersionBump::Patch),
+            _ => Err(ParseVersionBumpError(value.to_string())),
+        }
+    }
+}
+
 impl FromStr for ReleaseVersion {
     type Err = ParseVersionError;
 
@@ -22,8 +126,103 @@ impl FromStr for ReleaseVersion {
     }
 }
 
+// only well-formed versions are generated since the value is otherwise validated on parsing
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for ReleaseVersion {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let (major, minor, patch) = u.arbitrary::<(u16, u16, u16)>()?;
+        Ok(ReleaseVersion(format!("{major}.{minor}.{patch}")))
+    }
+}
+
+impl PartialOrd for ReleaseVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// versions are ordered by semver precedence rather than as strings so that 1.10.0 > 1.9.0
+impl Ord for ReleaseVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (
+            self.0.parse::<semver::Version>(),
+            other.0.parse::<semver::Version>(),
+        ) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            _ => self.0.cmp(&other.0),
+        }
+    }
+}
+
+// deserialized values are validated the same way as parsed ones
+#[cfg(feature = "json")]
+impl<'de> serde::Deserialize<'de> for ReleaseVersion {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 impl Display for ReleaseVersion {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
     }
 }
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_bump() {
+        let version: ReleaseVersion = "1.2.3-rc.1".parse().unwrap();
+        assert_eq!(
+            version.bump(VersionBump::Major).unwrap().to_string(),
+            "2.0.0"
+        );
+        assert_eq!(
+            version.bump(VersionBump::Minor).unwrap().to_string(),
+            "1.3.0"
+        );
+        assert_eq!(
+            version.bump(VersionBump::Patch).unwrap().to_string(),
+            "1.2.3"
+        );
+
+        let bump = |version: &str, bump: VersionBump| {
+            version
+                .parse::<ReleaseVersion>()
+                .unwrap()
+                .bump(bump)
+                .map(|version| version.to_string())
+                .ok()
+        };
+        assert_eq!(bump("1.2.0-rc.1", VersionBump::Minor).unwrap(), "1.2.0");
+        assert_eq!(bump("2.0.0-rc.1", VersionBump::Major).unwrap(), "2.0.0");
+        assert_eq!(bump("2.1.0-rc.1", VersionBump::Major).unwrap(), "3.0.0");
+        assert_eq!(bump(&format!("1.2.{}", u64::MAX), VersionBump::Patch), None);
+        assert_eq!(
+            bump(&format!("1.2.{}", u64::MAX), VersionBump::Minor).unwrap(),
+            "1.3.0"
+        );
+        assert_eq!(bump(&format!("{}.0.0", u64::MAX), VersionBump::Major), None);
+    }
+
+    #[test]
+    fn test_ordering() {
+        let v = |value: &str| value.parse::<ReleaseVersion>().unwrap();
+        assert!(v("1.10.0") > v("1.9.0"));
+        assert!(v("1.0.0") > v("1.0.0-rc.1"));
+        assert!(v("0.1.0") < v("1.0.0"));
+    }
+
+    #[test]
+    fn test_is_prerelease() {
+        let v = |value: &str| value.parse::<ReleaseVersion>().unwrap();
+        assert!(v("1.0.0-rc.1").is_prerelease());
+        assert!(!v("1.0.0").is_prerelease());
+        assert!(!v("1.0.0+build.5").is_prerelease());
+    }
+}
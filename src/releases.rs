@@ -1,15 +1,39 @@
 use crate::{Release, ReleaseVersion};
 use indexmap::IndexMap;
+use std::ops::Index;
 
 /// The list of releases in the changelog.
 #[derive(Debug, Eq, PartialEq, Default, Clone)]
 pub struct Releases(IndexMap<ReleaseVersion, Release>);
 
 impl Releases {
-    pub(crate) fn from_iter<I: IntoIterator<Item = (ReleaseVersion, Release)>>(
-        iterable: I,
-    ) -> Releases {
-        Self(IndexMap::from_iter(iterable))
+    // new releases go at the top of the changelog
+    pub(crate) fn insert_first(&mut self, release: Release) {
+        self.0.shift_insert(0, release.version.clone(), release);
+    }
+
+    // keeps releases ordered from newest to oldest when they already are, otherwise the release
+    // goes before the first older release
+    pub(crate) fn insert_ordered(&mut self, release: Release) {
+        let index = self
+            .0
+            .keys()
+            .position(|version| version < &release.version)
+            .unwrap_or(self.0.len());
+        self.0.shift_insert(index, release.version.clone(), release);
+    }
+
+    pub(crate) fn remove(&mut self, version: &ReleaseVersion) -> Option<Release> {
+        self.0.shift_remove(version)
+    }
+
+    // orders the releases from newest to oldest by version
+    pub(crate) fn sort(&mut self) {
+        self.0.sort_by(|a, _, b, _| b.cmp(a));
+    }
+
+    pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut Release> {
+        self.0.values_mut()
     }
 }
 
@@ -20,6 +44,22 @@ impl Releases {
         self.0.get(version)
     }
 
+    /// Returns a mutable reference to the release matching the requested `version` if it exists
+    /// in the changelog.
+    pub fn get_version_mut(&mut self, version: &ReleaseVersion) -> Option<&mut Release> {
+        self.0.get_mut(version)
+    }
+
+    /// Returns the release matching `version` if it is a valid version that exists in the
+    /// changelog.
+    #[must_use]
+    pub fn get(&self, version: &str) -> Option<&Release> {
+        version
+            .parse::<ReleaseVersion>()
+            .ok()
+            .and_then(|version| self.get_version(&version))
+    }
+
     /// Returns true if the requested `version` exists in the changelog.
     #[must_use]
     pub fn contains_version(&self, version: &ReleaseVersion) -> bool {
@@ -28,26 +68,243 @@ impl Releases {
 
     /// Returns an iterator over the version/release pairs
     #[must_use]
-    pub fn iter(&self) -> std::vec::IntoIter<(&ReleaseVersion, &Release)> {
+    pub fn iter(&self) -> ReleasesIter<'_> {
         self.into_iter()
     }
 }
 
+impl FromIterator<(ReleaseVersion, Release)> for Releases {
+    /// Collects the version/release pairs in order. A version that appears more than once keeps
+    /// its first position with the last release given for it.
+    fn from_iter<I: IntoIterator<Item = (ReleaseVersion, Release)>>(iter: I) -> Self {
+        Self(IndexMap::from_iter(iter))
+    }
+}
+
+impl Extend<(ReleaseVersion, Release)> for Releases {
+    /// Adds the releases after the existing ones. A release for a version that already exists
+    /// replaces it in its current position.
+    fn extend<I: IntoIterator<Item = (ReleaseVersion, Release)>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}
+
+// releases are keyed by their own version, so they are written as a list instead of a map
+#[cfg(feature = "json")]
+impl serde::Serialize for Releases {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.values())
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'de> serde::Deserialize<'de> for Releases {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Vec::<Release>::deserialize(deserializer)?
+            .into_iter()
+            .map(|release| (release.version.clone(), release))
+            .collect())
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Releases {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Releases".into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        Vec::<Release>::json_schema(generator)
+    }
+}
+
+// each release is keyed by its own version, so releases are generated and then collected
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Releases {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary_iter::<Release>()?
+            .map(|release| release.map(|release| (release.version.clone(), release)))
+            .collect()
+    }
+}
+
+impl Index<&ReleaseVersion> for Releases {
+    type Output = Release;
+
+    /// Returns the release matching `version`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the version does not exist in the changelog.
+    fn index(&self, version: &ReleaseVersion) -> &Self::Output {
+        self.get_version(version)
+            .unwrap_or_else(|| panic!("Release {version} does not exist in the changelog"))
+    }
+}
+
+impl Index<&str> for Releases {
+    type Output = Release;
+
+    /// Returns the release matching `version`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `version` is not a valid version or does not exist in the changelog.
+    fn index(&self, version: &str) -> &Self::Output {
+        self.get(version)
+            .unwrap_or_else(|| panic!("Release {version} does not exist in the changelog"))
+    }
+}
+
 impl IntoIterator for Releases {
     type Item = (ReleaseVersion, Release);
-    type IntoIter = std::vec::IntoIter<Self::Item>;
+    type IntoIter = ReleasesIntoIter;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter().collect::<Vec<_>>().into_iter()
+        ReleasesIntoIter(self.0.into_iter())
     }
 }
 
 impl<'a> IntoIterator for &'a Releases {
     type Item = (&'a ReleaseVersion, &'a Release);
 
-    type IntoIter = std::vec::IntoIter<Self::Item>;
+    type IntoIter = ReleasesIter<'a>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.iter().collect::<Vec<_>>().into_iter()
+        ReleasesIter(self.0.iter())
+    }
+}
+
+/// An iterator over the version/release pairs in [`Releases`].
+#[derive(Debug, Clone)]
+pub struct ReleasesIter<'a>(indexmap::map::Iter<'a, ReleaseVersion, Release>);
+
+impl<'a> Iterator for ReleasesIter<'a> {
+    type Item = (&'a ReleaseVersion, &'a Release);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for ReleasesIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl ExactSizeIterator for ReleasesIter<'_> {}
+
+/// An owning iterator over the version/release pairs in [`Releases`].
+#[derive(Debug)]
+pub struct ReleasesIntoIter(indexmap::map::IntoIter<ReleaseVersion, Release>);
+
+impl Iterator for ReleasesIntoIter {
+    type Item = (ReleaseVersion, Release);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for ReleasesIntoIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl ExactSizeIterator for ReleasesIntoIter {}
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use crate::{Changelog, ReleaseVersion, Releases};
+
+    const CHANGELOG: &str = "\
+# Changelog
+
+## [Unreleased]
+
+## [1.2.3] - 2024-01-01
+
+## [1.2.2] - 2023-12-01
+";
+
+    #[test]
+    fn test_get() {
+        let changelog: Changelog = CHANGELOG.parse().unwrap();
+        assert_eq!(
+            changelog.releases.get("1.2.3").unwrap().date.to_string(),
+            "2024-01-01"
+        );
+        assert!(changelog.releases.get("1.2.4").is_none());
+        assert!(changelog.releases.get("not a version").is_none());
+    }
+
+    #[test]
+    fn test_from_iter_and_extend() {
+        let changelog: Changelog = CHANGELOG.parse().unwrap();
+        let mut releases: Releases = changelog
+            .releases
+            .clone()
+            .into_iter()
+            .filter(|(version, _)| version.to_string() == "1.2.3")
+            .collect();
+        releases.extend(changelog.releases.clone());
+
+        assert_eq!(
+            releases
+                .iter()
+                .map(|(version, _)| version.to_string())
+                .collect::<Vec<_>>(),
+            vec!["1.2.3", "1.2.2"]
+        );
+        assert_eq!(releases, changelog.releases);
+    }
+
+    #[test]
+    fn test_index() {
+        let changelog: Changelog = CHANGELOG.parse().unwrap();
+        assert_eq!(changelog.releases["1.2.2"].date.to_string(), "2023-12-01");
+        let version: ReleaseVersion = "1.2.3".parse().unwrap();
+        assert_eq!(changelog.releases[&version].version, version);
+    }
+
+    #[test]
+    #[should_panic(expected = "Release 9.9.9 does not exist in the changelog")]
+    fn test_index_missing_release() {
+        let changelog: Changelog = CHANGELOG.parse().unwrap();
+        let _ = &changelog.releases["9.9.9"];
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_arbitrary_releases_are_keyed_by_version() {
+        let bytes = (1..=255).cycle().take(4096).collect::<Vec<u8>>();
+        let releases = arbitrary::Unstructured::new(&bytes)
+            .arbitrary::<Releases>()
+            .unwrap();
+        assert!(releases.iter().count() > 0);
+        for (version, release) in &releases {
+            assert_eq!(version, &release.version);
+            assert!(release
+                .version
+                .to_string()
+                .parse::<ReleaseVersion>()
+                .is_ok());
+            assert!(release
+                .date
+                .to_string()
+                .parse::<crate::ReleaseDate>()
+                .is_ok());
+        }
     }
 }
@@ -0,0 +1,157 @@
+use crate::changelog::CHANGELOG_HEADER;
+use crate::{Changelog, Changes};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fmt::{Display, Formatter};
+
+lazy_static! {
+    static ref MARKDOWN_LINK: Regex =
+        Regex::new(r"\[(?P<text>[^\]]+)]\((?P<url>[^)\s]+)\)").expect("Should be a valid regex");
+    static ref MARKDOWN_CODE: Regex =
+        Regex::new(r"`(?P<code>[^`]+)`").expect("Should be a valid regex");
+}
+
+pub(crate) struct Rst<'a>(pub(crate) &'a Changelog);
+
+impl Display for Rst<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let changelog = self.0;
+
+        write_title(f, "Changelog")?;
+        let preamble = CHANGELOG_HEADER
+            .trim_start_matches("# Changelog")
+            .trim_start();
+        write!(f, "\n{}\n", markdown_to_rst(preamble))?;
+
+        let unreleased_title = if changelog.unreleased.link.is_some() {
+            "`Unreleased`_"
+        } else {
+            "Unreleased"
+        };
+        write_section(f, unreleased_title, &changelog.unreleased.changes)?;
+
+        for (version, release) in &changelog.releases {
+            let version = if release.link.is_some() {
+                format!("`{version}`_")
+            } else {
+                version.to_string()
+            };
+            let tag = release
+                .tag
+                .as_ref()
+                .map(|tag| format!(" [{tag}]"))
+                .unwrap_or_default();
+            let title = format!("{version} - {}{tag}", release.date);
+            write_section(f, &title, &release.changes)?;
+        }
+
+        let mut link_targets = vec![];
+        if let Some(link) = &changelog.unreleased.link {
+            link_targets.push(format!(".. _Unreleased: {link}"));
+        }
+        for (version, release) in &changelog.releases {
+            if let Some(link) = &release.link {
+                link_targets.push(format!(".. _{version}: {link}"));
+            }
+        }
+        if !link_targets.is_empty() {
+            write!(f, "\n{}\n", link_targets.join("\n"))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_title(f: &mut Formatter<'_>, title: &str) -> std::fmt::Result {
+    let line = underline(title, '=');
+    writeln!(f, "{line}\n{title}\n{line}")
+}
+
+fn write_section(f: &mut Formatter<'_>, title: &str, changes: &Changes) -> std::fmt::Result {
+    write!(f, "\n{title}\n{}\n", underline(title, '='))?;
+    for (change_group, items) in changes {
+        let change_group = change_group.to_string();
+        write!(f, "\n{change_group}\n{}\n\n", underline(&change_group, '-'))?;
+        for item in items {
+            writeln!(f, "- {}", markdown_to_rst(item))?;
+        }
+    }
+    Ok(())
+}
+
+fn underline(title: &str, character: char) -> String {
+    character.to_string().repeat(title.chars().count())
+}
+
+// only the inline markdown commonly found in changelog entries is converted (links and code spans)
+fn markdown_to_rst(markdown: &str) -> String {
+    let rst = MARKDOWN_CODE.replace_all(markdown, "``$code``");
+    MARKDOWN_LINK
+        .replace_all(&rst, "`$text <$url>`_")
+        .to_string()
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_to_rst() {
+        let changelog: Changelog = "\
+# Changelog
+
+## [Unreleased]
+
+### Added
+
+- Added `--json` flag, see [docs](https://example.com/docs)
+
+## [1.0.0] - 2023-01-01 [YANKED]
+
+### Fixed
+
+- Fixed feature X
+- Fixed feature Y
+
+[unreleased]: https://github.com/my-org/my-project/compare/v1.0.0...HEAD
+[1.0.0]: https://github.com/my-org/my-project/releases/tag/v1.0.0
+"
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            changelog.to_rst(),
+            "\
+=========
+Changelog
+=========
+
+All notable changes to this project will be documented in this file.
+
+The format is based on `Keep a Changelog <https://keepachangelog.com/en/1.1.0/>`_,
+and this project adheres to `Semantic Versioning <https://semver.org/spec/v2.0.0.html>`_.
+
+`Unreleased`_
+=============
+
+Added
+-----
+
+- Added ``--json`` flag, see `docs <https://example.com/docs>`_
+
+`1.0.0`_ - 2023-01-01 [YANKED]
+==============================
+
+Fixed
+-----
+
+- Fixed feature X
+- Fixed feature Y
+
+.. _Unreleased: https://github.com/my-org/my-project/compare/v1.0.0...HEAD
+.. _1.0.0: https://github.com/my-org/my-project/releases/tag/v1.0.0
+"
+        );
+    }
+}
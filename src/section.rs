@@ -0,0 +1,54 @@
+use crate::{ParseVersionError, ReleaseVersion};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Identifies a section of the changelog, either the Unreleased section or a specific release.
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub enum Section {
+    /// The Unreleased section.
+    Unreleased,
+    /// The section for the release with the given version.
+    Release(ReleaseVersion),
+}
+
+impl Display for Section {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Section::Unreleased => write!(f, "Unreleased"),
+            Section::Release(version) => write!(f, "{version}"),
+        }
+    }
+}
+
+impl FromStr for Section {
+    type Err = ParseVersionError;
+
+    /// Parses `unreleased`, in any case, as the Unreleased section and anything else as the
+    /// version of a release.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.eq_ignore_ascii_case("unreleased") {
+            Ok(Section::Unreleased)
+        } else {
+            value.parse().map(Section::Release)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "Unreleased".parse::<Section>().unwrap(),
+            Section::Unreleased
+        );
+        assert_eq!(
+            "1.2.0".parse::<Section>().unwrap(),
+            Section::Release("1.2.0".parse().unwrap())
+        );
+        assert!("next".parse::<Section>().is_err());
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{ChangeGroup, Changelog, ReleaseDate, ReleaseTag};
+use indexmap::IndexMap;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // a GitHub username mentioned in an entry, but not the domain of an email address
+    static ref MENTION: Regex = Regex::new(r"(?:^|[^\w.@])@([A-Za-z0-9](?:[A-Za-z0-9-]{0,38}))\b")
+        .expect("Should be a valid regex");
+}
+
+/// Numbers describing the releases in a changelog, returned by [`Changelog::statistics`].
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "json", derive(serde::Serialize))]
+pub struct ChangelogStatistics {
+    /// The number of releases.
+    pub releases: usize,
+    /// The number of releases tagged as [yanked](ReleaseTag::Yanked).
+    pub yanked_releases: usize,
+    /// The date of the earliest release.
+    pub first_release: Option<ReleaseDate>,
+    /// The date of the most recent release.
+    pub latest_release: Option<ReleaseDate>,
+    /// The number of entries in each change group across all releases, including groups without
+    /// any entries.
+    pub entries: IndexMap<ChangeGroup, usize>,
+    /// The number of days between each release and the one before it, from the oldest release to
+    /// the newest.
+    pub days_between_releases: Vec<i64>,
+    /// The GitHub usernames mentioned in entries, such as `@octocat`, in the order they first
+    /// appear.
+    pub contributors: Vec<String>,
+}
+
+impl ChangelogStatistics {
+    /// Returns the mean number of days between releases, or `None` if there are fewer than two
+    /// releases.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn average_days_between_releases(&self) -> Option<f64> {
+        if self.days_between_releases.is_empty() {
+            return None;
+        }
+        let total = self.days_between_releases.iter().sum::<i64>();
+        Some(total as f64 / self.days_between_releases.len() as f64)
+    }
+}
+
+pub(crate) fn statistics(changelog: &Changelog) -> ChangelogStatistics {
+    let mut entries = [
+        ChangeGroup::Added,
+        ChangeGroup::Changed,
+        ChangeGroup::Deprecated,
+        ChangeGroup::Removed,
+        ChangeGroup::Fixed,
+        ChangeGroup::Security,
+    ]
+    .into_iter()
+    .map(|change_group| (change_group, 0))
+    .collect::<IndexMap<_, _>>();
+    let mut contributors = vec![];
+    let mut dates = vec![];
+    let mut yanked_releases = 0;
+
+    for (_, release) in &changelog.releases {
+        dates.push(&release.date);
+        if release.tag == Some(ReleaseTag::Yanked) {
+            yanked_releases += 1;
+        }
+        for (change_group, items) in &release.changes {
+            *entries.entry(change_group.clone()).or_default() += items.len();
+            for captures in items.iter().flat_map(|item| MENTION.captures_iter(item)) {
+                let username = captures[1].to_string();
+                if !contributors.contains(&username) {
+                    contributors.push(username);
+                }
+            }
+        }
+    }
+
+    // by date rather than changelog order, since releases aren't guaranteed to be ordered
+    dates.sort();
+    ChangelogStatistics {
+        releases: changelog.releases.iter().len(),
+        yanked_releases,
+        first_release: dates.first().map(|date| (*date).clone()),
+        latest_release: dates.last().map(|date| (*date).clone()),
+        entries,
+        days_between_releases: dates
+            .windows(2)
+            .map(|pair| pair[1].days_since(pair[0]))
+            .collect(),
+        contributors,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use crate::{ChangeGroup, Changelog};
+
+    #[test]
+    fn test_statistics() {
+        let changelog: Changelog = "\
+# Changelog
+
+## [Unreleased]
+
+### Added
+
+- Added W by @unreleased
+
+## [1.2.0] - 2024-03-01 [YANKED]
+
+### Added
+
+- Added X by @octocat
+- Added Y, reported by someone@example.com
+
+## [1.1.0] - 2024-01-01
+
+### Fixed
+
+- Fixed Z (@hubot, @octocat)
+
+## [1.0.0] - 2023-12-01
+"
+        .parse()
+        .unwrap();
+
+        let statistics = changelog.statistics();
+        assert_eq!(statistics.releases, 3);
+        assert_eq!(statistics.yanked_releases, 1);
+        assert_eq!(
+            statistics.first_release.as_ref().unwrap().to_string(),
+            "2023-12-01"
+        );
+        assert_eq!(
+            statistics.latest_release.as_ref().unwrap().to_string(),
+            "2024-03-01"
+        );
+        assert_eq!(statistics.entries[&ChangeGroup::Added], 2);
+        assert_eq!(statistics.entries[&ChangeGroup::Fixed], 1);
+        assert_eq!(statistics.entries[&ChangeGroup::Removed], 0);
+        assert_eq!(statistics.days_between_releases, vec![31, 60]);
+        assert_eq!(statistics.average_days_between_releases(), Some(45.5));
+        assert_eq!(statistics.contributors, vec!["octocat", "hubot"]);
+
+        let statistics = Changelog::default().statistics();
+        assert_eq!(statistics.releases, 0);
+        assert_eq!(statistics.average_days_between_releases(), None);
+    }
+}
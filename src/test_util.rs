@@ -0,0 +1,124 @@
+//! [proptest](https://docs.rs/proptest) strategies for generating changelogs.
+//!
+//! Every [`Changelog`] produced by [`changelog`] survives being written and parsed again, so
+//! crates that build changelogs from their own data can check the same round-trip property:
+//!
+//! ```
+//! use keep_a_changelog_file::test_util;
+//! use keep_a_changelog_file::Changelog;
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(changelog in test_util::changelog())| {
+//!     prop_assert_eq!(changelog.to_string().parse::<Changelog>().unwrap(), changelog);
+//! });
+//! ```
+
+use crate::{
+    ChangeGroup, Changelog, Changes, LineEnding, Release, ReleaseDate, ReleaseLink, ReleaseTag,
+    ReleaseVersion, Releases, Unreleased,
+};
+use proptest::collection::vec;
+use proptest::option;
+use proptest::prelude::*;
+
+/// Generates release versions of the form `MAJOR.MINOR.PATCH`.
+pub fn release_version() -> impl Strategy<Value = ReleaseVersion> {
+    (0..100u32, 0..100u32, 0..100u32).prop_map(|(major, minor, patch)| {
+        format!("{major}.{minor}.{patch}")
+            .parse()
+            .expect("Should be a valid version")
+    })
+}
+
+/// Generates release dates between 1970 and 2099.
+pub fn release_date() -> impl Strategy<Value = ReleaseDate> {
+    (1970..2100u32, 1..=12u32, 1..=28u32).prop_map(|(year, month, day)| {
+        format!("{year:04}-{month:02}-{day:02}")
+            .parse()
+            .expect("Should be a valid date")
+    })
+}
+
+/// Generates links to a release on GitHub.
+pub fn release_link() -> impl Strategy<Value = ReleaseLink> {
+    "[a-z][a-z0-9-]{0,15}".prop_map(|repository| {
+        format!("https://github.com/org/{repository}/releases")
+            .parse()
+            .expect("Should be a valid link")
+    })
+}
+
+/// Generates any of the change groups.
+pub fn change_group() -> impl Strategy<Value = ChangeGroup> {
+    prop_oneof![
+        Just(ChangeGroup::Added),
+        Just(ChangeGroup::Changed),
+        Just(ChangeGroup::Deprecated),
+        Just(ChangeGroup::Removed),
+        Just(ChangeGroup::Fixed),
+        Just(ChangeGroup::Security),
+    ]
+}
+
+/// Generates entries that are a single line of words, some starting with emphasis or with
+/// punctuation that could be mistaken for a list marker.
+pub fn entry() -> impl Strategy<Value = String> {
+    "(\\*[A-Za-z]{1,8}\\*: |\\*\\*[A-Za-z]{1,8}\\*\\* |_[A-Za-z]{1,8}_ |-[0-9] |-)?[A-Za-z][A-Za-z0-9]{0,9}( [A-Za-z0-9]{1,10}){0,7}"
+}
+
+/// Generates changes with up to four groups of one to four entries each.
+pub fn changes() -> impl Strategy<Value = Changes> {
+    vec((change_group(), vec(entry(), 1..5)), 0..5).prop_map(|groups| groups.into_iter().collect())
+}
+
+/// Generates the unreleased section of a changelog.
+pub fn unreleased() -> impl Strategy<Value = Unreleased> {
+    (option::of(release_link()), changes()).prop_map(|(link, changes)| Unreleased { link, changes })
+}
+
+/// Generates a release.
+pub fn release() -> impl Strategy<Value = Release> {
+    (
+        release_version(),
+        release_date(),
+        option::of(prop_oneof![
+            Just(ReleaseTag::Yanked),
+            Just(ReleaseTag::NoChanges)
+        ]),
+        option::of(release_link()),
+        changes(),
+    )
+        .prop_map(|(version, date, tag, link, changes)| Release {
+            version,
+            date,
+            tag,
+            link,
+            changes,
+        })
+}
+
+/// Generates up to five releases.
+pub fn releases() -> impl Strategy<Value = Releases> {
+    vec(release(), 0..6).prop_map(|releases| {
+        releases
+            .into_iter()
+            .map(|release| (release.version.clone(), release))
+            .collect()
+    })
+}
+
+/// Generates changelogs that are equal to themselves after being written and parsed again.
+pub fn changelog() -> impl Strategy<Value = Changelog> {
+    (
+        unreleased(),
+        releases(),
+        prop_oneof![Just(LineEnding::Lf), Just(LineEnding::CrLf)],
+    )
+        .prop_map(|(unreleased, releases, line_ending)| Changelog {
+            unreleased,
+            releases,
+            link_separator: None,
+            line_ending,
+            missing_unreleased_section: false,
+        })
+}
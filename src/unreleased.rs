@@ -1,10 +1,14 @@
 use crate::changes::Changes;
+use crate::release::heading_anchor;
 use crate::release_link::ReleaseLink;
 use crate::ChangeGroup;
 
 /// Tracks upcoming changes. You can move the Unreleased changes into a new [`Release`](struct@crate::release::Release)
 /// using [`promote_unreleased`](fn@crate::changelog::Changelog::promote_unreleased).
 #[derive(Debug, Eq, PartialEq, Default, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Unreleased {
     /// A link to all unreleased changes.
     pub link: Option<ReleaseLink>,
@@ -17,4 +21,10 @@ impl Unreleased {
     pub fn add(&mut self, change_group: ChangeGroup, item: impl Into<String>) {
         self.changes.add(change_group, item);
     }
+
+    /// Returns the anchor GitHub generates for the `## [Unreleased]` heading.
+    #[must_use]
+    pub fn anchor(&self) -> String {
+        heading_anchor("[Unreleased]")
+    }
 }
@@ -0,0 +1,426 @@
+use crate::{Changelog, Changes, ComplianceReport, ComplianceRule, ParseChangelogError, Section};
+use serde::Deserialize;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// A set of changelogs managed together, such as the per-component changelogs in a monorepo.
+///
+/// A workspace is described by a TOML manifest listing the changelogs it contains. Paths are
+/// relative to the manifest and each changelog can allow compliance rules that it should not be
+/// checked against, in addition to any rules allowed for the whole workspace:
+///
+/// ```toml
+/// allow = ["no-commit-log-dumps"]
+///
+/// [[changelogs]]
+/// path = "crates/parser/CHANGELOG.md"
+///
+/// [[changelogs]]
+/// name = "cli"
+/// path = "crates/cli/CHANGELOG.md"
+/// allow = ["linkable-versions"]
+/// ```
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Workspace {
+    members: Vec<WorkspaceMember>,
+}
+
+/// A changelog that belongs to a [`Workspace`].
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct WorkspaceMember {
+    /// The name of the component the changelog belongs to. Defaults to the name of the directory
+    /// containing the changelog.
+    pub name: String,
+    /// The path to the changelog.
+    pub path: PathBuf,
+    /// The compliance rules that are not checked for this changelog.
+    pub allow: Vec<ComplianceRule>,
+}
+
+/// The result of validating a single workspace member.
+#[derive(Debug)]
+pub struct MemberReport {
+    /// The workspace member that was validated.
+    pub member: WorkspaceMember,
+    /// The compliance report for the changelog or the error that prevented it from being checked.
+    pub result: Result<ComplianceReport, WorkspaceError>,
+}
+
+impl MemberReport {
+    /// Returns true if the changelog could be read and has no compliance findings.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.result
+            .as_ref()
+            .is_ok_and(ComplianceReport::is_compliant)
+    }
+}
+
+/// The results of validating every changelog in a [`Workspace`].
+#[derive(Debug, Default)]
+pub struct WorkspaceReport(Vec<MemberReport>);
+
+impl WorkspaceReport {
+    /// Returns true if every changelog in the workspace is valid.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.0.iter().all(MemberReport::is_valid)
+    }
+
+    /// Returns the number of changelogs that are not valid.
+    #[must_use]
+    pub fn invalid_count(&self) -> usize {
+        self.0.iter().filter(|report| !report.is_valid()).count()
+    }
+
+    /// Returns an iterator over the report for each workspace member.
+    pub fn iter(&self) -> std::slice::Iter<'_, MemberReport> {
+        self.0.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a WorkspaceReport {
+    type Item = &'a MemberReport;
+    type IntoIter = std::slice::Iter<'a, MemberReport>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// The changes from one section of every changelog in a [`Workspace`], grouped by component.
+///
+/// Displaying the release notes renders them as a single markdown document with a heading for each
+/// component that has changes in the section.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct CombinedReleaseNotes {
+    /// The section the changes were taken from.
+    pub section: Section,
+    components: Vec<(String, Changes)>,
+}
+
+impl CombinedReleaseNotes {
+    /// Returns true if none of the components have changes in the section.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// Returns an iterator over the component name/changes pairs in manifest order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Changes)> {
+        self.components
+            .iter()
+            .map(|(name, changes)| (name.as_str(), changes))
+    }
+}
+
+impl Display for CombinedReleaseNotes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "# {}", self.section)?;
+        for (name, changes) in &self.components {
+            write!(f, "\n\n## {name}\n\n{changes}")?;
+        }
+        writeln!(f)
+    }
+}
+
+/// Errors that can occur while loading a workspace or its changelogs.
+#[derive(Debug, Error)]
+pub enum WorkspaceError {
+    /// The manifest or a changelog could not be read.
+    #[error("Could not read {0}\nError: {1}")]
+    Io(PathBuf, #[source] std::io::Error),
+    /// The manifest is not valid.
+    #[error("Invalid workspace manifest {0}\nError: {1}")]
+    InvalidManifest(PathBuf, String),
+    /// A changelog could not be parsed.
+    #[error("Could not parse changelog {0}\nError: {1}")]
+    ParseChangelog(PathBuf, #[source] ParseChangelogError),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    #[serde(default)]
+    allow: Vec<String>,
+    #[serde(default)]
+    changelogs: Vec<ManifestMember>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ManifestMember {
+    name: Option<String>,
+    path: PathBuf,
+    #[serde(default)]
+    allow: Vec<String>,
+}
+
+impl Workspace {
+    /// Loads the workspace described by the manifest at `manifest_path`.
+    pub fn load(manifest_path: impl AsRef<Path>) -> Result<Workspace, WorkspaceError> {
+        let manifest_path = manifest_path.as_ref();
+        let contents = fs::read_to_string(manifest_path)
+            .map_err(|e| WorkspaceError::Io(manifest_path.to_path_buf(), e))?;
+        let root = manifest_path.parent().unwrap_or(Path::new(""));
+        Workspace::from_manifest(&contents, root)
+            .map_err(|e| WorkspaceError::InvalidManifest(manifest_path.to_path_buf(), e))
+    }
+
+    fn from_manifest(contents: &str, root: &Path) -> Result<Workspace, String> {
+        let manifest: Manifest = toml::from_str(contents).map_err(|e| e.to_string())?;
+        let workspace_allow = parse_rules(&manifest.allow)?;
+
+        let members = manifest
+            .changelogs
+            .into_iter()
+            .map(|member| {
+                let path = root.join(&member.path);
+                let name = member.name.unwrap_or_else(|| {
+                    member
+                        .path
+                        .parent()
+                        .and_then(|parent| parent.file_name())
+                        .map_or_else(
+                            || member.path.display().to_string(),
+                            |name| name.to_string_lossy().to_string(),
+                        )
+                });
+                let mut allow = workspace_allow.clone();
+                allow.extend(parse_rules(&member.allow)?);
+                Ok(WorkspaceMember { name, path, allow })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Workspace { members })
+    }
+
+    /// Returns an iterator over the changelogs in the workspace.
+    pub fn members(&self) -> std::slice::Iter<'_, WorkspaceMember> {
+        self.members.iter()
+    }
+
+    /// Reads every changelog in the workspace.
+    #[must_use]
+    pub fn changelogs(&self) -> Vec<(&WorkspaceMember, Result<Changelog, WorkspaceError>)> {
+        self.members
+            .iter()
+            .map(|member| (member, member.read()))
+            .collect()
+    }
+
+    /// Parses and checks every changelog in the workspace against the compliance rules that
+    /// aren't allowed by the manifest.
+    #[must_use]
+    pub fn validate(&self) -> WorkspaceReport {
+        WorkspaceReport(
+            self.members
+                .iter()
+                .map(|member| MemberReport {
+                    member: member.clone(),
+                    result: member.read().map(|changelog| {
+                        let mut report = changelog.compliance_report();
+                        report.allow(&member.allow);
+                        report
+                    }),
+                })
+                .collect(),
+        )
+    }
+
+    /// Merges the changes from `section` of every changelog in the workspace into a single set of
+    /// release notes grouped by component.
+    ///
+    /// Components without changes in the section, including those that don't have the requested
+    /// release, are left out.
+    pub fn combined_release_notes(
+        &self,
+        section: &Section,
+    ) -> Result<CombinedReleaseNotes, WorkspaceError> {
+        let mut components = vec![];
+        for member in &self.members {
+            let changelog = member.read()?;
+            let changes = match section {
+                Section::Unreleased => Some(changelog.unreleased.changes),
+                Section::Release(version) => changelog
+                    .releases
+                    .get_version(version)
+                    .map(|release| release.changes.clone()),
+            };
+            if let Some(changes) = changes.filter(|changes| !changes.is_empty()) {
+                components.push((member.name.clone(), changes));
+            }
+        }
+        Ok(CombinedReleaseNotes {
+            section: section.clone(),
+            components,
+        })
+    }
+}
+
+impl WorkspaceMember {
+    /// Reads and parses the changelog for this member.
+    pub fn read(&self) -> Result<Changelog, WorkspaceError> {
+        fs::read_to_string(&self.path)
+            .map_err(|e| WorkspaceError::Io(self.path.clone(), e))?
+            .parse()
+            .map_err(|e| WorkspaceError::ParseChangelog(self.path.clone(), e))
+    }
+}
+
+fn parse_rules(rules: &[String]) -> Result<Vec<ComplianceRule>, String> {
+    rules
+        .iter()
+        .map(|rule| {
+            rule.parse()
+                .map_err(|e: crate::ParseComplianceRuleError| e.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use tempfile::TempDir;
+
+    // removed when dropped, even if the test panics
+    fn workspace_dir(name: &str, files: &[(&str, &str)]) -> TempDir {
+        let dir = tempfile::Builder::new()
+            .prefix(&format!("keep_a_changelog_file-{name}-"))
+            .tempdir()
+            .unwrap();
+        for (file_name, contents) in files {
+            let path = dir.path().join(file_name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_validate_workspace() {
+        let dir = workspace_dir(
+            "workspace",
+            &[
+                (
+                    "changelogs.toml",
+                    r#"
+allow = ["no-commit-log-dumps"]
+
+[[changelogs]]
+path = "parser/CHANGELOG.md"
+
+[[changelogs]]
+name = "command-line"
+path = "cli/CHANGELOG.md"
+allow = ["linkable-versions"]
+
+[[changelogs]]
+path = "missing/CHANGELOG.md"
+"#,
+                ),
+                (
+                    "parser/CHANGELOG.md",
+                    "# Changelog\n\n## [Unreleased]\n\n## [1.0.0] - 2023-01-01\n\n### Added\n\n- Added X\n",
+                ),
+                (
+                    "cli/CHANGELOG.md",
+                    "# Changelog\n\n## [Unreleased]\n\n## [1.0.0] - 2023-01-01\n\n### Fixed\n\n- fix: Y\n",
+                ),
+            ],
+        );
+
+        let workspace = Workspace::load(dir.path().join("changelogs.toml")).unwrap();
+        assert_eq!(
+            workspace
+                .members()
+                .map(|member| member.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["parser", "command-line", "missing"]
+        );
+
+        let report = workspace.validate();
+        assert!(!report.is_valid());
+        assert_eq!(report.invalid_count(), 2);
+
+        let reports = report.iter().collect::<Vec<_>>();
+        assert!(!reports[0].is_valid());
+        assert!(reports[1].is_valid());
+        assert!(matches!(reports[2].result, Err(WorkspaceError::Io(_, _))));
+    }
+
+    #[test]
+    fn test_combined_release_notes() {
+        let dir = workspace_dir(
+            "combined",
+            &[
+                (
+                    "changelogs.toml",
+                    "[[changelogs]]\npath = \"parser/CHANGELOG.md\"\n\n[[changelogs]]\npath = \"cli/CHANGELOG.md\"\n\n[[changelogs]]\npath = \"docs/CHANGELOG.md\"\n",
+                ),
+                (
+                    "parser/CHANGELOG.md",
+                    "# Changelog\n\n## [Unreleased]\n\n### Added\n\n- Added X\n\n### Fixed\n\n- Fixed Y\n\n## [1.0.0] - 2023-01-01\n\n### Added\n\n- Initial release\n",
+                ),
+                (
+                    "cli/CHANGELOG.md",
+                    "# Changelog\n\n## [Unreleased]\n\n### Changed\n\n- Changed Z\n",
+                ),
+                ("docs/CHANGELOG.md", "# Changelog\n\n## [Unreleased]\n"),
+            ],
+        );
+        let workspace = Workspace::load(dir.path().join("changelogs.toml")).unwrap();
+
+        let notes = workspace
+            .combined_release_notes(&Section::Unreleased)
+            .unwrap();
+        assert_eq!(
+            notes.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            vec!["parser", "cli"]
+        );
+        assert_eq!(
+            notes.to_string(),
+            "\
+# Unreleased
+
+## parser
+
+### Added
+
+- Added X
+
+### Fixed
+
+- Fixed Y
+
+## cli
+
+### Changed
+
+- Changed Z
+"
+        );
+
+        let notes = workspace
+            .combined_release_notes(&Section::Release("1.0.0".parse().unwrap()))
+            .unwrap();
+        assert_eq!(
+            notes.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            vec!["parser"]
+        );
+
+        let notes = workspace
+            .combined_release_notes(&Section::Release("2.0.0".parse().unwrap()))
+            .unwrap();
+        assert!(notes.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_manifest() {
+        assert!(Workspace::from_manifest("allow = [\"not-a-rule\"]", Path::new("")).is_err());
+        assert!(Workspace::from_manifest("unknown = true", Path::new("")).is_err());
+    }
+}
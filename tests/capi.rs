@@ -0,0 +1,95 @@
+#![allow(clippy::unwrap_used)]
+#![allow(unused_crate_dependencies)]
+#![allow(missing_docs)]
+#![cfg(all(feature = "capi", unix))]
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+const PROGRAM: &str = r###"
+#include <stdio.h>
+#include "keep_a_changelog_file.h"
+
+int main(void) {
+    KaclChangelog *changelog = kacl_parse("# Changelog\n\n## [Unreleased]\n\n### Added\n\n* Added X\n");
+    if (changelog == NULL) {
+        return 1;
+    }
+    char *markdown = kacl_to_string(changelog);
+    printf("%s", markdown);
+    kacl_free_string(markdown);
+    kacl_free(changelog);
+
+    if (kacl_parse("## Not a release header\n") != NULL || kacl_parse(NULL) != NULL) {
+        return 1;
+    }
+    char *diagnostics = kacl_diagnostics("## Not a release header\n");
+    printf("%s\n", diagnostics);
+    kacl_free_string(diagnostics);
+    return 0;
+}
+"###;
+
+// the shared library is only built on demand, so it's built into a target directory of its own
+// and the header is checked by compiling and running a C program against both
+#[test]
+fn c_program() {
+    let target = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("capi");
+    let output = Command::new(env!("CARGO"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args([
+            "rustc",
+            "--lib",
+            "--crate-type",
+            "cdylib",
+            "--features",
+            "capi",
+        ])
+        .arg("--target-dir")
+        .arg(&target)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let library_dir = target.join("debug");
+    let library = library_dir.join(format!(
+        "{}keep_a_changelog_file{}",
+        std::env::consts::DLL_PREFIX,
+        std::env::consts::DLL_SUFFIX
+    ));
+    assert!(library.is_file(), "{} wasn't built", library.display());
+
+    let temp = tempfile::tempdir().unwrap();
+    let source = temp.path().join("main.c");
+    let program = temp.path().join("main");
+    fs::write(&source, PROGRAM).unwrap();
+    let include = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("include");
+    let output = Command::new(std::env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg("-I")
+        .arg(include)
+        .arg(&source)
+        .arg(&library)
+        .arg(format!("-Wl,-rpath,{}", library_dir.display()))
+        .arg("-o")
+        .arg(&program)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = Command::new(&program).output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let (markdown, diagnostics) = stdout.split_once("\n[").unwrap();
+    assert!(markdown.ends_with("## [Unreleased]\n\n### Added\n\n- Added X"));
+    let diagnostics: serde_json::Value = serde_json::from_str(&format!("[{diagnostics}")).unwrap();
+    assert_eq!(diagnostics[0]["severity"], "error");
+    assert_eq!(diagnostics[0]["line"], 1);
+}
@@ -0,0 +1,1721 @@
+#![allow(clippy::unwrap_used)]
+#![allow(unused_crate_dependencies)]
+#![allow(missing_docs)]
+#![cfg(feature = "cli")]
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use tempfile::TempDir;
+
+// removed when dropped, even if the test panics
+fn temp_dir(name: &str) -> TempDir {
+    tempfile::Builder::new()
+        .prefix(&format!("keep_a_changelog_file-cli-{name}-"))
+        .tempdir()
+        .unwrap()
+}
+
+fn keep_a_changelog(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_keep_a_changelog"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn keep_a_changelog_with_stdin(dir: &Path, args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_keep_a_changelog"))
+        .current_dir(dir)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+// the binary is put on the PATH for hooks that run it
+fn git(dir: &Path, args: &[&str]) -> Output {
+    let bin_dir = PathBuf::from(env!("CARGO_BIN_EXE_keep_a_changelog"))
+        .parent()
+        .unwrap()
+        .to_path_buf();
+    let path = std::env::join_paths(
+        std::iter::once(bin_dir).chain(std::env::split_paths(&std::env::var_os("PATH").unwrap())),
+    )
+    .unwrap();
+    Command::new("git")
+        .current_dir(dir)
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .env("PATH", path)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn init_add_release_and_show() {
+    let temp = temp_dir("init-add-release-and-show");
+    let dir = temp.path();
+
+    assert!(keep_a_changelog(dir, &["init"]).status.success());
+    assert_eq!(
+        keep_a_changelog(dir, &["init"]).status.code(),
+        Some(1),
+        "init should not overwrite an existing changelog"
+    );
+
+    let output = keep_a_changelog(
+        dir,
+        &["add", "--group", "fixed", "Fixed the flux capacitor"],
+    );
+    assert!(output.status.success());
+
+    let output = keep_a_changelog(
+        dir,
+        &["release", "--version", "1.0.0", "--date", "2024-01-01"],
+    );
+    assert!(output.status.success());
+
+    let changelog = fs::read_to_string(dir.join("CHANGELOG.md")).unwrap();
+    assert!(
+        changelog.contains("## [1.0.0] - 2024-01-01\n\n### Fixed\n\n- Fixed the flux capacitor\n")
+    );
+
+    let output = keep_a_changelog(dir, &["show", "1.0.0"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "### Fixed\n\n- Fixed the flux capacitor\n");
+
+    assert_eq!(
+        keep_a_changelog(dir, &["show", "2.0.0"]).status.code(),
+        Some(1)
+    );
+}
+
+#[test]
+fn validate_and_fmt() {
+    let temp = temp_dir("validate-and-fmt");
+    let dir = temp.path();
+
+    assert_eq!(keep_a_changelog(dir, &["validate"]).status.code(), Some(3));
+
+    fs::write(dir.join("CHANGELOG.md"), "## Not a release header\n").unwrap();
+    assert_eq!(keep_a_changelog(dir, &["validate"]).status.code(), Some(1));
+
+    fs::write(
+        dir.join("changes.md"),
+        "# Changelog\n\n## [Unreleased]\n\n### Added\n\n* Added X\n",
+    )
+    .unwrap();
+    assert!(keep_a_changelog(dir, &["--file", "changes.md", "validate"])
+        .status
+        .success());
+    assert!(keep_a_changelog(dir, &["fmt", "--file", "changes.md"])
+        .status
+        .success());
+    assert!(fs::read_to_string(dir.join("changes.md"))
+        .unwrap()
+        .ends_with("### Added\n\n- Added X\n"));
+}
+
+#[test]
+fn init_with_path_and_force() {
+    let temp = temp_dir("init-with-path-and-force");
+    let dir = temp.path();
+
+    assert!(keep_a_changelog(dir, &["init", "--path", "docs.md"])
+        .status
+        .success());
+    let changelog = fs::read_to_string(dir.join("docs.md")).unwrap();
+    assert!(changelog.starts_with("# Changelog\n\nAll notable changes"));
+    assert!(changelog.ends_with("## [Unreleased]\n"));
+
+    fs::write(dir.join("docs.md"), "existing").unwrap();
+    let output = keep_a_changelog(dir, &["init", "--path", "docs.md"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Use --force to overwrite it"));
+    assert_eq!(fs::read_to_string(dir.join("docs.md")).unwrap(), "existing");
+
+    assert!(
+        keep_a_changelog(dir, &["init", "--path", "docs.md", "--force"])
+            .status
+            .success()
+    );
+    assert_eq!(fs::read_to_string(dir.join("docs.md")).unwrap(), changelog);
+}
+
+#[test]
+fn validate_diagnostics() {
+    let temp = temp_dir("validate-diagnostics");
+    let dir = temp.path();
+
+    fs::write(dir.join("CHANGELOG.md"), "## Not a release header\n").unwrap();
+    let output = keep_a_changelog(dir, &["validate"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).starts_with("CHANGELOG.md:1:1: [error] Release header did not match"));
+
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n## [1.0.0] - 2023-01-01\n\n### Fixed\n\n- Fixed Y\n",
+    )
+    .unwrap();
+    let output = keep_a_changelog(dir, &["validate"]);
+    assert!(output.status.success());
+    assert!(stdout(&output)
+        .contains("CHANGELOG.md:5:1: [linkable-versions] Release has no link\n    See: "));
+
+    let output = keep_a_changelog(dir, &["validate", "--warnings-as-errors"]);
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = keep_a_changelog(dir, &["validate", "--error-format", "short"]);
+    assert!(stdout(&output).contains(
+        "CHANGELOG.md:5:1: warning[linkable-versions]: Release has no link; See: https://keepachangelog.com/en/1.1.0/#how\n"
+    ));
+
+    let output = keep_a_changelog(
+        dir,
+        &[
+            "validate",
+            "--error-format",
+            "sarif",
+            "--report",
+            "report.json",
+        ],
+    );
+    let sarif: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(
+        sarif["runs"][0]["results"][0]["ruleId"],
+        "linkable-versions"
+    );
+    let report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(dir.join("report.json")).unwrap()).unwrap();
+    assert_eq!(report["path"], "CHANGELOG.md");
+
+    let output = keep_a_changelog(dir, &["validate", "--error-format", "github"]);
+    assert!(stdout(&output).contains(
+        "::warning file=CHANGELOG.md,line=5,col=1,endLine=5,endColumn=24,title=linkable-versions::Release has no link%0ASee: https://keepachangelog.com/en/1.1.0/#how\n"
+    ));
+
+    let output = keep_a_changelog(dir, &["validate", "--error-format", "human"]);
+    let stdout = stdout(&output);
+    assert!(stdout.contains("warning[linkable-versions]"));
+    assert!(stdout.contains(" ## [1.0.0] - 2023-01-01\n"));
+    assert!(!stdout.contains('\x1b'), "no color without a terminal");
+}
+
+// `script` gives the command a terminal for stderr while stdout is redirected to a file, which
+// must only get the plain diagnostics
+#[cfg(target_os = "linux")]
+#[test]
+fn validate_redirected_from_terminal() {
+    let temp = temp_dir("validate-redirected");
+    let dir = temp.path();
+    fs::write(dir.join("CHANGELOG.md"), "## Not a release header\n").unwrap();
+
+    for command in ["validate", "lint"] {
+        let status = Command::new("script")
+            .current_dir(dir)
+            .args(["--quiet", "--return", "--command"])
+            .arg(format!(
+                "{} {command} > report.txt",
+                env!("CARGO_BIN_EXE_keep_a_changelog")
+            ))
+            .arg("/dev/null")
+            .stdin(Stdio::null())
+            .output()
+            .unwrap()
+            .status;
+        assert_eq!(status.code(), Some(1));
+        let report = fs::read_to_string(dir.join("report.txt")).unwrap();
+        assert!(
+            report.starts_with("CHANGELOG.md:1:1: [error] Release header did not match"),
+            "{report}"
+        );
+        assert!(!report.contains('\x1b'));
+    }
+}
+
+#[test]
+fn add_entries() {
+    let temp = temp_dir("add-entries");
+    let dir = temp.path();
+    assert!(keep_a_changelog(dir, &["init"]).status.success());
+
+    let output = keep_a_changelog(
+        dir,
+        &[
+            "add",
+            "-g",
+            "Added",
+            "--",
+            "- Added a --json flag",
+            "Added a --quiet flag",
+        ],
+    );
+    assert!(output.status.success());
+    let output = keep_a_changelog(
+        dir,
+        &["add", "--group", "security", "Patched CVE-2024-0001"],
+    );
+    assert!(output.status.success());
+    assert!(fs::read_to_string(dir.join("CHANGELOG.md")).unwrap().ends_with(
+        "## [Unreleased]\n\n### Added\n\n- Added a --json flag\n- Added a --quiet flag\n\n### Security\n\n- Patched CVE-2024-0001\n"
+    ));
+
+    assert_eq!(
+        keep_a_changelog(dir, &["add", "--group", "fixed", " "])
+            .status
+            .code(),
+        Some(2)
+    );
+    assert_eq!(
+        keep_a_changelog(dir, &["add", "--group", "improved", "X"])
+            .status
+            .code(),
+        Some(2)
+    );
+    // without entries it only prompts in a terminal
+    assert_eq!(
+        keep_a_changelog_with_stdin(dir, &["add"], "5\nFixed X\n\n")
+            .status
+            .code(),
+        Some(2)
+    );
+
+    // a release with prose in it would lose its change groups by being written back
+    let prose = "# Changelog\n\n## [Unreleased]\n\n## [1.0.0] - 2024-01-01\n\nThis release requires Rust 1.80.\n\n### Added\n\n- Added X\n";
+    fs::write(dir.join("CHANGELOG.md"), prose).unwrap();
+    let output = keep_a_changelog(dir, &["add", "-g", "fixed", "Fixed Y"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("CHANGELOG.md:7:1 warning: Content isn't part of a change group"));
+    assert_eq!(fs::read_to_string(dir.join("CHANGELOG.md")).unwrap(), prose);
+    let output =
+        keep_a_changelog_with_stdin(dir, &["-f", "-", "add", "-g", "fixed", "Fixed Y"], prose);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "");
+}
+
+#[test]
+fn release_with_tag_and_links() {
+    let temp = temp_dir("release-with-tag-and-links");
+    let dir = temp.path();
+    assert!(keep_a_changelog(dir, &["init"]).status.success());
+    assert!(keep_a_changelog(dir, &["add", "-g", "added", "Added X"])
+        .status
+        .success());
+
+    let output = keep_a_changelog(
+        dir,
+        &[
+            "release",
+            "--version",
+            "1.0.0",
+            "--date",
+            "2024-01-01",
+            "--auto-link",
+            "https://github.com/org/repo",
+        ],
+    );
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "### Added\n\n- Added X\n");
+
+    let output = keep_a_changelog(
+        dir,
+        &[
+            "release",
+            "--version",
+            "1.0.1",
+            "--date",
+            "2024-01-02",
+            "--tag",
+            "no-changes",
+            "--link",
+            "https://github.com/org/repo/compare/v1.0.0...v1.0.1",
+        ],
+    );
+    assert!(output.status.success());
+
+    let changelog = fs::read_to_string(dir.join("CHANGELOG.md")).unwrap();
+    assert!(changelog.contains("## [1.0.1] - 2024-01-02 [NO CHANGES]\n"));
+    assert!(changelog.contains(
+        "[1.0.1]: https://github.com/org/repo/compare/v1.0.0...v1.0.1\n[1.0.0]: https://github.com/org/repo/releases/tag/v1.0.0\n"
+    ));
+
+    assert_eq!(
+        keep_a_changelog(dir, &["release", "--version", "1.0.1"])
+            .status
+            .code(),
+        Some(1)
+    );
+}
+
+#[test]
+fn show_formats() {
+    let temp = temp_dir("show-formats");
+    let dir = temp.path();
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n### Added\n\n- Added X\n\n## [1.2.3] - 2024-01-01 [YANKED]\n\n### Added\n\n- Added Y\n\n### Fixed\n\n- Fixed Z\n",
+    )
+    .unwrap();
+
+    let output = keep_a_changelog(dir, &["show", "1.2.3"]);
+    assert_eq!(
+        stdout(&output),
+        "### Added\n\n- Added Y\n\n### Fixed\n\n- Fixed Z\n"
+    );
+
+    let output = keep_a_changelog(dir, &["show", "1.2.3", "--format", "text"]);
+    assert_eq!(
+        stdout(&output),
+        "Added:\n  * Added Y\n\nFixed:\n  * Fixed Z\n"
+    );
+
+    let output = keep_a_changelog(dir, &["show", "1.2.3", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(json["version"], "1.2.3");
+    assert_eq!(json["tag"], "Yanked");
+    assert_eq!(json["changes"]["Fixed"][0], "Fixed Z");
+
+    let output = keep_a_changelog(dir, &["show", "unreleased", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(json["changes"]["Added"][0], "Added X");
+}
+
+#[test]
+fn latest_version() {
+    let temp = temp_dir("latest-version");
+    let dir = temp.path();
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n## [2.0.0-rc.1] - 2024-02-01\n\n## [1.1.0] - 2024-01-01\n\n## [1.0.0] - 2023-01-01\n\n[1.1.0]: https://github.com/org/repo/releases/tag/v1.1.0\n",
+    )
+    .unwrap();
+
+    assert_eq!(stdout(&keep_a_changelog(dir, &["latest"])), "2.0.0-rc.1\n");
+
+    let output = keep_a_changelog(dir, &["latest", "--stable-only", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "version": "1.1.0",
+            "date": "2024-01-01",
+            "tag": null,
+            "link": "https://github.com/org/repo/releases/tag/v1.1.0"
+        })
+    );
+
+    assert!(keep_a_changelog(dir, &["init", "--path", "empty.md"])
+        .status
+        .success());
+    assert_eq!(
+        keep_a_changelog(dir, &["latest", "--file", "empty.md"])
+            .status
+            .code(),
+        Some(1)
+    );
+}
+
+#[test]
+fn json_output() {
+    let temp = temp_dir("json-output");
+    let dir = temp.path();
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n### Added\n\n- Added X\n\n## [1.0.0] - 2023-01-01\n",
+    )
+    .unwrap();
+
+    let output = keep_a_changelog(dir, &["validate", "--format", "json"]);
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(json["path"], "CHANGELOG.md");
+    assert_eq!(json["diagnostics"][0]["rule"], "linkable-versions");
+
+    let output = keep_a_changelog(
+        dir,
+        &[
+            "release",
+            "--version",
+            "1.1.0",
+            "--date",
+            "2024-01-01",
+            "--format",
+            "json",
+        ],
+    );
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(json["version"], "1.1.0");
+    assert_eq!(json["changes"]["Added"][0], "Added X");
+}
+
+// commands that update the changelog report what they changed
+#[test]
+fn json_output_from_updates() {
+    let temp = temp_dir("json-output-from-updates");
+    let dir = temp.path();
+    let json = |args: &[&str]| {
+        let output = keep_a_changelog(dir, &[args, &["--format", "json"]].concat());
+        assert!(output.status.success(), "{args:?}");
+        serde_json::from_str::<serde_json::Value>(&stdout(&output)).unwrap()
+    };
+
+    assert_eq!(
+        json(&["init"]),
+        serde_json::json!({ "path": "CHANGELOG.md", "replaced": false })
+    );
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n### Added\n\n* Added X\n\n## [1.0.0] - 2023-01-01\n\n### Added\n\n- Added Y\n\n## [1.1.0] - 2023-02-01\n\n### Fixed\n\n- Fixed Z\n\n[1.0.0]: https://example.com/1.0.0\n",
+    )
+    .unwrap();
+
+    let output = keep_a_changelog(dir, &["fmt", "--check", "--format", "json"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&stdout(&output)).unwrap(),
+        serde_json::json!({ "path": "CHANGELOG.md", "changed": true })
+    );
+    assert_eq!(
+        json(&["sort"]),
+        serde_json::json!({
+            "moved": [
+                { "version": "1.1.0", "position": 1 },
+                { "version": "1.0.0", "position": 2 }
+            ]
+        })
+    );
+    let links = json(&["gen-links", "--repo", "https://github.com/o/r", "--force"]);
+    assert_eq!(
+        links["added"],
+        serde_json::json!([
+            {
+                "section": "Unreleased",
+                "link": "https://github.com/o/r/compare/v1.1.0...HEAD"
+            },
+            {
+                "section": "1.1.0",
+                "link": "https://github.com/o/r/compare/v1.0.0...v1.1.0"
+            }
+        ])
+    );
+    assert_eq!(
+        links["replaced"],
+        serde_json::json!([
+            { "section": "1.0.0", "link": "https://github.com/o/r/releases/tag/v1.0.0" }
+        ])
+    );
+    assert_eq!(
+        json(&["set-link", "1.0.0", "https://example.com/1.0.0"]),
+        serde_json::json!({
+            "section": "1.0.0",
+            "link": "https://example.com/1.0.0",
+            "previous_link": "https://github.com/o/r/releases/tag/v1.0.0"
+        })
+    );
+    let yanked = json(&["yank", "1.0.0"]);
+    assert_eq!(yanked["version"], "1.0.0");
+    assert_eq!(yanked["tag"], "Yanked");
+    let unreleased = json(&["demote", "1.1.0"]);
+    assert_eq!(unreleased["changes"]["Fixed"][0], "Fixed Z");
+    let unreleased = json(&["add", "--group", "removed", "Removed W"]);
+    assert_eq!(unreleased["changes"]["Removed"][0], "Removed W");
+}
+
+#[test]
+fn fmt_check() {
+    let temp = temp_dir("fmt-check");
+    let dir = temp.path();
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n### Added\n\n* Added X\n",
+    )
+    .unwrap();
+
+    let output = keep_a_changelog(dir, &["fmt", "--check"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "CHANGELOG.md\n");
+    assert!(fs::read_to_string(dir.join("CHANGELOG.md"))
+        .unwrap()
+        .contains("* Added X"));
+
+    assert!(keep_a_changelog(dir, &["fmt"]).status.success());
+    let output = keep_a_changelog(dir, &["fmt", "--check"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+
+    let prose = "# Changelog\n\n<!-- keep this -->\n\n## [Unreleased]\n\n## [1.0.0] - 2024-01-01\n\nThis release requires Rust 1.80.\n\n### Added\n\n- Added X\n";
+    fs::write(dir.join("CHANGELOG.md"), prose).unwrap();
+    for args in [&["fmt"][..], &["fmt", "--check"]] {
+        let output = keep_a_changelog(dir, args);
+        assert_eq!(output.status.code(), Some(1));
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains("'CHANGELOG.md' has content that would be lost by rewriting it"));
+        assert!(stderr.contains("CHANGELOG.md:3:1 warning: Preamble differs"));
+        assert!(stderr.contains("CHANGELOG.md:9:1 warning: Content isn't part of a change group"));
+    }
+    assert_eq!(fs::read_to_string(dir.join("CHANGELOG.md")).unwrap(), prose);
+}
+
+#[test]
+fn stdin_and_stdout() {
+    let temp = temp_dir("stdin-and-stdout");
+    let dir = temp.path();
+    let input = "# Changelog\n\n## [Unreleased]\n\n### Added\n\n* Added X\n";
+
+    let output = keep_a_changelog_with_stdin(dir, &["validate", "-"], input);
+    assert!(output.status.success());
+    let output = keep_a_changelog_with_stdin(dir, &["validate", "-"], "## Nope\n");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).starts_with("-:1:1: [error]"));
+
+    let output = keep_a_changelog_with_stdin(dir, &["fmt", "--file", "-"], input);
+    assert!(output.status.success());
+    assert!(stdout(&output).ends_with("### Added\n\n- Added X\n"));
+
+    let output = keep_a_changelog_with_stdin(
+        dir,
+        &[
+            "-f",
+            "-",
+            "release",
+            "--version",
+            "1.0.0",
+            "--date",
+            "2024-01-01",
+        ],
+        input,
+    );
+    assert!(output.status.success());
+    assert!(stdout(&output).ends_with("## [1.0.0] - 2024-01-01\n\n### Added\n\n- Added X\n"));
+
+    assert_eq!(fs::read_dir(dir).unwrap().count(), 0);
+}
+
+#[test]
+fn validate_multiple_files() {
+    let temp = temp_dir("validate-multiple-files");
+    let dir = temp.path();
+    for (name, contents) in [
+        ("a", "# Changelog\n\n## [Unreleased]\n"),
+        ("b", "## Nope\n"),
+        (
+            "c",
+            "# Changelog\n\n## [Unreleased]\n\n## [1.0.0] - 2023-01-01\n\n### Fixed\n\n- Fixed Y\n",
+        ),
+    ] {
+        fs::create_dir_all(dir.join("crates").join(name)).unwrap();
+        fs::write(dir.join("crates").join(name).join("CHANGELOG.md"), contents).unwrap();
+    }
+
+    let output = keep_a_changelog(dir, &["validate", "crates/*/CHANGELOG.md"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = stdout(&output);
+    assert!(stdout.contains("crates/a/CHANGELOG.md: ok\n"));
+    assert!(stdout.contains("crates/b/CHANGELOG.md: 1 error, 0 warnings\n"));
+    assert!(stdout.contains("crates/c/CHANGELOG.md: 0 errors, 2 warnings\n"));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("1 of 3 changelogs failed validation"));
+
+    let output = keep_a_changelog(
+        dir,
+        &[
+            "validate",
+            "crates/a/CHANGELOG.md",
+            "crates/c/CHANGELOG.md",
+            "--format",
+            "json",
+        ],
+    );
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_str(&self::stdout(&output)).unwrap();
+    assert_eq!(json[1]["path"], "crates/c/CHANGELOG.md");
+
+    let output = keep_a_changelog(dir, &["validate", "crates/a/CHANGELOG.md", "missing.md"]);
+    assert_eq!(output.status.code(), Some(3));
+
+    let output = keep_a_changelog(dir, &["validate", "packages/*/CHANGELOG.md"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = keep_a_changelog(
+        dir,
+        &[
+            "validate",
+            "crates/*/CHANGELOG.md",
+            "--exclude",
+            "crates/b/**",
+        ],
+    );
+    assert!(output.status.success());
+    assert!(!self::stdout(&output).contains("crates/b/CHANGELOG.md"));
+}
+
+#[test]
+fn diff_changelogs() {
+    let temp = temp_dir("diff-changelogs");
+    let dir = temp.path();
+    fs::write(
+        dir.join("old.md"),
+        "# Changelog\n\n## [Unreleased]\n\n### Added\n\n- Added X\n\n## [1.0.0] - 2023-01-01\n\n### Fixed\n\n- Fixed Y\n\n## [0.1.0] - 2022-01-01\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("new.md"),
+        "# Changelog\n\n## [Unreleased]\n\n### Changed\n\n- Changed Z\n\n## [1.1.0] - 2024-01-01\n\n### Added\n\n- Added X\n\n## [1.0.0] - 2023-01-01\n\n### Fixed\n\n- Fixed Y\n",
+    )
+    .unwrap();
+
+    let output = keep_a_changelog(dir, &["diff", "old.md", "new.md"]);
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "Unreleased\n  + Changed: Changed Z\n  - Added: Added X\n1.1.0 (added)\n  + Added: Added X\n0.1.0 (removed)\n"
+    );
+
+    let output = keep_a_changelog(dir, &["diff", "old.md", "new.md", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(json[1]["section"], "1.1.0");
+    assert_eq!(json[1]["status"], "added");
+    assert_eq!(json[1]["added"]["Added"][0], "Added X");
+
+    let output = keep_a_changelog(dir, &["diff", "new.md", "new.md"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+}
+
+#[test]
+fn merge_changelogs() {
+    let temp = temp_dir("merge-changelogs");
+    let dir = temp.path();
+    fs::write(
+        dir.join("a.md"),
+        "# Changelog\n\n## [Unreleased]\n\n### Added\n\n- Added X\n\n## [1.0.0] - 2023-01-01\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("b.md"),
+        "# Changelog\n\n## [Unreleased]\n\n### Added\n\n- Added Y\n\n## [1.1.0] - 2023-06-01\n\n## [1.0.0] - 2023-01-02\n",
+    )
+    .unwrap();
+
+    let output = keep_a_changelog(dir, &["merge", "a.md", "b.md", "-o", "merged.md"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "conflict: b.md: Release 1.0.0 is dated 2023-01-01 in one changelog and 2023-01-02 in the other, keeping 2023-01-01\n"
+    );
+    let merged = fs::read_to_string(dir.join("merged.md")).unwrap();
+    assert!(merged.contains("- Added X\n- Added Y\n"));
+    assert!(merged.contains("## [1.1.0] - 2023-06-01\n\n## [1.0.0] - 2023-01-01\n"));
+
+    let output = keep_a_changelog(dir, &["merge", "a.md", "b.md"]);
+    assert_eq!(stdout(&output), merged);
+
+    let output = keep_a_changelog(dir, &["merge", "a.md", "b.md", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(
+        json["unreleased"]["changes"]["Added"],
+        serde_json::json!(["Added X", "Added Y"])
+    );
+    assert_eq!(json["releases"][1]["version"], "1.0.0");
+    assert_eq!(json["releases"][1]["date"], "2023-01-01");
+
+    assert!(!keep_a_changelog(dir, &["merge", "a.md"]).status.success());
+}
+
+#[test]
+fn yank_release() {
+    let temp = temp_dir("yank-release");
+    let dir = temp.path();
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n## [1.3.0] - 2024-01-05\n\n## [1.2.0] - 2023-12-01\n",
+    )
+    .unwrap();
+
+    let output = keep_a_changelog(dir, &["yank", "1.3.0", "--note", "Broken on musl"]);
+    assert!(output.status.success());
+    assert!(keep_a_changelog(dir, &["yank", "1.2.0"]).status.success());
+    let changelog = fs::read_to_string(dir.join("CHANGELOG.md")).unwrap();
+    assert!(changelog.contains(
+        "## [1.3.0] - 2024-01-05 [YANKED]\n\n### Changed\n\n- Broken on musl\n\n## [1.2.0] - 2023-12-01 [YANKED]\n"
+    ));
+
+    assert_eq!(
+        keep_a_changelog(dir, &["yank", "9.9.9"]).status.code(),
+        Some(1)
+    );
+}
+
+#[test]
+fn gen_links() {
+    let temp = temp_dir("gen-links");
+    let dir = temp.path();
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n## [1.1.0] - 2024-02-01\n\n## [1.0.0] - 2024-01-01\n\n[1.0.0]: https://example.com/1.0.0\n",
+    )
+    .unwrap();
+
+    assert!(
+        keep_a_changelog(dir, &["gen-links", "--repo", "https://github.com/org/repo"])
+            .status
+            .success()
+    );
+    assert!(fs::read_to_string(dir.join("CHANGELOG.md")).unwrap().ends_with(
+        "[unreleased]: https://github.com/org/repo/compare/v1.1.0...HEAD\n[1.1.0]: https://github.com/org/repo/compare/v1.0.0...v1.1.0\n[1.0.0]: https://example.com/1.0.0\n"
+    ));
+
+    assert!(keep_a_changelog(
+        dir,
+        &[
+            "gen-links",
+            "--repo",
+            "https://git.example.com/org/repo",
+            "--provider",
+            "gitlab",
+            "--tag-prefix",
+            "",
+            "--force"
+        ]
+    )
+    .status
+    .success());
+    assert!(fs::read_to_string(dir.join("CHANGELOG.md")).unwrap().ends_with(
+        "[1.1.0]: https://git.example.com/org/repo/-/compare/1.0.0...1.1.0\n[1.0.0]: https://git.example.com/org/repo/-/tags/1.0.0\n"
+    ));
+
+    assert_eq!(
+        keep_a_changelog(
+            dir,
+            &["gen-links", "--repo", "https://git.example.com/org/repo"]
+        )
+        .status
+        .code(),
+        Some(2)
+    );
+}
+
+#[test]
+fn compare_versions() {
+    let temp = temp_dir("compare-versions");
+    let dir = temp.path();
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n## [1.5.0] - 2024-03-01\n\n### Fixed\n\n- Fixed Z\n\n## [1.3.0] - 2024-02-01\n\n### Fixed\n\n- Fixed Y\n\n## [1.2.0] - 2024-01-01\n\n### Added\n\n- Added X\n",
+    )
+    .unwrap();
+
+    let output = keep_a_changelog(dir, &["compare", "1.2.0", "1.5.0"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "### Fixed\n\n- Fixed Z\n- Fixed Y\n");
+
+    let output = keep_a_changelog(dir, &["compare", "1.2.0", "1.3.0", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(json["Fixed"][0], "Fixed Y");
+
+    assert_eq!(
+        keep_a_changelog(dir, &["compare", "1.1.0", "1.5.0"])
+            .status
+            .code(),
+        Some(1)
+    );
+}
+
+#[test]
+fn sort_releases() {
+    let temp = temp_dir("sort-releases");
+    let dir = temp.path();
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n## [2.0.0] - 2024-03-01\n\n## [1.0.0] - 2024-01-01\n\n## [1.1.0] - 2024-02-01\n",
+    )
+    .unwrap();
+
+    let output = keep_a_changelog(dir, &["sort", "--check"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("moved: 1.1.0 to position 2\n"));
+
+    let output = keep_a_changelog(dir, &["sort"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "moved: 1.1.0 to position 2\nmoved: 1.0.0 to position 3\n"
+    );
+    assert!(fs::read_to_string(dir.join("CHANGELOG.md"))
+        .unwrap()
+        .ends_with(
+            "## [2.0.0] - 2024-03-01\n\n## [1.1.0] - 2024-02-01\n\n## [1.0.0] - 2024-01-01\n"
+        ));
+
+    assert!(keep_a_changelog(dir, &["sort", "--check"]).status.success());
+}
+
+#[test]
+fn demote_release() {
+    let temp = temp_dir("demote-release");
+    let dir = temp.path();
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n## [1.5.0-rc.1] - 2024-02-01\n\n### Added\n\n- Added Y\n\n## [1.4.0] - 2024-01-05\n",
+    )
+    .unwrap();
+
+    assert!(keep_a_changelog(dir, &["demote", "1.5.0-rc.1"])
+        .status
+        .success());
+    assert!(fs::read_to_string(dir.join("CHANGELOG.md"))
+        .unwrap()
+        .ends_with("## [Unreleased]\n\n### Added\n\n- Added Y\n\n## [1.4.0] - 2024-01-05\n"));
+
+    assert_eq!(
+        keep_a_changelog(dir, &["demote", "1.5.0-rc.1"])
+            .status
+            .code(),
+        Some(1)
+    );
+}
+
+#[test]
+fn unreleased_changes() {
+    let temp = temp_dir("unreleased-changes");
+    let dir = temp.path();
+    assert!(keep_a_changelog(dir, &["init"]).status.success());
+
+    let output = keep_a_changelog(dir, &["unreleased"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+    let output = keep_a_changelog(dir, &["unreleased", "--require-nonempty"]);
+    assert_eq!(output.status.code(), Some(1));
+
+    assert!(keep_a_changelog(dir, &["add", "-g", "added", "Added X"])
+        .status
+        .success());
+    let output = keep_a_changelog(dir, &["unreleased", "--require-nonempty"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "### Added\n\n- Added X\n");
+
+    let output = keep_a_changelog(dir, &["unreleased", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(json["changes"]["Added"][0], "Added X");
+
+    let code = |args: &[&str]| keep_a_changelog(dir, args).status.code();
+    assert_eq!(code(&["unreleased", "--min-entries", "1"]), Some(0));
+    assert_eq!(code(&["unreleased", "--min-entries", "2"]), Some(1));
+    assert_eq!(code(&["unreleased", "--require-group", "added"]), Some(0));
+    assert_eq!(code(&["unreleased", "--require-group", "fixed"]), Some(1));
+}
+
+#[test]
+fn set_link() {
+    let temp = temp_dir("set-link");
+    let dir = temp.path();
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n## [1.2.0] - 2024-01-05\n\n[1.2.0]: https://github.com/org/repo/compare/v1.1.0...v1.2.0\n",
+    )
+    .unwrap();
+
+    for args in [
+        [
+            "set-link",
+            "unreleased",
+            "https://github.com/org/repo/compare/v1.2.0...HEAD",
+        ],
+        [
+            "set-link",
+            "1.2.0",
+            "https://github.com/org/repo/releases/tag/v1.2.0",
+        ],
+    ] {
+        assert!(keep_a_changelog(dir, &args).status.success());
+    }
+    let changelog = fs::read_to_string(dir.join("CHANGELOG.md")).unwrap();
+    assert!(changelog.ends_with(
+        "[unreleased]: https://github.com/org/repo/compare/v1.2.0...HEAD\n[1.2.0]: https://github.com/org/repo/releases/tag/v1.2.0\n"
+    ));
+
+    let output = keep_a_changelog(dir, &["set-link", "9.9.9", "https://example.com"]);
+    assert_eq!(output.status.code(), Some(1));
+    let output = keep_a_changelog(dir, &["set-link", "1.2.0", "not a link"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn release_notes() {
+    let temp = temp_dir("release-notes");
+    let dir = temp.path();
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n## [1.1.0] - 2024-01-05\n\n### Added\n\n- Added X\n\n## [1.0.0] - 2023-01-01\n\n### Fixed\n\n- Fixed Y\n",
+    )
+    .unwrap();
+
+    let output = keep_a_changelog(dir, &["release-notes"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "### Added\n\n- Added X\n");
+    assert_eq!(
+        stdout(&keep_a_changelog(dir, &["release-notes", "--latest"])),
+        "### Added\n\n- Added X\n"
+    );
+
+    let output = keep_a_changelog(
+        dir,
+        &[
+            "release-notes",
+            "--version",
+            "1.0.0",
+            "--output",
+            "notes.md",
+        ],
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        fs::read_to_string(dir.join("notes.md")).unwrap(),
+        "### Fixed\n\n- Fixed Y\n"
+    );
+
+    let output = keep_a_changelog(dir, &["release-notes", "--version", "2.0.0"]);
+    assert_eq!(output.status.code(), Some(1));
+
+    assert!(
+        keep_a_changelog(dir, &["add", "-g", "changed", "Changed Z"])
+            .status
+            .success()
+    );
+    assert_eq!(
+        stdout(&keep_a_changelog(dir, &["release-notes", "--unreleased"])),
+        "### Changed\n\n- Changed Z\n"
+    );
+}
+
+#[test]
+fn check_version() {
+    let temp = temp_dir("check-version");
+    let dir = temp.path();
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n## [1.4.2] - 2024-01-05\n\n## [1.4.1] - 2023-12-01\n",
+    )
+    .unwrap();
+
+    let code = |args: &[&str]| keep_a_changelog(dir, args).status.code();
+    assert_eq!(code(&["check-version", "1.4.2"]), Some(0));
+    assert_eq!(
+        code(&["check-version", "v1.4.2", "--must-be-latest"]),
+        Some(0)
+    );
+    assert_eq!(code(&["check-version", "1.4.1"]), Some(0));
+    assert_eq!(
+        code(&["check-version", "1.4.1", "--must-be-latest"]),
+        Some(1)
+    );
+    assert_eq!(
+        code(&["check-version", "1.4.2", "--released-today"]),
+        Some(1)
+    );
+    assert_eq!(code(&["check-version", "1.4.3"]), Some(1));
+    assert_eq!(code(&["check-version", "latest"]), Some(2));
+
+    let output = keep_a_changelog(dir, &["check-version", "1.4.2", "--format", "json"]);
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(json["version"], "1.4.2");
+    assert_eq!(json["date"], "2024-01-05");
+    assert_eq!(
+        stdout(&keep_a_changelog(dir, &["check-version", "1.4.2"])),
+        ""
+    );
+
+    fs::write(dir.join("VERSION"), "1.4.2\n").unwrap();
+    fs::write(
+        dir.join("Dockerfile"),
+        "FROM alpine:3.19.1\nLABEL version=\"1.4.1\"\n",
+    )
+    .unwrap();
+    assert_eq!(
+        code(&["check-version", "--version-file", "VERSION"]),
+        Some(0)
+    );
+    assert_eq!(
+        code(&["check-version", "--version-file", "Dockerfile"]),
+        Some(1)
+    );
+    assert_eq!(
+        code(&[
+            "check-version",
+            "--version-file",
+            "Dockerfile",
+            "--pattern",
+            r#"version="([^"]+)""#
+        ]),
+        Some(0)
+    );
+    assert_eq!(
+        code(&[
+            "check-version",
+            "--version-file",
+            "VERSION",
+            "--pattern",
+            "release-(.+)"
+        ]),
+        Some(1)
+    );
+    assert_eq!(code(&["check-version"]), Some(2));
+}
+
+#[test]
+fn bump_version() {
+    let temp = temp_dir("bump-version");
+    let dir = temp.path();
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n### Added\n\n- Added X\n\n## [1.3.2] - 2024-01-05\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("Cargo.toml"),
+        "[package] # the package\nname = \"example\"\nversion = \"1.3.2\" # the version\n\n[dependencies]\nserde = { version = \"1\" }\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("package.json"),
+        "{\n  \"name\": \"example\",\n  \"publishConfig\": { \"version\": \"0.0.0\" },\n  \"version\": \"1.3.2\"\n}\n",
+    )
+    .unwrap();
+
+    let output = keep_a_changelog(dir, &["bump"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "1.4.0\n");
+    assert!(fs::read_to_string(dir.join("CHANGELOG.md"))
+        .unwrap()
+        .contains("## [1.4.0] - "));
+    assert_eq!(
+        fs::read_to_string(dir.join("Cargo.toml")).unwrap(),
+        "[package] # the package\nname = \"example\"\nversion = \"1.4.0\" # the version\n\n[dependencies]\nserde = { version = \"1\" }\n"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("package.json")).unwrap(),
+        "{\n  \"name\": \"example\",\n  \"publishConfig\": { \"version\": \"0.0.0\" },\n  \"version\": \"1.4.0\"\n}\n"
+    );
+
+    let output = keep_a_changelog(dir, &["bump"]);
+    assert_eq!(output.status.code(), Some(1), "nothing to release");
+
+    fs::write(
+        dir.join("pyproject.toml"),
+        "[project]\nname = \"example\"\n",
+    )
+    .unwrap();
+    let output = keep_a_changelog(dir, &["bump", "--level", "major"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(fs::read_to_string(dir.join("Cargo.toml"))
+        .unwrap()
+        .contains("version = \"1.4.0\""));
+
+    let output = keep_a_changelog(
+        dir,
+        &[
+            "bump",
+            "--level",
+            "major",
+            "--no-manifest",
+            "--format",
+            "json",
+        ],
+    );
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "previous_version": "1.4.0",
+            "version": "2.0.0",
+            "bump": "major",
+            "manifests": []
+        })
+    );
+}
+
+#[test]
+fn bump_workspace_version() {
+    let temp = temp_dir("bump-workspace-version");
+    let dir = temp.path();
+    let member = dir.join("crates").join("example");
+    fs::create_dir_all(&member).unwrap();
+    fs::write(
+        dir.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nversion = \"0.3.1\"\n",
+    )
+    .unwrap();
+    fs::write(
+        member.join("Cargo.toml"),
+        "[package]\nname = \"example\"\nversion.workspace = true\n",
+    )
+    .unwrap();
+    fs::write(
+        member.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n### Removed\n\n- Removed X\n\n## [0.3.1] - 2024-01-05\n",
+    )
+    .unwrap();
+
+    let output = keep_a_changelog(&member, &["bump", "--format", "json"]);
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(
+        json["version"], "0.4.0",
+        "breaking changes to 0.x are minor"
+    );
+    assert_eq!(json["bump"], "minor");
+    assert_eq!(
+        json["manifests"],
+        serde_json::json!([dir.canonicalize().unwrap().join("Cargo.toml")])
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("Cargo.toml")).unwrap(),
+        "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nversion = \"0.4.0\"\n"
+    );
+    assert_eq!(
+        fs::read_to_string(member.join("Cargo.toml")).unwrap(),
+        "[package]\nname = \"example\"\nversion.workspace = true\n"
+    );
+}
+
+#[test]
+fn stats() {
+    let temp = temp_dir("stats");
+    let dir = temp.path();
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n## [1.2.0] - 2024-03-01\n\n### Added\n\n- Added X by @octocat\n\n## [1.1.0] - 2024-01-01\n\n### Fixed\n\n- Fixed Y by @hubot\n\n## [1.0.0] - 2023-12-01\n",
+    )
+    .unwrap();
+
+    let output = keep_a_changelog(dir, &["stats"]);
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "Releases: 3 (0 yanked)\nReleased: 2023-12-01 to 2024-03-01\nDays between releases: 45.5 on average, 31 to 60\nEntries:\n  Added: 1\n  Changed: 0\n  Deprecated: 0\n  Removed: 0\n  Fixed: 1\n  Security: 0\nContributors: 2\n"
+    );
+
+    let output = keep_a_changelog(dir, &["stats", "--since", "1.1.0", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(json["releases"], 2);
+    assert_eq!(json["days_between_releases"], serde_json::json!([60]));
+    assert_eq!(json["entries"]["Fixed"], 1);
+}
+
+#[test]
+fn grep_entries() {
+    let temp = temp_dir("grep-entries");
+    let dir = temp.path();
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n### Fixed\n\n- Fixed another OOM\n\n## [2.1.0] - 2024-03-01\n\n### Added\n\n- Added an OOM killer\n\n### Fixed\n\n- Fixed an oom when parsing\n\n## [1.0.0] - 2023-12-01\n\n### Fixed\n\n- Fixed OOM on startup\n",
+    )
+    .unwrap();
+
+    let output = keep_a_changelog(dir, &["grep", "OOM"]);
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "Unreleased Fixed: Fixed another OOM\n2.1.0 (2024-03-01) Added: Added an OOM killer\n1.0.0 (2023-12-01) Fixed: Fixed OOM on startup\n"
+    );
+
+    let output = keep_a_changelog(
+        dir,
+        &["grep", "-i", "oom", "--group", "fixed", "--since", "2.0.0"],
+    );
+    assert_eq!(
+        stdout(&output),
+        "Unreleased Fixed: Fixed another OOM\n2.1.0 (2024-03-01) Fixed: Fixed an oom when parsing\n"
+    );
+
+    let output = keep_a_changelog(dir, &["grep", "startup|another", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!([
+            {
+                "version": null,
+                "date": null,
+                "change_group": "Fixed",
+                "entry": "Fixed another OOM"
+            },
+            {
+                "version": "1.0.0",
+                "date": "2023-12-01",
+                "change_group": "Fixed",
+                "entry": "Fixed OOM on startup"
+            }
+        ])
+    );
+
+    assert_eq!(
+        keep_a_changelog(dir, &["grep", "segfault"]).status.code(),
+        Some(1)
+    );
+    let output = keep_a_changelog(dir, &["grep", "segfault", "--format", "json"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "[]\n");
+    assert_eq!(keep_a_changelog(dir, &["grep", "("]).status.code(), Some(2));
+}
+
+#[test]
+fn to_json_and_from_json() {
+    let temp = temp_dir("to-json-and-from-json");
+    let dir = temp.path();
+    let changelog = "# Changelog\r\n\r\nAll notable changes to this project will be documented in this file.\r\n\r\nThe format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),\r\nand this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).\r\n\r\n## [Unreleased]\r\n\r\n### Added\r\n\r\n- Added X\r\n\r\n## [1.0.0] - 2023-12-01 [YANKED]\r\n\r\n### Fixed\r\n\r\n- Fixed Y\r\n\r\n[unreleased]: https://github.com/org/repo/compare/v1.0.0...HEAD\r\n[1.0.0]: https://github.com/org/repo/releases/tag/v1.0.0\r\n";
+    fs::write(dir.join("CHANGELOG.md"), changelog).unwrap();
+
+    let output = keep_a_changelog(dir, &["to-json", "-o", "changelog.json"]);
+    assert!(output.status.success());
+    let json = fs::read_to_string(dir.join("changelog.json")).unwrap();
+    assert_eq!(stdout(&keep_a_changelog(dir, &["to-json"])), json);
+
+    let output = keep_a_changelog_with_stdin(dir, &["--file", "-", "from-json"], &json);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), changelog);
+
+    let output = keep_a_changelog(dir, &["--file", "NEW.md", "from-json", "changelog.json"]);
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(dir.join("NEW.md")).unwrap(), changelog);
+
+    fs::write(dir.join("invalid.json"), "{\"releases\": []}").unwrap();
+    let output = keep_a_changelog(dir, &["--file", "NEW.md", "from-json", "invalid.json"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn notes_template() {
+    let temp = temp_dir("notes-template");
+    let dir = temp.path();
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n### Fixed\n\n- Fixed Z\n\n## [1.2.0] - 2024-03-01\n\n### Added\n\n- Added <X>\n- Added Y\n\n### Fixed\n\n- Fixed W\n\n[1.2.0]: https://github.com/org/repo/releases/tag/v1.2.0\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("announcement.tera"),
+        "Version {{ version }} is out ({{ date }})!\n{% for change in changes %}\n{{ change.group }}:\n{% for entry in change.entries %}* {{ entry }}\n{% endfor %}{% endfor %}\nSee {{ link }}\n",
+    )
+    .unwrap();
+
+    let output = keep_a_changelog(dir, &["notes", "1.2.0", "--template", "announcement.tera"]);
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "Version 1.2.0 is out (2024-03-01)!\n\nAdded:\n* Added <X>\n* Added Y\n\nFixed:\n* Fixed W\n\nSee https://github.com/org/repo/releases/tag/v1.2.0\n"
+    );
+
+    fs::write(
+        dir.join("unreleased.tera"),
+        "{{ section }}: {{ changes | length }}",
+    )
+    .unwrap();
+    let output = keep_a_changelog(dir, &["notes", "-t", "unreleased.tera"]);
+    assert_eq!(stdout(&output), "Unreleased: 1");
+
+    fs::write(dir.join("broken.tera"), "{{ version").unwrap();
+    let output = keep_a_changelog(dir, &["notes", "1.2.0", "-t", "broken.tera"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn install_hook() {
+    let temp = temp_dir("install-hook");
+    let dir = temp.path();
+    let git = |args: &[&str]| git(dir, args);
+    assert!(git(&["init", "-q"]).status.success());
+
+    let output = keep_a_changelog(dir, &["install-hook"]);
+    assert!(output.status.success());
+    let hook = dir.join(".git/hooks/pre-commit");
+    assert!(fs::read_to_string(&hook)
+        .unwrap()
+        .contains("changelog='CHANGELOG.md'\n"));
+    assert_eq!(
+        keep_a_changelog(dir, &["install-hook"]).status.code(),
+        Some(1)
+    );
+    assert!(keep_a_changelog(dir, &["install-hook", "--force"])
+        .status
+        .success());
+
+    fs::write(dir.join("CHANGELOG.md"), "## Not a release header\n").unwrap();
+    assert!(git(&["add", "CHANGELOG.md"]).status.success());
+    assert!(!git(&["commit", "-q", "-m", "Invalid"]).status.success());
+
+    assert!(keep_a_changelog(dir, &["init", "--force"]).status.success());
+    assert!(git(&["add", "CHANGELOG.md"]).status.success());
+    assert!(git(&["commit", "-q", "-m", "Valid"]).status.success());
+
+    let output = keep_a_changelog(dir, &["install-hook", "--pre-commit-config"]);
+    assert!(stdout(&output).contains("entry: keep_a_changelog --file 'CHANGELOG.md' validate\n"));
+    assert!(stdout(&output).contains("files: ^CHANGELOG\\.md$\n"));
+
+    assert!(keep_a_changelog(
+        dir,
+        &["--file", "./CHANGELOG.md", "install-hook", "--force"]
+    )
+    .status
+    .success());
+    assert!(fs::read_to_string(&hook)
+        .unwrap()
+        .contains("changelog='CHANGELOG.md'\n"));
+    fs::write(dir.join("CHANGELOG.md"), "## Not a release header\n").unwrap();
+    assert!(git(&["add", "CHANGELOG.md"]).status.success());
+    assert!(!git(&["commit", "-q", "-m", "Invalid"]).status.success());
+    assert!(git(&["reset", "-q", "--hard"]).status.success());
+
+    fs::create_dir(dir.join("docs")).unwrap();
+    let output = keep_a_changelog(
+        &dir.join("docs"),
+        &[
+            "--file",
+            "../CHANGELOG.md",
+            "install-hook",
+            "--pre-commit-config",
+        ],
+    );
+    assert!(stdout(&output).contains("entry: keep_a_changelog --file 'CHANGELOG.md' validate\n"));
+
+    // the hook goes into the changelog's repository, not the one the command is run in
+    fs::remove_file(&hook).unwrap();
+    let elsewhere = temp_dir("install-hook-elsewhere");
+    assert!(crate::git(elsewhere.path(), &["init", "-q"])
+        .status
+        .success());
+    let changelog = dir.join("docs/../CHANGELOG.md");
+    let output = keep_a_changelog(
+        elsewhere.path(),
+        &["--file", changelog.to_str().unwrap(), "install-hook"],
+    );
+    assert!(output.status.success());
+    assert!(fs::read_to_string(&hook)
+        .unwrap()
+        .contains("changelog='CHANGELOG.md'\n"));
+    assert!(!elsewhere.path().join(".git/hooks/pre-commit").exists());
+}
+
+#[test]
+fn lint_rules() {
+    let temp = temp_dir("lint-rules");
+    let dir = temp.path();
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n## [1.0.0] - 2023-01-01\n\n### Fixed\n\n- fix: Y\n",
+    )
+    .unwrap();
+    let lint = |args: &[&str]| {
+        let output = keep_a_changelog(dir, &[&["lint", "--error-format", "short"], args].concat());
+        let codes = stdout(&output)
+            .lines()
+            .map(|line| line.split(": ").nth(1).unwrap().to_string())
+            .collect::<Vec<_>>();
+        (output.status.code(), codes)
+    };
+
+    assert_eq!(
+        lint(&[]),
+        (
+            Some(0),
+            vec![
+                "warning[linkable-versions]".to_string(),
+                "warning[linkable-versions]".to_string(),
+                "warning[no-commit-log-dumps]".to_string(),
+            ]
+        )
+    );
+    assert_eq!(
+        lint(&["--only", "no-commit-log-dumps"]),
+        (Some(0), vec!["warning[no-commit-log-dumps]".to_string()])
+    );
+    assert_eq!(
+        lint(&[
+            "--skip",
+            "linkable-versions",
+            "--deny",
+            "no-commit-log-dumps"
+        ]),
+        (Some(1), vec!["error[no-commit-log-dumps]".to_string()])
+    );
+    assert_eq!(lint(&["--skip", "unknown-rule"]).0, Some(2));
+
+    let output = keep_a_changelog(dir, &["lint", "--list-checks"]);
+    assert!(output.status.success());
+    let checks: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(checks[0]["code"], "latest-version-first");
+    assert_eq!(checks[0]["level"], "warning");
+    assert_eq!(checks.as_array().unwrap().len(), 7);
+
+    fs::write(dir.join("CHANGELOG.md"), "## Not a release header\n").unwrap();
+    assert_eq!(
+        lint(&["--only", "linkable-versions"]),
+        (Some(1), vec!["error".to_string()])
+    );
+}
+
+// every link is dead on a server that answers 404 to everything
+#[cfg(feature = "link-check")]
+#[test]
+fn lint_check_links() {
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            write!(
+                stream,
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            )
+            .unwrap();
+        }
+    });
+
+    let temp = temp_dir("lint-check-links");
+    let dir = temp.path();
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        format!("# Changelog\n\n## [Unreleased]\n\n[unreleased]: http://{address}/compare/v1.0.0...HEAD\n"),
+    )
+    .unwrap();
+
+    let output = keep_a_changelog(dir, &["lint", "--error-format", "short"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+
+    let output = keep_a_changelog(dir, &["lint", "--error-format", "short", "--check-links"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).starts_with(
+        "CHANGELOG.md:5:1: error[linkable-versions]: Link for Unreleased doesn't resolve: "
+    ));
+
+    let output = keep_a_changelog(
+        dir,
+        &["lint", "--check-links", "--skip", "linkable-versions"],
+    );
+    assert!(output.status.success());
+}
+
+#[test]
+fn verify_tags() {
+    let temp = temp_dir("verify-tags");
+    let dir = temp.path();
+    fs::write(
+        dir.join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n## [1.2.0] - 2024-03-01\n\n## [1.1.0] - 2024-01-01\n\n## [1.0.0] - 2023-12-01\n",
+    )
+    .unwrap();
+    assert!(git(dir, &["init", "-q"]).status.success());
+    assert!(git(dir, &["add", "CHANGELOG.md"]).status.success());
+    assert!(
+        git(dir, &["commit", "-q", "--no-verify", "-m", "Changelog"])
+            .status
+            .success()
+    );
+    for tag in [
+        "v1.0.0",
+        "v1.1.0",
+        "v1.3.0",
+        "not-a-version",
+        "release-1.2.0",
+    ] {
+        assert!(git(dir, &["tag", tag]).status.success());
+    }
+
+    let output = keep_a_changelog(dir, &["verify-tags"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "1.2.0: no tag named v1.2.0\nv1.3.0: no release 1.3.0 in the changelog\n"
+    );
+
+    let output = keep_a_changelog(dir, &["verify-tags", "--format", "json"]);
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "untagged_releases": [{ "version": "1.2.0", "tag": "v1.2.0" }],
+            "unreleased_tags": [{ "version": "1.3.0", "tag": "v1.3.0" }]
+        })
+    );
+
+    let output = keep_a_changelog(dir, &["verify-tags", "--tag-template", "release-{version}"]);
+    assert_eq!(
+        stdout(&output),
+        "1.1.0: no tag named release-1.1.0\n1.0.0: no tag named release-1.0.0\n"
+    );
+
+    assert!(git(dir, &["tag", "-d", "v1.3.0"]).status.success());
+    assert!(git(dir, &["tag", "v1.2.0"]).status.success());
+    assert!(keep_a_changelog(dir, &["verify-tags"]).status.success());
+
+    // the tags come from the changelog's repository, not the one the command is run in
+    let elsewhere = temp_dir("verify-tags-elsewhere");
+    assert!(git(elsewhere.path(), &["init", "-q"]).status.success());
+    let changelog = dir.join("CHANGELOG.md");
+    let output = keep_a_changelog(
+        elsewhere.path(),
+        &["--file", changelog.to_str().unwrap(), "verify-tags"],
+    );
+    assert!(output.status.success(), "{}", stdout(&output));
+
+    assert_eq!(
+        keep_a_changelog(dir, &["verify-tags", "--tag-template", "v"])
+            .status
+            .code(),
+        Some(2)
+    );
+}
+
+#[test]
+fn validate_watch() {
+    let temp = temp_dir("validate-watch");
+    let dir = temp.path();
+    assert!(keep_a_changelog(dir, &["init"]).status.success());
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_keep_a_changelog"))
+        .current_dir(dir)
+        .args(["validate", "--watch", "--error-format", "short"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // the output is read as it's printed so the test only waits as long as the watcher takes
+    let (sender, receiver) = std::sync::mpsc::channel();
+    for (stream, reader) in [
+        (
+            "stdout",
+            Box::new(child.stdout.take().unwrap()) as Box<dyn std::io::Read + Send>,
+        ),
+        ("stderr", Box::new(child.stderr.take().unwrap())),
+    ] {
+        let sender = sender.clone();
+        std::thread::spawn(move || {
+            for line in std::io::BufRead::lines(std::io::BufReader::new(reader)) {
+                let _ = sender.send((stream, line.unwrap()));
+            }
+        });
+    }
+    let next_line = || {
+        receiver
+            .recv_timeout(std::time::Duration::from_secs(30))
+            .unwrap()
+    };
+
+    assert_eq!(
+        next_line(),
+        (
+            "stderr",
+            "No problems found, watching for changes".to_string()
+        )
+    );
+    // the same length as the valid changelog so only the contents tell the change apart
+    let valid = fs::read_to_string(dir.join("CHANGELOG.md")).unwrap();
+    let invalid = format!("## Not a release header\n{}", " ".repeat(valid.len() - 24));
+    assert_eq!(invalid.len(), valid.len());
+    fs::write(dir.join("CHANGELOG.md"), &invalid).unwrap();
+    let mut lines = (0..4).map(|_| next_line()).collect::<Vec<_>>();
+    child.kill().unwrap();
+    child.wait().unwrap();
+    lines.sort();
+
+    assert_eq!(
+        lines,
+        [
+            ("stderr", "Watching for changes".to_string()),
+            ("stderr", "error: 'CHANGELOG.md' has 1 problem".to_string()),
+            ("stdout", String::new()),
+            ("stdout", "CHANGELOG.md:1:1: error: Release header did not match the expected format; Expected: [Unreleased] | [<version>] - <yyyy>-<mm>-<dd> | [<version>] - <yyyy>-<mm>-<dd> [<tag>]; Value: Not a release header".to_string()),
+        ]
+    );
+
+    let output = keep_a_changelog(dir, &["validate", "--watch", "-"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn import_from_tags() {
+    let temp = temp_dir("import-from-tags");
+    let dir = temp.path();
+    assert!(git(dir, &["init", "-q"]).status.success());
+    for (subject, tag) in [
+        ("feat: first release", Some("v1.0.0")),
+        ("fix(parser): crash on empty input", None),
+        ("chore: update dependencies", Some("v1.1.0")),
+        ("feat: add a json flag", None),
+    ] {
+        assert!(git(
+            dir,
+            &[
+                "commit",
+                "-q",
+                "--allow-empty",
+                "--no-verify",
+                "-m",
+                subject
+            ]
+        )
+        .status
+        .success());
+        if let Some(tag) = tag {
+            assert!(git(dir, &["tag", tag]).status.success());
+        }
+    }
+
+    assert!(
+        keep_a_changelog(dir, &["import", "--from-tags", "--conventional"])
+            .status
+            .success()
+    );
+    let changelog = fs::read_to_string(dir.join("CHANGELOG.md")).unwrap();
+    let today = stdout(&git(dir, &["log", "-1", "--format=%cs"]));
+    let today = today.trim();
+    assert!(changelog.ends_with(&format!(
+        "## [Unreleased]\n\n### Added\n\n- Add a json flag\n\n## [1.1.0] - {today}\n\n### Fixed\n\n- (parser) Crash on empty input\n\n## [1.0.0] - {today}\n\n### Added\n\n- First release\n"
+    )));
+
+    assert_eq!(
+        keep_a_changelog(dir, &["import", "--from-tags"])
+            .status
+            .code(),
+        Some(1)
+    );
+    assert!(keep_a_changelog(dir, &["import", "--from-tags", "--force"])
+        .status
+        .success());
+    assert!(fs::read_to_string(dir.join("CHANGELOG.md"))
+        .unwrap()
+        .ends_with(&format!(
+            "## [Unreleased]\n\n## [1.1.0] - {today}\n\n## [1.0.0] - {today}\n"
+        )));
+}
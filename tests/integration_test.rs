@@ -1,5 +1,6 @@
 #![allow(clippy::unwrap_used)]
 #![allow(unused_crate_dependencies)]
+#![allow(missing_docs)]
 
 use keep_a_changelog_file::{ChangeGroup, Changelog, PromoteOptions};
 
@@ -161,3 +162,67 @@ and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0
 
     assert!(changelog.parse::<Changelog>().is_err());
 }
+
+#[test]
+fn generating_missing_links() {
+    let mut changelog: Changelog = "\
+# Changelog
+
+All notable changes to this project will be documented in this file.
+
+The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),
+and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).
+
+## [Unreleased]
+
+## [0.0.2] - 2023-02-01
+
+### Fixed
+
+- Fixed feature Y
+
+## [0.0.1] - 2023-01-01
+
+### Added
+
+- Added feature Y
+
+[0.0.1]: https://gitlab.com/my-org/my-project/-/tags/0.0.1\n"
+        .parse()
+        .unwrap();
+
+    changelog.generate_links(&"https://gitlab.com/my-org/my-project".parse().unwrap());
+
+    assert!(changelog.to_string().ends_with(
+        "\
+[unreleased]: https://gitlab.com/my-org/my-project/-/compare/v0.0.2...HEAD
+[0.0.2]: https://gitlab.com/my-org/my-project/-/compare/v0.0.1...v0.0.2
+[0.0.1]: https://gitlab.com/my-org/my-project/-/tags/0.0.1\n"
+    ));
+}
+
+#[test]
+fn regenerating_stale_links() {
+    let mut changelog: Changelog = "\
+# Changelog
+
+## [Unreleased]
+
+## [0.0.2] - 2023-02-01
+
+## [0.0.1] - 2023-01-01
+
+[unreleased]: https://github.com/old-org/my-project/compare/v0.0.2...HEAD
+[0.0.1]: https://github.com/old-org/my-project/releases/tag/v0.0.1\n"
+        .parse()
+        .unwrap();
+
+    changelog.regenerate_links(&"https://github.com/my-org/my-project".parse().unwrap());
+
+    assert!(changelog.to_string().ends_with(
+        "\
+[unreleased]: https://github.com/my-org/my-project/compare/v0.0.2...HEAD
+[0.0.2]: https://github.com/my-org/my-project/compare/v0.0.1...v0.0.2
+[0.0.1]: https://github.com/my-org/my-project/releases/tag/v0.0.1\n"
+    ));
+}
@@ -0,0 +1,14 @@
+#![allow(clippy::unwrap_used)]
+#![allow(unused_crate_dependencies)]
+#![allow(missing_docs)]
+#![cfg(feature = "test-util")]
+
+use keep_a_changelog_file::{test_util, Changelog};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn changelog_round_trips(changelog in test_util::changelog()) {
+        prop_assert_eq!(changelog.to_string().parse::<Changelog>().unwrap(), changelog);
+    }
+}